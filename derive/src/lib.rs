@@ -0,0 +1,185 @@
+//! Derive macros for [zenity-dialog](https://crates.io/crates/zenity-dialog), enabled through
+//! its `derive` feature. See `zenity_dialog::ToArgVector` for the attributes.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, quote_spanned};
+use syn::{parse_macro_input, spanned::Spanned, Data, DeriveInput, Fields, LitStr, Member, Type};
+
+/// Derive `ToArgVector` from `#[zenity(...)]` attributes. The struct names its Zenity option
+/// with `#[zenity(subcommand = "--my-dialog")]`, and its fields are passed in order: a `bool`
+/// marked `#[zenity(flag = "--my-flag")]` when it's `true`, and an `Option` of a `Display` type
+/// marked `#[zenity(arg = "--my-opt")]` as `--my-opt=value` when it's set. Other fields are
+/// skipped.
+#[proc_macro_derive(ToArgVector, attributes(zenity))]
+pub fn derive_to_arg_vector(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// How a field is passed to Zenity.
+enum Passing {
+    /// A bool passed as the flag when set
+    Flag(LitStr),
+    /// An optional value passed as `option=value`
+    Arg(LitStr),
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new(
+            input.ident.span(),
+            "ToArgVector can only be derived for structs",
+        ));
+    };
+
+    let subcommand = subcommand(input)?;
+    let mut pushes = Vec::new();
+    let fields = match &data.fields {
+        Fields::Named(fields) => fields.named.iter().collect(),
+        Fields::Unnamed(fields) => fields.unnamed.iter().collect(),
+        Fields::Unit => Vec::new(),
+    };
+
+    for (index, field) in fields.into_iter().enumerate() {
+        let member = match &field.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(index.into()),
+        };
+
+        let Some(option) = field_option(field)? else {
+            continue;
+        };
+
+        pushes.push(match option {
+            Passing::Flag(flag) => {
+                if !is_type(&field.ty, "bool") {
+                    return Err(syn::Error::new(
+                        field.ty.span(),
+                        "`flag` can only be used on bool fields",
+                    ));
+                }
+
+                quote_spanned! {field.span()=>
+                    if self.#member {
+                        args.push(::std::ffi::OsString::from(#flag));
+                    }
+                }
+            }
+            Passing::Arg(arg) => {
+                if !is_type(&field.ty, "Option") {
+                    return Err(syn::Error::new(
+                        field.ty.span(),
+                        "`arg` can only be used on Option fields",
+                    ));
+                }
+
+                quote_spanned! {field.span()=>
+                    if let ::std::option::Option::Some(ref value) = self.#member {
+                        args.push(::std::ffi::OsString::from(
+                            ::std::format!("{}={}", #arg, value),
+                        ));
+                    }
+                }
+            }
+        });
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::zenity_dialog::__private::ToArgVector for #name #ty_generics
+        #where_clause
+        {
+            fn to_argv(&self) -> ::std::vec::Vec<::std::ffi::OsString> {
+                let mut args = ::std::vec![::std::ffi::OsString::from(#subcommand)];
+                #(#pushes)*
+                args
+            }
+        }
+    })
+}
+
+/// The Zenity option selecting the dialog, from the struct's `#[zenity(subcommand = "...")]`.
+fn subcommand(input: &DeriveInput) -> syn::Result<LitStr> {
+    let mut subcommand = None;
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("zenity"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if !meta.path.is_ident("subcommand") {
+                return Err(meta.error("expected `subcommand = \"--option\"`"));
+            }
+
+            if subcommand.is_some() {
+                return Err(meta.error("duplicate `subcommand`"));
+            }
+
+            subcommand = Some(option_name(meta.value()?.parse()?)?);
+            Ok(())
+        })?;
+    }
+
+    subcommand.ok_or_else(|| {
+        syn::Error::new(
+            input.ident.span(),
+            "missing `#[zenity(subcommand = \"--option\")]` naming the dialog",
+        )
+    })
+}
+
+/// How the field is passed to Zenity, from its `#[zenity(...)]`, or [None] if it isn't.
+fn field_option(field: &syn::Field) -> syn::Result<Option<Passing>> {
+    let mut option = None;
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("zenity"))
+    {
+        attr.parse_nested_meta(|meta| {
+            let kind: fn(LitStr) -> Passing = if meta.path.is_ident("flag") {
+                Passing::Flag
+            } else if meta.path.is_ident("arg") {
+                Passing::Arg
+            } else {
+                return Err(meta.error("expected `flag = \"--option\"` or `arg = \"--option\"`"));
+            };
+
+            if option.is_some() {
+                return Err(meta.error("a field can only be passed as one option"));
+            }
+
+            option = Some(kind(option_name(meta.value()?.parse()?)?));
+            Ok(())
+        })?;
+    }
+
+    Ok(option)
+}
+
+/// Check that the option name starts with `--`, as Zenity's options do.
+fn option_name(name: LitStr) -> syn::Result<LitStr> {
+    match name.value().starts_with("--") && name.value().len() > 2 {
+        true => Ok(name),
+        false => Err(syn::Error::new(
+            name.span(),
+            "expected an option name starting with `--`",
+        )),
+    }
+}
+
+/// Whether the type is a path ending in the name, such as `bool` or `std::option::Option<T>`.
+fn is_type(ty: &Type, name: &str) -> bool {
+    match ty {
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == name),
+        _ => false,
+    }
+}
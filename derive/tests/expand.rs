@@ -0,0 +1,112 @@
+//! The arguments derived for custom applications, which follow the order of the fields.
+
+use std::ffi::OsString;
+use zenity_dialog::{dialog::ToArgVector, ToArgVector};
+
+#[derive(Default, ToArgVector)]
+#[zenity(subcommand = "--scale")]
+struct Scale {
+    #[zenity(arg = "--text")]
+    text: Option<String>,
+    #[zenity(arg = "--value")]
+    value: Option<i32>,
+    #[zenity(flag = "--print-partial")]
+    print_partial: bool,
+    #[zenity(arg = "--step")]
+    step: Option<u32>,
+    #[zenity(flag = "--hide-value")]
+    hide_value: bool,
+    // Not passed to Zenity.
+    #[allow(dead_code)]
+    label: String,
+}
+
+#[derive(ToArgVector)]
+#[zenity(subcommand = "--progress")]
+struct Progress(
+    #[zenity(flag = "--pulsate")] bool,
+    #[zenity(arg = "--percentage")] Option<u8>,
+);
+
+#[derive(ToArgVector)]
+#[zenity(subcommand = "--text-info")]
+struct TextInfo<T: std::fmt::Display> {
+    #[zenity(arg = "--checkbox")]
+    checkbox: Option<T>,
+}
+
+#[derive(ToArgVector)]
+#[zenity(subcommand = "--password")]
+struct Password;
+
+fn argv(application: &impl ToArgVector) -> Vec<OsString> {
+    application.to_argv()
+}
+
+#[test]
+fn only_the_subcommand_is_passed_when_nothing_is_set() {
+    assert_eq!(argv(&Scale::default()), ["--scale"]);
+    assert_eq!(argv(&Password), ["--password"]);
+}
+
+#[test]
+fn fields_are_passed_in_declaration_order() {
+    let scale = Scale {
+        text: Some("Volume".to_owned()),
+        value: Some(-5),
+        print_partial: true,
+        step: Some(2),
+        hide_value: true,
+        label: "ignored".to_owned(),
+    };
+
+    assert_eq!(
+        argv(&scale),
+        [
+            "--scale",
+            "--text=Volume",
+            "--value=-5",
+            "--print-partial",
+            "--step=2",
+            "--hide-value",
+        ]
+    );
+}
+
+#[test]
+fn unset_fields_are_skipped_without_reordering_the_rest() {
+    let scale = Scale {
+        value: Some(30),
+        hide_value: true,
+        ..Default::default()
+    };
+
+    assert_eq!(argv(&scale), ["--scale", "--value=30", "--hide-value"]);
+}
+
+#[test]
+fn values_are_passed_unquoted() {
+    let scale = Scale {
+        text: Some("Say \"hi\" = $HOME".to_owned()),
+        ..Default::default()
+    };
+
+    assert_eq!(argv(&scale), ["--scale", "--text=Say \"hi\" = $HOME"]);
+}
+
+#[test]
+fn tuple_fields_are_passed_in_order() {
+    assert_eq!(
+        argv(&Progress(true, Some(40))),
+        ["--progress", "--pulsate", "--percentage=40"]
+    );
+    assert_eq!(argv(&Progress(false, None)), ["--progress"]);
+}
+
+#[test]
+fn generic_values_are_displayed() {
+    let text_info = TextInfo {
+        checkbox: Some("I agree"),
+    };
+    assert_eq!(argv(&text_info), ["--text-info", "--checkbox=I agree"]);
+}
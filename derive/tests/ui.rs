@@ -0,0 +1,4 @@
+#[test]
+fn misuse_is_reported_at_compile_time() {
+    trybuild::TestCases::new().compile_fail("tests/ui/*.rs");
+}
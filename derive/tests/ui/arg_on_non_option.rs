@@ -0,0 +1,10 @@
+use zenity_dialog::ToArgVector;
+
+#[derive(ToArgVector)]
+#[zenity(subcommand = "--scale")]
+struct Scale {
+    #[zenity(arg = "--value")]
+    value: i32,
+}
+
+fn main() {}
@@ -0,0 +1,7 @@
+use zenity_dialog::ToArgVector;
+
+#[derive(ToArgVector)]
+#[zenity(subcommand = "--scale", subcommand = "--progress")]
+struct Scale {}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use zenity_dialog::ToArgVector;
+
+#[derive(ToArgVector)]
+#[zenity(subcommand = "--scale")]
+enum Scale {
+    Small,
+    Large,
+}
+
+fn main() {}
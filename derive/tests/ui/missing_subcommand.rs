@@ -0,0 +1,9 @@
+use zenity_dialog::ToArgVector;
+
+#[derive(ToArgVector)]
+struct Scale {
+    #[zenity(flag = "--hide-value")]
+    hide_value: bool,
+}
+
+fn main() {}
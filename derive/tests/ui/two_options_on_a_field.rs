@@ -0,0 +1,10 @@
+use zenity_dialog::ToArgVector;
+
+#[derive(ToArgVector)]
+#[zenity(subcommand = "--scale")]
+struct Scale {
+    #[zenity(flag = "--hide-value", flag = "--print-partial")]
+    hide_value: bool,
+}
+
+fn main() {}
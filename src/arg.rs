@@ -1,24 +1,147 @@
-use std::fmt::Display;
+use std::{
+    collections::BTreeMap,
+    ffi::{OsStr, OsString},
+    fmt::Display,
+    path::{Path, PathBuf},
+};
 
 /// Represents a generic argument. For use with [crate::ZenityDialog::with_additional_arg], which allows
-/// you to pass in arguments that aren't currently supported statically. See [From] implementations for
-/// methods of constructing.
-#[derive(Debug, Clone, PartialEq)]
+/// you to pass in arguments that aren't currently supported statically. See [Arg::flag],
+/// [Arg::with_value], and the [From] implementations for methods of constructing.
+///
+/// An argument is always passed to Zenity as a single element of its argument vector, as in
+/// `--name` or `--name=value`. No shell is involved, so values are passed exactly as given:
+/// values containing `=`, spaces, or newlines, or starting with a dash, are never split, quoted,
+/// or escaped. Paths are passed as is, even if they aren't valid UTF-8. Names must be non-empty
+/// and must not contain whitespace or `=`, which [crate::ZenityDialog::validate] checks before
+/// the dialog is shown. [Arg::try_flag] and [Arg::try_with_value] check the name as the
+/// argument is constructed instead.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Arg {
     name: String,
-    value: Option<String>,
+    value: Option<OsString>,
 }
 
+impl Arg {
+    /// Construct an argument without a value, as in `--modal`. The leading `--` is optional.
+    pub fn flag(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            value: None,
+        }
+    }
+
+    /// Construct an argument with a value, as in `--width=300`. The leading `--` of the name is
+    /// optional. The value needn't be valid UTF-8, as with a path.
+    pub fn with_value(name: impl Into<String>, value: impl Into<OsString>) -> Self {
+        Self {
+            name: name.into(),
+            value: Some(value.into()),
+        }
+    }
+
+    /// Like [Arg::flag], but fails if the name is invalid. See [Arg::validate].
+    pub fn try_flag(name: impl Into<String>) -> Result<Self, crate::ValidationError> {
+        let arg = Self::flag(name);
+        arg.validate().map(|()| arg)
+    }
+
+    /// Like [Arg::with_value], but fails if the name is invalid. See [Arg::validate].
+    pub fn try_with_value(
+        name: impl Into<String>,
+        value: impl Into<OsString>,
+    ) -> Result<Self, crate::ValidationError> {
+        let arg = Self::with_value(name, value);
+        arg.validate().map(|()| arg)
+    }
+
+    /// The name without any leading `--`.
+    pub fn name(&self) -> &str {
+        self.name.strip_prefix("--").unwrap_or(&self.name)
+    }
+
+    /// The value, or [None] for a flag.
+    pub fn value(&self) -> Option<&OsStr> {
+        self.value.as_deref()
+    }
+
+    /// Check that the name is non-empty and contains neither whitespace nor `=`, which would
+    /// change how Zenity reads the argument.
+    pub fn validate(&self) -> Result<(), crate::ValidationError> {
+        let name = self.name();
+        let reason = if name.is_empty() {
+            "the name is empty"
+        } else if name.contains(char::is_whitespace) {
+            "the name contains whitespace"
+        } else if name.contains('=') {
+            "the name contains `=`"
+        } else {
+            return Ok(());
+        };
+
+        Err(crate::ValidationError::new(
+            "additional_args",
+            format!("{reason} in {self}"),
+        ))
+    }
+
+    /// Render the argument as it is passed to Zenity, preserving values that aren't valid
+    /// UTF-8.
+    pub fn to_os_string(&self) -> OsString {
+        let mut arg = OsString::from(format!("--{}", self.name()));
+        if let Some(ref value) = self.value {
+            arg.push("=");
+            arg.push(value);
+        }
+        arg
+    }
+}
+
+/// The name of a rendered option without its leading `--` or value, as in `width` for
+/// `--width=300`, or [None] if the argument isn't an option.
+pub(crate) fn option_name(arg: &OsStr) -> Option<String> {
+    let arg = arg.to_string_lossy();
+    let option = arg.strip_prefix("--")?;
+    let name = match option.split_once('=') {
+        Some((name, _)) => name,
+        None => option,
+    };
+    Some(name.to_owned())
+}
+
+/// Replace the values of the named options, given without the leading `--`, with `***`.
+pub(crate) fn redact(args: &mut [OsString], secrets: &[&str]) {
+    for arg in args {
+        if let Some(name) = option_name(arg).filter(|name| secrets.contains(&name.as_str())) {
+            if arg.to_string_lossy().contains('=') {
+                *arg = format!("--{name}=***").into();
+            }
+        }
+    }
+}
+
+/// The option names, as in `--width`, that appear more than once in the arguments.
+pub(crate) fn duplicate_options(args: &[OsString]) -> Vec<String> {
+    let mut counts = BTreeMap::new();
+    for name in args.iter().filter_map(|arg| option_name(arg)) {
+        *counts.entry(name).or_insert(0) += 1;
+    }
+
+    counts
+        .into_iter()
+        .filter(|&(_, count)| count > 1)
+        .map(|(name, _)| format!("--{name}"))
+        .collect()
+}
+
+/// Renders the argument for display. Values that aren't valid UTF-8 are converted lossily; use
+/// [Arg::to_os_string] for the exact argument.
 impl Display for Arg {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let name = match self.name.starts_with("--") {
-            true => &self.name[2..],
-            false => &self.name,
-        };
         if let Some(ref value) = self.value {
-            write!(f, "--{}={}", name, value)
+            write!(f, "--{}={}", self.name(), value.to_string_lossy())
         } else {
-            write!(f, "--{}", name)
+            write!(f, "--{}", self.name())
         }
     }
 }
@@ -45,7 +168,7 @@ impl From<(String, String)> for Arg {
     fn from(value: (String, String)) -> Self {
         Self {
             name: value.0,
-            value: Some(value.1),
+            value: Some(value.1.into()),
         }
     }
 }
@@ -54,7 +177,7 @@ impl From<(&str, String)> for Arg {
     fn from(value: (&str, String)) -> Self {
         Self {
             name: value.0.into(),
-            value: Some(value.1),
+            value: Some(value.1.into()),
         }
     }
 }
@@ -67,3 +190,275 @@ impl From<(&str, &str)> for Arg {
         }
     }
 }
+
+impl From<(&str, OsString)> for Arg {
+    fn from(value: (&str, OsString)) -> Self {
+        Self::with_value(value.0, value.1)
+    }
+}
+
+impl From<(&str, &OsStr)> for Arg {
+    fn from(value: (&str, &OsStr)) -> Self {
+        Self::with_value(value.0, value.1)
+    }
+}
+
+impl From<(&str, PathBuf)> for Arg {
+    fn from(value: (&str, PathBuf)) -> Self {
+        Self::with_value(value.0, value.1)
+    }
+}
+
+impl From<(&str, &Path)> for Arg {
+    fn from(value: (&str, &Path)) -> Self {
+        Self::with_value(value.0, value.1)
+    }
+}
+
+impl From<(&str, u64)> for Arg {
+    fn from(value: (&str, u64)) -> Self {
+        Self::with_value(value.0, value.1.to_string())
+    }
+}
+
+impl From<(&str, i64)> for Arg {
+    fn from(value: (&str, i64)) -> Self {
+        Self::with_value(value.0, value.1.to_string())
+    }
+}
+
+impl From<(&str, usize)> for Arg {
+    fn from(value: (&str, usize)) -> Self {
+        Self::with_value(value.0, value.1.to_string())
+    }
+}
+
+impl From<(String, PathBuf)> for Arg {
+    fn from(value: (String, PathBuf)) -> Self {
+        Self::with_value(value.0, value.1)
+    }
+}
+
+impl From<(String, &Path)> for Arg {
+    fn from(value: (String, &Path)) -> Self {
+        Self::with_value(value.0, value.1)
+    }
+}
+
+impl From<(String, u64)> for Arg {
+    fn from(value: (String, u64)) -> Self {
+        Self::with_value(value.0, value.1.to_string())
+    }
+}
+
+impl From<(String, i64)> for Arg {
+    fn from(value: (String, i64)) -> Self {
+        Self::with_value(value.0, value.1.to_string())
+    }
+}
+
+impl From<(String, usize)> for Arg {
+    fn from(value: (String, usize)) -> Self {
+        Self::with_value(value.0, value.1.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{duplicate_options, option_name, redact, Arg};
+    use crate::{dialog::Info, ZenityDialog};
+    use std::{
+        ffi::{OsStr, OsString},
+        path::PathBuf,
+    };
+
+    #[test]
+    fn renders_flags_and_values_with_a_single_leading_dash_pair() {
+        assert_eq!(Arg::flag("modal").to_os_string(), "--modal");
+        assert_eq!(Arg::flag("--modal").to_os_string(), "--modal");
+        assert_eq!(
+            Arg::with_value("text", "a=b --c").to_os_string(),
+            "--text=a=b --c"
+        );
+    }
+
+    #[test]
+    fn exposes_the_name_and_value() {
+        let arg = Arg::with_value("--ok-label", "Go");
+        assert_eq!(arg.name(), "ok-label");
+        assert_eq!(arg.value(), Some(OsStr::new("Go")));
+
+        let arg = Arg::flag("modal");
+        assert_eq!(arg.name(), "modal");
+        assert_eq!(arg.value(), None);
+    }
+
+    #[test]
+    fn passes_nasty_values_as_a_single_argument() {
+        for value in [
+            "line one\nline two",
+            "-x",
+            "--text=injected",
+            "  spaced  ",
+            "a=b",
+            "",
+        ] {
+            let arg = Arg::with_value("text", value);
+            assert_eq!(arg.value(), Some(OsStr::new(value)));
+            assert_eq!(
+                arg.to_os_string(),
+                OsString::from(format!("--text={value}"))
+            );
+            assert_eq!(option_name(&arg.to_os_string()).as_deref(), Some("text"));
+
+            let args = ZenityDialog::new(Info::new())
+                .with_additional_arg(arg.clone())
+                .force_legacy_flags()
+                .to_args();
+            assert_eq!(
+                args.iter()
+                    .filter(|rendered| **rendered == arg.to_os_string())
+                    .count(),
+                1,
+                "{:?}",
+                args
+            );
+            assert!(!args.iter().any(|rendered| rendered == value));
+        }
+    }
+
+    #[test]
+    fn checks_the_name_when_asked_to() {
+        assert!(Arg::try_flag("modal").is_ok());
+        assert!(Arg::try_with_value("--ok-label", "Go -x\n").is_ok());
+
+        for name in ["", "--", "ok label", "ok\nlabel", "ok-label=Go"] {
+            assert!(Arg::try_flag(name).is_err(), "{:?}", name);
+            assert!(Arg::try_with_value(name, "Go").is_err(), "{:?}", name);
+            let err = Arg::flag(name).validate().unwrap_err();
+            assert_eq!(err.field, "additional_args");
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn keeps_values_that_arent_utf8() {
+        use std::os::unix::ffi::{OsStrExt, OsStringExt};
+
+        let path = OsString::from_vec(b"/tmp/caf\xe9.txt".to_vec());
+        let arg = Arg::with_value("filename", path.clone());
+
+        assert_eq!(arg.value(), Some(path.as_os_str()));
+        assert_eq!(
+            arg.to_os_string().as_bytes(),
+            b"--filename=/tmp/caf\xe9.txt"
+        );
+        assert_eq!(arg.to_string(), "--filename=/tmp/caf\u{fffd}.txt");
+        assert_eq!(
+            option_name(&arg.to_os_string()).as_deref(),
+            Some("filename")
+        );
+    }
+
+    #[test]
+    fn redacts_only_the_values_of_secret_options() {
+        let mut args: Vec<OsString> = vec![
+            "--entry".into(),
+            "--entry-text=hunter2".into(),
+            "--text=Password".into(),
+        ];
+        redact(&mut args, &["entry-text"]);
+        assert_eq!(args, ["--entry", "--entry-text=***", "--text=Password"]);
+    }
+
+    #[test]
+    fn finds_options_given_more_than_once() {
+        let args: Vec<OsString> = vec![
+            "--width=1".into(),
+            "--modal".into(),
+            "--width=2".into(),
+            "--modal".into(),
+            "--title=x".into(),
+        ];
+        assert_eq!(duplicate_options(&args), ["--modal", "--width"]);
+    }
+
+    #[test]
+    fn converts_numbers_to_their_decimal_text() {
+        let args = [
+            Arg::from(("width", 300u64)),
+            Arg::from(("offset", -12i64)),
+            Arg::from(("height", 200usize)),
+            Arg::from(("width".to_owned(), u64::MAX)),
+            Arg::from(("offset".to_owned(), i64::MIN)),
+            Arg::from(("height".to_owned(), 0usize)),
+        ];
+        let rendered: Vec<_> = args.iter().map(Arg::to_os_string).collect();
+        assert_eq!(
+            rendered,
+            [
+                "--width=300",
+                "--offset=-12",
+                "--height=200",
+                "--width=18446744073709551615",
+                "--offset=-9223372036854775808",
+                "--height=0",
+            ]
+        );
+    }
+
+    #[test]
+    fn converts_paths_to_a_single_argument() {
+        let path = PathBuf::from("/tmp/my notes=v2.txt");
+        let args = [
+            Arg::from(("filename", path.clone())),
+            Arg::from(("filename", path.as_path())),
+            Arg::from(("filename".to_owned(), path.clone())),
+            Arg::from(("filename".to_owned(), path.as_path())),
+        ];
+
+        for arg in args {
+            assert_eq!(arg.name(), "filename");
+            assert_eq!(arg.value(), Some(path.as_os_str()));
+            let argv = ZenityDialog::new(Info::new())
+                .with_additional_arg(arg)
+                .force_legacy_flags()
+                .to_args();
+            assert_eq!(
+                argv.iter()
+                    .filter(|arg| **arg == "--filename=/tmp/my notes=v2.txt")
+                    .count(),
+                1,
+                "{:?}",
+                argv
+            );
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn converts_paths_that_arent_utf8_without_loss() {
+        use std::os::unix::ffi::{OsStrExt, OsStringExt};
+
+        let path = PathBuf::from(OsString::from_vec(b"/tmp/caf\xe9.txt".to_vec()));
+        let args = [
+            Arg::from(("filename", path.clone())),
+            Arg::from(("filename", path.as_path())),
+            Arg::from(("filename".to_owned(), path.clone())),
+            Arg::from(("filename".to_owned(), path.as_path())),
+        ];
+
+        for arg in args {
+            let argv = ZenityDialog::new(Info::new())
+                .with_additional_arg(arg)
+                .force_legacy_flags()
+                .to_args();
+            let filenames: Vec<_> = argv
+                .iter()
+                .filter(|arg| arg.as_bytes().starts_with(b"--filename"))
+                .collect();
+            assert_eq!(filenames.len(), 1, "{:?}", argv);
+            assert_eq!(filenames[0].as_bytes(), b"--filename=/tmp/caf\xe9.txt");
+        }
+    }
+}
@@ -0,0 +1,427 @@
+use crate::ZenitySettings;
+use std::{
+    env,
+    ffi::{OsStr, OsString},
+    path::PathBuf,
+    process::Output,
+    sync::OnceLock,
+};
+
+#[cfg(all(feature = "portal", unix))]
+mod portal;
+
+#[cfg(all(feature = "portal", unix))]
+pub use self::portal::PortalBackend;
+
+/// A program that renders dialogs. Dialogs are always described with Zenity's arguments; a
+/// backend may rewrite them for another program and decides how that program's exit codes are
+/// interpreted. Output parsing always goes through [crate::dialog::ZenityApplication::parse].
+pub trait DialogBackend {
+    /// The executable to run.
+    fn program(&self) -> &OsStr;
+
+    /// Rewrite Zenity's arguments for the backend's program. Defaults to passing them through
+    /// unchanged.
+    fn translate_args(&self, args: Vec<OsString>) -> Vec<OsString> {
+        args
+    }
+
+    /// Show the dialog without spawning [DialogBackend::program], for backends that render
+    /// dialogs in-process or over IPC, such as the desktop portal backend. Receives the translated
+    /// arguments and returns what the program would have output, which is classified and
+    /// parsed as usual. Defaults to [None], which spawns the program. Blocks until the dialog
+    /// is answered, also when the dialog is shown asynchronously.
+    fn run(&self, args: &[OsString]) -> Option<crate::Result<Output>> {
+        let _ = args;
+        None
+    }
+
+    /// Whether the program is Zenity itself, which lists every option it supports in
+    /// `--help-all`, so that options it doesn't support are reported before the dialog is
+    /// shown. Defaults to `false`, which trusts the program to accept Zenity's options.
+    fn is_zenity(&self) -> bool {
+        false
+    }
+
+    /// Interpret the exit code of the program. Defaults to Zenity's exit codes.
+    fn classify_exit(&self, code: i32) -> Outcome {
+        match code {
+            0 => Outcome::Affirmed,
+            1 => Outcome::Rejected,
+            5 => Outcome::TimedOut,
+            255 => Outcome::Failed,
+            _ => Outcome::Unknown,
+        }
+    }
+}
+
+/// How a backend interprets an exit code. See [DialogBackend::classify_exit].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Outcome {
+    /// The user gave an affirmative response. Maps to [crate::ZenityOutput::Affirmed].
+    Affirmed,
+    /// The user rejected or closed the dialog. Maps to [crate::ZenityOutput::Rejected].
+    Rejected,
+    /// The dialog timed out. Maps to [crate::ZenityOutput::TimedOut].
+    TimedOut,
+    /// The program failed to display the dialog. Maps to [crate::Error::ZenityFailed].
+    Failed,
+    /// The exit code has no known meaning. Maps to [crate::ZenityOutput::Unknown].
+    Unknown,
+}
+
+/// The default backend, which runs Zenity itself. Drop-in clones of Zenity with the same
+/// command line interface, such as qarma and matedialog, are supported through
+/// [ZenityBackend::compatible].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ZenityBackend {
+    program: PathBuf,
+    /// Whether the program is a clone rather than Zenity itself.
+    compatible: bool,
+}
+
+impl ZenityBackend {
+    /// Run the Zenity executable configured in the installed [ZenitySettings], which is
+    /// `zenity` unless overridden.
+    pub fn new() -> Self {
+        Self::with_program(ZenitySettings::current().resolved_program())
+    }
+
+    /// Run the provided Zenity executable, whatever it's named, as with a wrapper script or a
+    /// symlink. The options of a dialog are checked against those it lists in `--help-all`.
+    pub fn with_program(program: impl Into<PathBuf>) -> Self {
+        Self {
+            program: program.into(),
+            compatible: false,
+        }
+    }
+
+    /// Run a program that is a drop-in replacement for Zenity. It's trusted to accept Zenity's
+    /// options, which aren't checked against its `--help-all`.
+    pub fn compatible(program_name: impl Into<PathBuf>) -> Self {
+        Self {
+            program: program_name.into(),
+            compatible: true,
+        }
+    }
+
+    /// Run qarma, a Qt clone of Zenity.
+    pub fn qarma() -> Self {
+        Self::compatible("qarma")
+    }
+
+    /// Run matedialog, the MATE desktop's fork of Zenity.
+    pub fn matedialog() -> Self {
+        Self::compatible("matedialog")
+    }
+}
+
+/// The programs [detect_backend] looks for, in order of preference.
+pub const DEFAULT_BACKEND_PRIORITY: [&str; 3] = ["zenity", "qarma", "matedialog"];
+
+/// Find the first of the [DEFAULT_BACKEND_PRIORITY] programs available on `PATH`.
+pub fn detect_backend() -> Option<ZenityBackend> {
+    detect_backend_in(&DEFAULT_BACKEND_PRIORITY)
+}
+
+/// Find the first of the provided Zenity-compatible programs available on `PATH`.
+pub fn detect_backend_in(priority: &[&str]) -> Option<ZenityBackend> {
+    detect_backend_on(priority, &env::var_os("PATH")?)
+}
+
+/// Find the first of the provided programs in the directories of a `PATH`-style list. Only
+/// `zenity` is run as Zenity itself; the other programs are run as clones.
+fn detect_backend_on(priority: &[&str], path: &OsStr) -> Option<ZenityBackend> {
+    priority.iter().find_map(|&name| {
+        let program = find_program_in(name, path)?;
+        Some(if name == ZenitySettings::DEFAULT_PROGRAM {
+            ZenityBackend::with_program(program)
+        } else {
+            ZenityBackend::compatible(program)
+        })
+    })
+}
+
+/// The result of [detect_backend], detected once per process.
+pub(crate) fn detected_backend() -> Option<&'static ZenityBackend> {
+    static DETECTED: OnceLock<Option<ZenityBackend>> = OnceLock::new();
+    detect_once(&DETECTED, detect_backend)
+}
+
+/// The backend held by the cache, running `detect` to fill it on first use.
+fn detect_once(
+    cache: &OnceLock<Option<ZenityBackend>>,
+    detect: impl FnOnce() -> Option<ZenityBackend>,
+) -> Option<&ZenityBackend> {
+    cache.get_or_init(detect).as_ref()
+}
+
+/// Look up an executable on `PATH`.
+#[cfg(feature = "sound")]
+pub(crate) fn find_program(name: &str) -> Option<PathBuf> {
+    find_program_in(name, &env::var_os("PATH")?)
+}
+
+/// Look up an executable in the directories of a `PATH`-style list.
+pub(crate) fn find_program_in(name: &str, path: &OsStr) -> Option<PathBuf> {
+    env::split_paths(path)
+        .map(|dir| dir.join(name))
+        .find(|path| path.is_file())
+}
+
+impl Default for ZenityBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DialogBackend for ZenityBackend {
+    fn program(&self) -> &OsStr {
+        self.program.as_os_str()
+    }
+
+    fn is_zenity(&self) -> bool {
+        !self.compatible
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::{
+        detect_backend_on, detect_once, DialogBackend, Outcome, ZenityBackend,
+        DEFAULT_BACKEND_PRIORITY,
+    };
+    #[cfg(feature = "entry")]
+    use crate::dialog::Entry;
+    use crate::harness::TestScripts;
+    #[cfg(any(feature = "entry", feature = "question"))]
+    use crate::ZenityDialog;
+    #[cfg(feature = "question")]
+    use crate::{dialog::Question, ZenityOutput};
+    #[cfg(any(feature = "entry", feature = "question"))]
+    use std::path::PathBuf;
+    #[cfg(feature = "question")]
+    use std::process::Output;
+    use std::{
+        env,
+        ffi::{OsStr, OsString},
+        sync::OnceLock,
+    };
+
+    /// A `PATH` listing the directories of the scripts, in order.
+    fn path_of(scripts: &[&TestScripts]) -> OsString {
+        env::join_paths(scripts.iter().map(|scripts| scripts.dir())).unwrap()
+    }
+
+    /// Prints its arguments, one per line, and exits with the code given by `--exit=`.
+    const ECHO: &str = r#"#!/bin/sh
+case "$1" in --version|--help-all) exit 0 ;; esac
+code=0
+for arg in "$@"; do
+    printf '%s\n' "$arg"
+    case "$arg" in --exit=*) code="${arg#--exit=}" ;; esac
+done
+exit "$code"
+"#;
+
+    /// Runs another program and marks the arguments it passes, with its own exit codes.
+    #[cfg(any(feature = "entry", feature = "question"))]
+    struct RenamingBackend {
+        program: PathBuf,
+    }
+
+    #[cfg(any(feature = "entry", feature = "question"))]
+    impl DialogBackend for RenamingBackend {
+        fn program(&self) -> &OsStr {
+            self.program.as_os_str()
+        }
+
+        fn translate_args(&self, mut args: Vec<OsString>) -> Vec<OsString> {
+            args.insert(0, "--translated".into());
+            args
+        }
+
+        fn classify_exit(&self, code: i32) -> Outcome {
+            match code {
+                0 => Outcome::Affirmed,
+                3 => Outcome::Rejected,
+                _ => Outcome::Unknown,
+            }
+        }
+    }
+
+    #[cfg(feature = "entry")]
+    #[test]
+    fn runs_the_backend_program_with_translated_arguments() {
+        let scripts = TestScripts::new().unwrap();
+        let backend = RenamingBackend {
+            program: scripts.add("mydialog", ECHO).unwrap(),
+        };
+        let dialog = ZenityDialog::new(Entry::new().with_text("Name?")).allow_headless();
+
+        // The answer is parsed by the application, as for Zenity.
+        let answer = dialog.show_with_backend(&backend).unwrap();
+        assert_eq!(
+            answer.content().unwrap().text,
+            "--translated\n--entry\n--text=Name?\n--title=Entry"
+        );
+    }
+
+    #[cfg(feature = "question")]
+    #[test]
+    fn classifies_exit_codes_through_the_backend() {
+        let scripts = TestScripts::new().unwrap();
+        let backend = RenamingBackend {
+            program: scripts.add("mydialog", ECHO).unwrap(),
+        };
+        let dialog = |code: &str| {
+            ZenityDialog::new(Question::new())
+                .with_additional_arg(("exit", code))
+                .allow_headless()
+        };
+
+        assert_eq!(
+            dialog("3").show_with_backend(&backend).unwrap(),
+            ZenityOutput::Rejected {
+                content: Some("--translated\n--question\n--title=Question\n--exit=3".to_owned())
+            }
+        );
+        // Zenity's rejection code means nothing to this backend.
+        assert!(matches!(
+            dialog("1").show_with_backend(&backend).unwrap(),
+            ZenityOutput::Unknown { exit_code: 1, .. }
+        ));
+    }
+
+    /// Answers every dialog itself with its arguments, without spawning anything.
+    #[cfg(feature = "question")]
+    struct InProcessBackend;
+
+    #[cfg(feature = "question")]
+    impl DialogBackend for InProcessBackend {
+        fn program(&self) -> &OsStr {
+            OsStr::new("/nonexistent/dialog")
+        }
+
+        fn run(&self, args: &[OsString]) -> Option<crate::Result<Output>> {
+            use std::os::unix::process::ExitStatusExt;
+
+            let stdout = args
+                .iter()
+                .map(|arg| arg.to_string_lossy())
+                .collect::<Vec<_>>()
+                .join(" ");
+            Some(Ok(Output {
+                status: std::process::ExitStatus::from_raw(1 << 8),
+                stdout: stdout.into_bytes(),
+                stderr: Vec::new(),
+            }))
+        }
+    }
+
+    #[cfg(feature = "question")]
+    #[test]
+    fn backends_can_show_dialogs_without_spawning_a_program() {
+        let dialog = ZenityDialog::new(Question::new()).allow_headless();
+
+        assert_eq!(
+            dialog.show_with_backend(&InProcessBackend).unwrap(),
+            ZenityOutput::Rejected {
+                content: Some("--question --title=Question".to_owned())
+            }
+        );
+    }
+
+    #[test]
+    fn the_defaults_are_zenitys() {
+        struct Plain;
+        impl DialogBackend for Plain {
+            fn program(&self) -> &OsStr {
+                OsStr::new("zenity")
+            }
+        }
+
+        assert_eq!(Plain.classify_exit(0), Outcome::Affirmed);
+        assert_eq!(Plain.classify_exit(1), Outcome::Rejected);
+        assert_eq!(Plain.classify_exit(5), Outcome::TimedOut);
+        assert_eq!(Plain.classify_exit(255), Outcome::Failed);
+        assert_eq!(Plain.classify_exit(3), Outcome::Unknown);
+        assert_eq!(Plain.translate_args(vec!["--info".into()]), ["--info"]);
+        assert!(Plain.run(&["--info".into()]).is_none());
+    }
+
+    #[test]
+    fn detection_follows_the_priority_order() {
+        let scripts = TestScripts::new().unwrap();
+        let matedialog = scripts.add("matedialog", ECHO).unwrap();
+        let qarma = scripts.add("qarma", ECHO).unwrap();
+        let path = path_of(&[&scripts]);
+
+        assert_eq!(
+            detect_backend_on(&DEFAULT_BACKEND_PRIORITY, &path),
+            Some(ZenityBackend::compatible(&qarma))
+        );
+        assert_eq!(
+            detect_backend_on(&["matedialog", "qarma"], &path),
+            Some(ZenityBackend::compatible(&matedialog))
+        );
+
+        let zenity = scripts.add("zenity", ECHO).unwrap();
+        assert_eq!(
+            detect_backend_on(&DEFAULT_BACKEND_PRIORITY, &path),
+            Some(ZenityBackend::with_program(zenity))
+        );
+        assert_eq!(detect_backend_on(&["kdialog"], &path), None);
+    }
+
+    #[test]
+    fn priority_wins_over_the_order_of_path() {
+        let first = TestScripts::new().unwrap();
+        let second = TestScripts::new().unwrap();
+        first.add("qarma", ECHO).unwrap();
+        let zenity = second.add("zenity", ECHO).unwrap();
+
+        assert_eq!(
+            detect_backend_on(&DEFAULT_BACKEND_PRIORITY, &path_of(&[&first, &second])),
+            Some(ZenityBackend::with_program(zenity))
+        );
+    }
+
+    #[test]
+    fn directories_are_not_detected_as_programs() {
+        let scripts = TestScripts::new().unwrap();
+        std::fs::create_dir(scripts.dir().join("zenity")).unwrap();
+        assert_eq!(
+            detect_backend_on(&DEFAULT_BACKEND_PRIORITY, &path_of(&[&scripts])),
+            None
+        );
+    }
+
+    #[test]
+    fn detection_is_cached_after_first_use() {
+        let before = TestScripts::new().unwrap();
+        let after = TestScripts::new().unwrap();
+        let qarma = before.add("qarma", ECHO).unwrap();
+        after.add("zenity", ECHO).unwrap();
+        let cache = OnceLock::new();
+
+        let detect = |scripts: &TestScripts| {
+            let path = path_of(&[scripts]);
+            move || detect_backend_on(&DEFAULT_BACKEND_PRIORITY, &path)
+        };
+        assert_eq!(
+            detect_once(&cache, detect(&before)),
+            Some(&ZenityBackend::compatible(&qarma))
+        );
+        // A better program appearing later doesn't change the cached result.
+        assert_eq!(
+            detect_once(&cache, detect(&after)),
+            Some(&ZenityBackend::compatible(qarma))
+        );
+
+        // Nor does a program appearing after nothing was found.
+        let cache = OnceLock::new();
+        assert_eq!(detect_once(&cache, || None), None);
+        assert_eq!(detect_once(&cache, detect(&after)), None);
+    }
+}
@@ -0,0 +1,936 @@
+use super::DialogBackend;
+use crate::CancelFlag;
+use std::{
+    collections::HashMap,
+    convert::TryFrom,
+    ffi::{OsStr, OsString},
+    fmt::Display,
+    io,
+    os::unix::{ffi::OsStrExt, process::ExitStatusExt},
+    path::{Path, PathBuf},
+    process::{ExitStatus, Output},
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+    time::Duration,
+};
+use zbus::{
+    zvariant::{OwnedValue, Value},
+    DBusError as _,
+};
+
+const DESTINATION: &str = "org.freedesktop.portal.Desktop";
+const PATH: &str = "/org/freedesktop/portal/desktop";
+const FILE_CHOOSER: &str = "org.freedesktop.portal.FileChooser";
+const REQUEST: &str = "org.freedesktop.portal.Request";
+
+/// D-Bus errors returned while xdg-desktop-portal restarts, which are worth retrying.
+const TRANSIENT_ERRORS: [&str; 2] = [
+    "org.freedesktop.DBus.Error.NoReply",
+    "org.freedesktop.DBus.Error.ServiceUnknown",
+];
+
+/// D-Bus errors refusing the dialog, which retrying won't change.
+const DENIED_ERRORS: [&str; 2] = [
+    "org.freedesktop.DBus.Error.AccessDenied",
+    "org.freedesktop.portal.Error.NotAllowed",
+];
+
+/// Shows file selection dialogs through the file chooser of the XDG desktop portal,
+/// `org.freedesktop.portal.FileChooser`, over D-Bus instead of spawning Zenity. Works inside
+/// sandboxes such as Flatpak and Snap, where Zenity on the host usually can't be run.
+///
+/// Dialogs are described with Zenity's arguments as usual, and the selected files are returned
+/// like Zenity's, so calling code doesn't change. Cancelling the dialog maps to
+/// [crate::ZenityOutput::Rejected]. The portal can't show other dialogs, or honour a timeout;
+/// those fail with [crate::Error::UnsupportedByBackend]. Cosmetic options such as the size
+/// and icon are ignored.
+///
+/// Calls that fail because the portal is restarting, with `NoReply` or `ServiceUnknown`, are
+/// retried with exponential backoff; see [PortalBackend::with_retries]. A refused call, as with
+/// `AccessDenied`, isn't retried and fails with [crate::Error::ZenityFailed].
+///
+/// ```no_run
+/// # use zenity_dialog::{backend::PortalBackend, dialog::FileSelection, ZenityDialog};
+/// let files = ZenityDialog::new(FileSelection::new().with_multiple())
+///     .show_with_backend(&PortalBackend::new())?;
+/// # Ok::<(), zenity_dialog::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct PortalBackend {
+    parent_window: String,
+    attempts: u32,
+    initial_delay: Duration,
+    cancel_flag: Option<CancelFlag>,
+}
+
+impl PortalBackend {
+    /// How often a call is attempted unless configured otherwise.
+    pub const DEFAULT_ATTEMPTS: u32 = 4;
+    /// The delay before the first retry unless configured otherwise. It doubles with every
+    /// retry, up to [PortalBackend::MAX_DELAY].
+    pub const DEFAULT_INITIAL_DELAY: Duration = Duration::from_millis(250);
+    /// The longest delay between two attempts.
+    pub const MAX_DELAY: Duration = Duration::from_secs(4);
+
+    /// Show dialogs without a parent window.
+    pub fn new() -> Self {
+        Self::with_parent_window("")
+    }
+
+    /// Show dialogs for the window with the provided handle, as in `x11:1c00003` or
+    /// `wayland:<exported handle>`, so that the portal can place them over it.
+    pub fn with_parent_window(parent_window: impl Into<String>) -> Self {
+        Self {
+            parent_window: parent_window.into(),
+            attempts: Self::DEFAULT_ATTEMPTS,
+            initial_delay: Self::DEFAULT_INITIAL_DELAY,
+            cancel_flag: None,
+        }
+    }
+
+    /// Attempt calls failing with a transient error at most `attempts` times, waiting
+    /// `initial_delay` before the first retry and twice as long before each following one.
+    /// One attempt disables retrying.
+    pub fn with_retries(mut self, attempts: u32, initial_delay: Duration) -> Self {
+        self.attempts = attempts.max(1);
+        self.initial_delay = initial_delay;
+        self
+    }
+
+    /// Stop retrying once the flag is cancelled, answering the dialog with
+    /// [crate::ZenityOutput::Rejected] as if the user had cancelled it. A flag that is already
+    /// cancelled rejects the dialog without calling the portal. A dialog the portal is already
+    /// showing can't be closed.
+    pub fn with_cancel_flag(mut self, flag: CancelFlag) -> Self {
+        self.cancel_flag = Some(flag);
+        self
+    }
+
+    /// Translate the arguments, call the portal through the transport, and translate its
+    /// response, retrying transient failures.
+    fn show(&self, transport: &impl Transport, args: &[OsString]) -> crate::Result<Output> {
+        let request = PortalRequest::from_args(args)?;
+        let cancelled = || output(1, Vec::new(), Vec::new());
+
+        for attempt in 0.. {
+            if self.is_cancelled() {
+                return Ok(cancelled());
+            }
+
+            let err = match transport.send(&request, &self.parent_window) {
+                Ok(response) => return Ok(response.to_output(&request.separator)),
+                Err(err) => err,
+            };
+
+            match err.kind() {
+                BusErrorKind::Transient if attempt + 1 < self.attempts => {
+                    if self.wait(Self::backoff(self.initial_delay, attempt)) {
+                        return Ok(cancelled());
+                    }
+                }
+                BusErrorKind::Denied => {
+                    return Ok(output(255, Vec::new(), err.to_string().into_bytes()))
+                }
+                _ => return Err(crate::Error::UnexpectedIoError(io::Error::other(err))),
+            }
+        }
+
+        unreachable!("the attempts are bounded")
+    }
+
+    /// The delay before retry number `retry`, starting from 0.
+    fn backoff(initial_delay: Duration, retry: u32) -> Duration {
+        initial_delay
+            .checked_mul(2_u32.saturating_pow(retry))
+            .map_or(Self::MAX_DELAY, |delay| delay.min(Self::MAX_DELAY))
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancel_flag
+            .as_ref()
+            .is_some_and(CancelFlag::is_cancelled)
+    }
+
+    /// Wait before retrying. Returns whether the flag was cancelled meanwhile.
+    fn wait(&self, delay: Duration) -> bool {
+        match self.cancel_flag {
+            Some(ref flag) => flag.wait_timeout(delay),
+            None => {
+                thread::sleep(delay);
+                false
+            }
+        }
+    }
+}
+
+impl Default for PortalBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DialogBackend for PortalBackend {
+    /// The portal's service, used in errors. It's never spawned.
+    fn program(&self) -> &OsStr {
+        OsStr::new("xdg-desktop-portal")
+    }
+
+    fn run(&self, args: &[OsString]) -> Option<crate::Result<Output>> {
+        Some(self.show(&SessionBus, args))
+    }
+}
+
+/// Delivers requests to the portal. Abstracted so that tests can script the portal's answers.
+trait Transport {
+    fn send(
+        &self,
+        request: &PortalRequest,
+        parent_window: &str,
+    ) -> Result<PortalResponse, BusError>;
+}
+
+/// The portal on the session bus.
+struct SessionBus;
+
+impl Transport for SessionBus {
+    fn send(
+        &self,
+        request: &PortalRequest,
+        parent_window: &str,
+    ) -> Result<PortalResponse, BusError> {
+        request.send(parent_window).map_err(BusError::from)
+    }
+}
+
+/// A failed D-Bus call, with the name of the D-Bus error if there is one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct BusError {
+    name: Option<String>,
+    message: String,
+}
+
+/// How a [BusError] is handled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BusErrorKind {
+    /// The portal is likely restarting; the call may succeed when retried.
+    Transient,
+    /// The call was refused.
+    Denied,
+    /// Anything else, which isn't retried.
+    Other,
+}
+
+impl BusError {
+    fn kind(&self) -> BusErrorKind {
+        match self.name.as_deref() {
+            Some(name) if TRANSIENT_ERRORS.contains(&name) => BusErrorKind::Transient,
+            Some(name) if DENIED_ERRORS.contains(&name) => BusErrorKind::Denied,
+            _ => BusErrorKind::Other,
+        }
+    }
+}
+
+impl From<zbus::Error> for BusError {
+    fn from(err: zbus::Error) -> Self {
+        let name = match err {
+            zbus::Error::MethodError(ref name, _, _) => Some(name.to_string()),
+            zbus::Error::FDO(ref err) => Some(err.name().to_string()),
+            _ => None,
+        };
+        Self {
+            name,
+            message: err.to_string(),
+        }
+    }
+}
+
+impl Display for BusError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.name {
+            Some(ref name) if !self.message.contains(name.as_str()) => {
+                write!(f, "{name}: {}", self.message)
+            }
+            _ => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for BusError {}
+
+/// Which file chooser method a request calls.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Method {
+    OpenFile,
+    SaveFile,
+}
+
+/// A file selection dialog, translated from Zenity's arguments to the options of the portal's
+/// file chooser.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PortalRequest {
+    pub(crate) method: Method,
+    pub(crate) title: String,
+    pub(crate) modal: bool,
+    pub(crate) multiple: bool,
+    pub(crate) directory: bool,
+    pub(crate) current_folder: Option<PathBuf>,
+    pub(crate) current_name: Option<OsString>,
+    /// Named filters, each a list of glob patterns tagged with the pattern type `0`.
+    pub(crate) filters: Vec<(String, Vec<(u32, String)>)>,
+    /// Separates the selected files in the translated output.
+    pub(crate) separator: Vec<u8>,
+}
+
+impl PortalRequest {
+    /// Zenity's separator when none is given.
+    const DEFAULT_SEPARATOR: &'static [u8] = b"|";
+
+    /// Translate the arguments of a file selection dialog. Other dialogs, and options the
+    /// portal can't honour, fail with [crate::Error::UnsupportedByBackend].
+    pub(crate) fn from_args(args: &[OsString]) -> crate::Result<Self> {
+        let unsupported = |reason: String| crate::Error::UnsupportedByBackend {
+            backend: "xdg-desktop-portal".to_owned(),
+            reason,
+        };
+
+        let Some((first, options)) = args.split_first() else {
+            return Err(unsupported("no dialog was given".to_owned()));
+        };
+        if first != "--file-selection" {
+            return Err(unsupported(format!(
+                "only file selection dialogs are supported, not {}",
+                first.to_string_lossy()
+            )));
+        }
+
+        let mut request = Self {
+            method: Method::OpenFile,
+            title: String::new(),
+            modal: false,
+            multiple: false,
+            directory: false,
+            current_folder: None,
+            current_name: None,
+            filters: Vec::new(),
+            separator: Self::DEFAULT_SEPARATOR.to_vec(),
+        };
+        let mut filename = None;
+
+        for arg in options {
+            let (name, value) = split(arg);
+            match (name, value) {
+                ("--title", Some(title)) => request.title = title.to_string_lossy().into_owned(),
+                ("--filename", Some(path)) => filename = Some(path),
+                ("--separator", Some(separator)) => {
+                    request.separator = separator.as_bytes().to_vec()
+                }
+                ("--file-filter", Some(filter)) => request
+                    .filters
+                    .push(parse_filter(&filter.to_string_lossy())),
+                ("--save", None) => request.method = Method::SaveFile,
+                ("--multiple", None) => request.multiple = true,
+                ("--directory", None) => request.directory = true,
+                ("--modal", None) => request.modal = true,
+                // The portal always confirms overwriting, and sizes and icons are cosmetic.
+                ("--confirm-overwrite", None)
+                | ("--width" | "--height" | "--icon-name" | "--icon", Some(_)) => {}
+                _ => {
+                    return Err(unsupported(format!(
+                        "the option {} isn't supported",
+                        arg.to_string_lossy()
+                    )))
+                }
+            }
+        }
+
+        if let Some(filename) = filename {
+            request.set_filename(Path::new(filename));
+        }
+
+        Ok(request)
+    }
+
+    /// Open the dialog in the directory of the file. When saving, also suggest its name. A
+    /// path ending in `/` only opens the directory, as with Zenity.
+    fn set_filename(&mut self, filename: &Path) {
+        if filename.as_os_str().as_bytes().ends_with(b"/") {
+            self.current_folder = Some(filename.to_owned());
+            return;
+        }
+
+        self.current_folder = filename
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+            .map(Path::to_owned);
+        if self.method == Method::SaveFile {
+            self.current_name = filename.file_name().map(OsStr::to_owned);
+        }
+    }
+
+    /// The options of the file chooser call. The token names the request object the response
+    /// is sent to.
+    pub(crate) fn options(&self, handle_token: &str) -> HashMap<&'static str, Value<'_>> {
+        let mut options = HashMap::new();
+        options.insert("handle_token", Value::from(handle_token.to_owned()));
+        options.insert("modal", Value::from(self.modal));
+        if self.method == Method::OpenFile {
+            options.insert("multiple", Value::from(self.multiple));
+            options.insert("directory", Value::from(self.directory));
+        }
+
+        if let Some(ref folder) = self.current_folder {
+            options.insert("current_folder", Value::from(nul_terminated(folder)));
+        }
+
+        if let Some(ref name) = self.current_name {
+            options.insert(
+                "current_name",
+                Value::from(name.to_string_lossy().into_owned()),
+            );
+        }
+
+        if !self.filters.is_empty() {
+            options.insert("filters", Value::from(self.filters.clone()));
+        }
+
+        options
+    }
+
+    /// Call the file chooser on the session bus and wait for the user's response.
+    fn send(&self, parent_window: &str) -> zbus::Result<PortalResponse> {
+        static REQUESTS: AtomicUsize = AtomicUsize::new(0);
+
+        let connection = zbus::blocking::Connection::session()?;
+        let token = format!(
+            "zenity_dialog_{}_{}",
+            std::process::id(),
+            REQUESTS.fetch_add(1, Ordering::Relaxed)
+        );
+
+        // Subscribe to the response before calling, so that a fast answer isn't missed. The
+        // request object's path is derived from the caller's unique name and the token.
+        let sender = connection
+            .unique_name()
+            .map(|name| name.trim_start_matches(':').replace('.', "_"))
+            .unwrap_or_default();
+        let request = zbus::blocking::Proxy::new(
+            &connection,
+            DESTINATION,
+            format!("{PATH}/request/{sender}/{token}"),
+            REQUEST,
+        )?;
+        let mut responses = request.receive_signal("Response")?;
+
+        let method = match self.method {
+            Method::OpenFile => "OpenFile",
+            Method::SaveFile => "SaveFile",
+        };
+        connection.call_method(
+            Some(DESTINATION),
+            PATH,
+            Some(FILE_CHOOSER),
+            method,
+            &(parent_window, self.title.as_str(), self.options(&token)),
+        )?;
+
+        let message = responses
+            .next()
+            .ok_or_else(|| zbus::Error::Failure("The portal closed the request".to_owned()))?;
+        let (code, results): (u32, HashMap<String, OwnedValue>) = message.body().deserialize()?;
+        let uris = match results.get("uris") {
+            Some(uris) => Vec::<String>::try_from(uris.try_clone()?)?,
+            None => Vec::new(),
+        };
+        Ok(PortalResponse { code, uris })
+    }
+}
+
+/// The portal's answer to a [PortalRequest].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PortalResponse {
+    /// `0` if the user selected files, `1` if they cancelled, and `2` if the interaction ended
+    /// some other way.
+    pub(crate) code: u32,
+    /// The selected files, as `file://` URIs.
+    pub(crate) uris: Vec<String>,
+}
+
+impl PortalResponse {
+    /// What Zenity would have output for the response: the selected paths joined by the
+    /// separator and exit code `0`, exit code `1` for a cancelled dialog, or `255` with an
+    /// explanation on stderr for anything else.
+    pub(crate) fn to_output(&self, separator: &[u8]) -> Output {
+        let failure = |stderr: String| output(255, Vec::new(), stderr.into_bytes());
+        match self.code {
+            0 => {
+                let mut stdout = Vec::new();
+                for (position, uri) in self.uris.iter().enumerate() {
+                    let Some(path) = file_path(uri) else {
+                        return failure(format!("The portal returned {uri}, which isn't a file"));
+                    };
+                    if position > 0 {
+                        stdout.extend_from_slice(separator);
+                    }
+                    stdout.extend_from_slice(&path);
+                }
+                stdout.push(b'\n');
+                output(0, stdout, Vec::new())
+            }
+            1 => output(1, Vec::new(), Vec::new()),
+            code => failure(format!("The portal ended the interaction with code {code}")),
+        }
+    }
+}
+
+fn output(code: i32, stdout: Vec<u8>, stderr: Vec<u8>) -> Output {
+    Output {
+        status: ExitStatus::from_raw((code & 0xff) << 8),
+        stdout,
+        stderr,
+    }
+}
+
+/// Split an argument into its name and value, as in `--title` and `Open` for `--title=Open`.
+fn split(arg: &OsStr) -> (&str, Option<&OsStr>) {
+    let bytes = arg.as_bytes();
+    let (name, value) = match bytes.iter().position(|&byte| byte == b'=') {
+        Some(position) => (
+            &bytes[..position],
+            Some(OsStr::from_bytes(&bytes[position + 1..])),
+        ),
+        None => (bytes, None),
+    };
+    (std::str::from_utf8(name).unwrap_or_default(), value)
+}
+
+/// Parse a filter in Zenity's format, `NAME | PATTERN1 PATTERN2`. A filter without a name is
+/// named after its patterns.
+fn parse_filter(filter: &str) -> (String, Vec<(u32, String)>) {
+    let (name, patterns) = match filter.split_once('|') {
+        Some((name, patterns)) => (name.trim(), patterns),
+        None => (filter.trim(), filter),
+    };
+    let patterns = patterns
+        .split_whitespace()
+        .map(|pattern| (0, pattern.to_owned()))
+        .collect();
+    (name.to_owned(), patterns)
+}
+
+/// The path as a byte array ending in a NUL byte, as the portal expects paths.
+fn nul_terminated(path: &Path) -> Vec<u8> {
+    let mut bytes = path.as_os_str().as_bytes().to_vec();
+    bytes.push(0);
+    bytes
+}
+
+/// The path of a `file://` URI, percent-decoded, or [None] for other URIs.
+fn file_path(uri: &str) -> Option<Vec<u8>> {
+    let encoded = uri.strip_prefix("file://")?.as_bytes();
+    let mut path = Vec::with_capacity(encoded.len());
+    let mut bytes = encoded.iter();
+    while let Some(&byte) = bytes.next() {
+        if byte != b'%' {
+            path.push(byte);
+            continue;
+        }
+
+        let hex = [*bytes.next()?, *bytes.next()?];
+        path.push(u8::from_str_radix(std::str::from_utf8(&hex).ok()?, 16).ok()?);
+    }
+    Some(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        BusError, BusErrorKind, Method, PortalBackend, PortalRequest, PortalResponse, Transport,
+    };
+    use crate::{
+        backend::{DialogBackend, Outcome},
+        dialog::{FileFilter, FileSelection},
+        CancelFlag, ZenityDialog,
+    };
+    use std::{
+        cell::{Cell, RefCell},
+        collections::VecDeque,
+        ffi::OsString,
+        path::PathBuf,
+        time::Duration,
+    };
+
+    /// Answers requests with the scripted results, in order, and counts the attempts.
+    struct ScriptedPortal {
+        results: RefCell<VecDeque<Result<PortalResponse, BusError>>>,
+        attempts: Cell<u32>,
+        /// Cancelled after this many attempts, if set.
+        cancel_after: Option<(u32, CancelFlag)>,
+    }
+
+    impl ScriptedPortal {
+        fn new(results: impl IntoIterator<Item = Result<PortalResponse, BusError>>) -> Self {
+            Self {
+                results: RefCell::new(results.into_iter().collect()),
+                attempts: Cell::new(0),
+                cancel_after: None,
+            }
+        }
+    }
+
+    impl Transport for ScriptedPortal {
+        fn send(&self, _: &PortalRequest, _: &str) -> Result<PortalResponse, BusError> {
+            self.attempts.set(self.attempts.get() + 1);
+            if let Some((after, ref flag)) = self.cancel_after {
+                if self.attempts.get() >= after {
+                    flag.cancel();
+                }
+            }
+            self.results
+                .borrow_mut()
+                .pop_front()
+                .expect("the portal was called more often than scripted")
+        }
+    }
+
+    fn bus_error(name: &str) -> Result<PortalResponse, BusError> {
+        Err(BusError {
+            name: Some(name.to_owned()),
+            message: "scripted".to_owned(),
+        })
+    }
+
+    fn selected(path: &str) -> Result<PortalResponse, BusError> {
+        Ok(PortalResponse {
+            code: 0,
+            uris: vec![format!("file://{path}")],
+        })
+    }
+
+    fn args() -> Vec<OsString> {
+        vec!["--file-selection".into(), "--separator=\n".into()]
+    }
+
+    fn backend() -> PortalBackend {
+        PortalBackend::new().with_retries(4, Duration::from_millis(1))
+    }
+    use zbus::zvariant::Value;
+
+    fn request(dialog: ZenityDialog<FileSelection>) -> PortalRequest {
+        PortalRequest::from_args(&dialog.force_legacy_flags().to_args()).unwrap()
+    }
+
+    #[test]
+    fn translates_an_open_dialog() {
+        let request = request(
+            ZenityDialog::new(
+                FileSelection::new()
+                    .with_filename("/home/user/notes/")
+                    .with_file_filter(FileFilter::new("Text", ["*.txt", "*.md"]))
+                    .with_multiple(),
+            )
+            .with_title("Open notes")
+            .with_modal(),
+        );
+
+        assert_eq!(request.method, Method::OpenFile);
+        assert_eq!(request.title, "Open notes");
+        assert!(request.modal);
+        assert!(request.multiple);
+        assert!(!request.directory);
+        assert_eq!(
+            request.current_folder,
+            Some(PathBuf::from("/home/user/notes/"))
+        );
+        assert_eq!(request.current_name, None);
+        assert_eq!(
+            request.filters,
+            [(
+                "Text".to_owned(),
+                vec![(0, "*.txt".to_owned()), (0, "*.md".to_owned())]
+            )]
+        );
+        assert_eq!(request.separator, b"\n");
+    }
+
+    #[test]
+    fn translates_a_save_dialog() {
+        let request = request(ZenityDialog::new(
+            FileSelection::new()
+                .with_filename("/home/user/report.pdf")
+                .with_save(),
+        ));
+
+        assert_eq!(request.method, Method::SaveFile);
+        assert_eq!(request.title, "Select a file");
+        assert_eq!(request.current_folder, Some(PathBuf::from("/home/user")));
+        assert_eq!(request.current_name, Some("report.pdf".into()));
+    }
+
+    #[test]
+    fn translates_a_directory_dialog() {
+        let request = request(ZenityDialog::new(FileSelection::new().with_directory()));
+        assert!(request.directory);
+        assert_eq!(request.current_folder, None);
+    }
+
+    #[test]
+    fn translates_the_options_of_the_call() {
+        let request = request(ZenityDialog::new(
+            FileSelection::new()
+                .with_filename("/tmp/out.txt")
+                .with_file_filter(FileFilter::new("Text", ["*.txt"]))
+                .with_save(),
+        ));
+        let options = request.options("token");
+
+        assert_eq!(options["handle_token"], Value::from("token"));
+        assert_eq!(options["modal"], Value::from(false));
+        assert_eq!(options["current_folder"], Value::from(b"/tmp\0".to_vec()));
+        assert_eq!(options["current_name"], Value::from("out.txt"));
+        assert_eq!(
+            options["filters"],
+            Value::from(vec![("Text".to_owned(), vec![(0_u32, "*.txt".to_owned())])])
+        );
+        // Saving always selects a single file.
+        assert!(!options.contains_key("multiple"));
+        assert!(!options.contains_key("directory"));
+    }
+
+    #[test]
+    fn ignores_cosmetic_options() {
+        let request = request(
+            ZenityDialog::new(FileSelection::new())
+                .with_width(600)
+                .with_height(400)
+                .with_icon("folder"),
+        );
+        assert_eq!(request.method, Method::OpenFile);
+    }
+
+    #[test]
+    fn rejects_other_dialogs_and_unsupported_options() {
+        let err = PortalRequest::from_args(&["--entry".into()]).unwrap_err();
+        assert!(
+            matches!(err, crate::Error::UnsupportedByBackend { ref reason, .. } if reason.contains("--entry")),
+            "{:?}",
+            err
+        );
+
+        let err = PortalRequest::from_args(&["--file-selection".into(), "--timeout=5".into()])
+            .unwrap_err();
+        assert!(
+            matches!(err, crate::Error::UnsupportedByBackend { ref reason, .. } if reason.contains("--timeout=5")),
+            "{:?}",
+            err
+        );
+    }
+
+    #[test]
+    fn filters_without_a_name_are_named_after_their_patterns() {
+        let request =
+            PortalRequest::from_args(&["--file-selection".into(), "--file-filter=*.png".into()])
+                .unwrap();
+        assert_eq!(
+            request.filters,
+            [("*.png".to_owned(), vec![(0, "*.png".to_owned())])]
+        );
+        assert_eq!(request.separator, b"|");
+    }
+
+    #[test]
+    fn translates_selected_files_into_zenity_output() {
+        let response = PortalResponse {
+            code: 0,
+            uris: vec![
+                "file:///home/user/a%20b.txt".to_owned(),
+                "file:///home/user/caf%E9.txt".to_owned(),
+            ],
+        };
+        let output = response.to_output(b"\n");
+
+        assert_eq!(output.status.code(), Some(0));
+        assert_eq!(
+            output.stdout,
+            b"/home/user/a b.txt\n/home/user/caf\xe9.txt\n".to_vec()
+        );
+    }
+
+    #[test]
+    fn cancelling_maps_to_rejected() {
+        let output = PortalResponse {
+            code: 1,
+            uris: Vec::new(),
+        }
+        .to_output(b"\n");
+        let code = output.status.code().unwrap();
+        assert_eq!(PortalBackend::new().classify_exit(code), Outcome::Rejected);
+        assert!(output.stdout.is_empty());
+    }
+
+    #[test]
+    fn other_responses_and_uris_are_failures() {
+        let output = PortalResponse {
+            code: 2,
+            uris: Vec::new(),
+        }
+        .to_output(b"\n");
+        assert_eq!(output.status.code(), Some(255));
+        assert!(String::from_utf8_lossy(&output.stderr).contains("code 2"));
+
+        let output = PortalResponse {
+            code: 0,
+            uris: vec!["https://example.com/a.txt".to_owned()],
+        }
+        .to_output(b"\n");
+        assert_eq!(output.status.code(), Some(255));
+    }
+
+    #[test]
+    fn transient_errors_are_retried_until_the_call_succeeds() {
+        let portal = ScriptedPortal::new([
+            bus_error("org.freedesktop.DBus.Error.NoReply"),
+            bus_error("org.freedesktop.DBus.Error.ServiceUnknown"),
+            selected("/tmp/a.txt"),
+        ]);
+
+        let output = backend().show(&portal, &args()).unwrap();
+        assert_eq!(portal.attempts.get(), 3);
+        assert_eq!(output.status.code(), Some(0));
+        assert_eq!(output.stdout, b"/tmp/a.txt\n");
+    }
+
+    #[test]
+    fn retries_are_bounded() {
+        let portal =
+            ScriptedPortal::new((0..4).map(|_| bus_error("org.freedesktop.DBus.Error.NoReply")));
+
+        let err = backend().show(&portal, &args()).unwrap_err();
+        assert_eq!(portal.attempts.get(), 4);
+        assert!(err.to_string().contains("NoReply"), "{}", err);
+    }
+
+    #[test]
+    fn denied_calls_fail_without_retrying() {
+        let portal = ScriptedPortal::new([bus_error("org.freedesktop.DBus.Error.AccessDenied")]);
+
+        let output = backend().show(&portal, &args()).unwrap();
+        assert_eq!(portal.attempts.get(), 1);
+        assert_eq!(output.status.code(), Some(255));
+        assert!(String::from_utf8_lossy(&output.stderr).contains("AccessDenied"));
+    }
+
+    #[test]
+    fn other_errors_fail_without_retrying() {
+        let portal = ScriptedPortal::new([bus_error("org.freedesktop.DBus.Error.InvalidArgs")]);
+
+        let err = backend().show(&portal, &args()).unwrap_err();
+        assert_eq!(portal.attempts.get(), 1);
+        assert!(
+            matches!(err, crate::Error::UnexpectedIoError(_)),
+            "{:?}",
+            err
+        );
+    }
+
+    #[test]
+    fn cancelling_by_the_user_isnt_retried() {
+        let portal = ScriptedPortal::new([Ok(PortalResponse {
+            code: 1,
+            uris: Vec::new(),
+        })]);
+
+        let output = backend().show(&portal, &args()).unwrap();
+        assert_eq!(portal.attempts.get(), 1);
+        assert_eq!(output.status.code(), Some(1));
+    }
+
+    #[test]
+    fn cancelling_the_flag_stops_retrying() {
+        let flag = CancelFlag::new();
+        let mut portal = ScriptedPortal::new([
+            bus_error("org.freedesktop.DBus.Error.NoReply"),
+            selected("/tmp/a.txt"),
+        ]);
+        portal.cancel_after = Some((1, flag.clone()));
+
+        let backend = PortalBackend::new()
+            .with_retries(4, Duration::from_secs(60))
+            .with_cancel_flag(flag);
+        let output = backend.show(&portal, &args()).unwrap();
+        assert_eq!(portal.attempts.get(), 1);
+        assert_eq!(output.status.code(), Some(1));
+        assert_eq!(backend.classify_exit(1), Outcome::Rejected);
+    }
+
+    #[test]
+    fn a_cancelled_flag_skips_the_portal() {
+        let flag = CancelFlag::new();
+        flag.cancel();
+        let portal = ScriptedPortal::new([]);
+
+        let output = backend()
+            .with_cancel_flag(flag)
+            .show(&portal, &args())
+            .unwrap();
+        assert_eq!(portal.attempts.get(), 0);
+        assert_eq!(output.status.code(), Some(1));
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_the_maximum() {
+        let initial = Duration::from_millis(250);
+        let delays: Vec<_> = (0..6)
+            .map(|retry| PortalBackend::backoff(initial, retry))
+            .collect();
+        assert_eq!(
+            delays,
+            [250, 500, 1000, 2000, 4000, 4000].map(Duration::from_millis)
+        );
+        assert_eq!(
+            PortalBackend::backoff(initial, u32::MAX),
+            PortalBackend::MAX_DELAY
+        );
+    }
+
+    #[test]
+    fn classifies_bus_errors_by_name() {
+        let kind = |name: Option<&str>| {
+            BusError {
+                name: name.map(str::to_owned),
+                message: String::new(),
+            }
+            .kind()
+        };
+
+        assert_eq!(
+            kind(Some("org.freedesktop.DBus.Error.NoReply")),
+            BusErrorKind::Transient
+        );
+        assert_eq!(
+            kind(Some("org.freedesktop.DBus.Error.ServiceUnknown")),
+            BusErrorKind::Transient
+        );
+        assert_eq!(
+            kind(Some("org.freedesktop.DBus.Error.AccessDenied")),
+            BusErrorKind::Denied
+        );
+        assert_eq!(
+            kind(Some("org.freedesktop.portal.Error.NotAllowed")),
+            BusErrorKind::Denied
+        );
+        assert_eq!(
+            kind(Some("org.freedesktop.DBus.Error.Failed")),
+            BusErrorKind::Other
+        );
+        assert_eq!(kind(None), BusErrorKind::Other);
+    }
+
+    #[test]
+    fn bus_errors_keep_the_name_of_fdo_errors() {
+        let err = BusError::from(zbus::Error::FDO(Box::new(zbus::fdo::Error::NoReply(
+            "restarting".to_owned(),
+        ))));
+        assert_eq!(err.kind(), BusErrorKind::Transient);
+        assert!(err.to_string().contains("restarting"), "{}", err);
+    }
+}
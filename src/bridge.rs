@@ -0,0 +1,404 @@
+use crate::{dialog::ZenityApplication, CancelFlag, CommandRunner, ZenityDialog, ZenityOutput};
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    sync::{Arc, Mutex},
+    thread,
+};
+
+/// Shows dialogs on worker threads and delivers their outcomes as messages, for GUI frameworks
+/// such as egui or iced that process results in their update loop instead of blocking on them.
+///
+/// Every request is identified by a caller-chosen `request_id`. Each request produces exactly
+/// one message at most: either its outcome, or [ErasedOutcome::Cancelled] if it was cancelled
+/// first. Once a request has been delivered or cancelled, its id can be reused.
+///
+/// Dialogs are spawned as with [ZenityDialog::show_with_cancel], or run through a
+/// [CommandRunner] for bridges created with [DialogBridge::with_runner].
+#[derive(Clone)]
+pub struct DialogBridge {
+    sender: Arc<dyn Fn(BridgeMessage) + Send + Sync>,
+    runner: Option<Arc<dyn CommandRunner + Send + Sync>>,
+    in_flight: Arc<Mutex<InFlight>>,
+}
+
+/// The requests that have been shown and not yet delivered.
+#[derive(Debug, Default)]
+struct InFlight {
+    /// The requests by id
+    requests: HashMap<u64, Request>,
+    /// The generation of the next request
+    next_generation: u64,
+}
+
+/// A request that is being shown.
+#[derive(Debug)]
+struct Request {
+    /// Tells this request apart from earlier requests with the same id, whose workers may still
+    /// be finishing
+    generation: u64,
+    /// Closes the request's dialog when it's cancelled
+    cancel: CancelFlag,
+}
+
+/// The outcome of a dialog shown through a [DialogBridge].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BridgeMessage {
+    /// The id the dialog was shown with
+    pub request_id: u64,
+    /// What happened to the dialog
+    pub outcome: ErasedOutcome,
+}
+
+/// A type-erased view of [ZenityOutput], with the affirmed content rendered as a string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErasedOutcome {
+    /// See [ZenityOutput::Affirmed]
+    Affirmed {
+        /// The affirmed content, rendered with [Display]
+        content: Option<String>,
+    },
+    /// See [ZenityOutput::Rejected]
+    Rejected {
+        /// The rejection content
+        content: Option<String>,
+    },
+    /// See [ZenityOutput::TimedOut]
+    TimedOut {
+        /// Any output printed when the dialog timed out
+        content: Option<String>,
+    },
+    /// See [ZenityOutput::Dismissed]
+    Dismissed,
+    /// See [ZenityOutput::Unknown]
+    Unknown {
+        /// The returned exit code.
+        exit_code: i32,
+        /// The content Zenity returned to stdout.
+        stdout: String,
+        /// The content Zenity returned to stderr.
+        stderr: String,
+    },
+    /// Showing the dialog failed with a [crate::Error], rendered as a string
+    Failed(String),
+    /// The request was cancelled with [DialogBridge::cancel] before the dialog finished
+    Cancelled,
+}
+
+impl<T> From<ZenityOutput<T>> for ErasedOutcome
+where
+    T: Display,
+{
+    fn from(value: ZenityOutput<T>) -> Self {
+        match value {
+            ZenityOutput::Affirmed { content } => ErasedOutcome::Affirmed {
+                content: content.map(|content| content.to_string()),
+            },
+            ZenityOutput::Rejected { content } => ErasedOutcome::Rejected { content },
+            ZenityOutput::TimedOut { content } => ErasedOutcome::TimedOut { content },
+            ZenityOutput::Dismissed => ErasedOutcome::Dismissed,
+            ZenityOutput::Unknown {
+                exit_code,
+                stdout,
+                stderr,
+            } => ErasedOutcome::Unknown {
+                exit_code,
+                stdout,
+                stderr,
+            },
+        }
+    }
+}
+
+impl DialogBridge {
+    /// Create a bridge that delivers outcomes through `sender`, for example by forwarding them
+    /// into a channel or an event loop proxy. The sender is called from worker threads.
+    pub fn new(sender: impl Fn(BridgeMessage) + Send + Sync + 'static) -> Self {
+        Self {
+            sender: Arc::new(sender),
+            runner: None,
+            in_flight: Default::default(),
+        }
+    }
+
+    /// Create a bridge that runs its dialogs through the runner, as with
+    /// [ZenityDialog::show_with_runner], for example to test an application's dialog flows
+    /// with a `MockRunner`. Cancelling a request still delivers [ErasedOutcome::Cancelled]
+    /// immediately, but it's up to the runner to stop a dialog that's running.
+    pub fn with_runner(
+        sender: impl Fn(BridgeMessage) + Send + Sync + 'static,
+        runner: impl CommandRunner + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            runner: Some(Arc::new(runner)),
+            ..Self::new(sender)
+        }
+    }
+
+    /// Show the dialog on a worker thread and deliver its outcome under `request_id`. Returns
+    /// `false` without showing anything if a request with the same id is still in flight.
+    pub fn show<T>(&self, dialog: ZenityDialog<T>, request_id: u64) -> bool
+    where
+        T: ZenityApplication + Send + 'static,
+        T::Return: Display,
+    {
+        let cancel = CancelFlag::new();
+        let generation = {
+            let mut in_flight = self.lock();
+            if in_flight.requests.contains_key(&request_id) {
+                return false;
+            }
+
+            let generation = in_flight.next_generation;
+            in_flight.next_generation += 1;
+            in_flight.requests.insert(
+                request_id,
+                Request {
+                    generation,
+                    cancel: cancel.clone(),
+                },
+            );
+            generation
+        };
+
+        let bridge = self.clone();
+        thread::spawn(move || {
+            let output = match bridge.runner {
+                Some(ref runner) => dialog.show_with_runner(&runner.as_ref()),
+                None => dialog.show_with_cancel(&cancel),
+            };
+            let outcome = match output {
+                Ok(output) => output.into(),
+                Err(err) => ErasedOutcome::Failed(err.to_string()),
+            };
+            bridge.deliver(request_id, Some(generation), outcome);
+        });
+
+        true
+    }
+
+    /// Cancel an in-flight request, closing its dialog. The request's outcome is replaced with
+    /// [ErasedOutcome::Cancelled], which is delivered immediately. Returns `false` if no request
+    /// with the id was in flight.
+    pub fn cancel(&self, request_id: u64) -> bool {
+        self.deliver(request_id, None, ErasedOutcome::Cancelled)
+    }
+
+    /// Whether a request with the id has been shown and not yet delivered.
+    pub fn is_in_flight(&self, request_id: u64) -> bool {
+        self.lock().requests.contains_key(&request_id)
+    }
+
+    /// The ids of all requests that have been shown and not yet delivered.
+    pub fn in_flight(&self) -> Vec<u64> {
+        self.lock().requests.keys().copied().collect()
+    }
+
+    /// Deliver the outcome if the request is still in flight, ensuring each request is
+    /// delivered at most once. A worker passes the generation of its request, so that it
+    /// can't deliver to a later request with the same id; cancelling passes [None] and closes
+    /// the request's dialog.
+    fn deliver(&self, request_id: u64, generation: Option<u64>, outcome: ErasedOutcome) -> bool {
+        // The lock is released before calling the sender so that it may call back into the
+        // bridge.
+        let request = {
+            let mut in_flight = self.lock();
+            match in_flight.requests.get(&request_id) {
+                Some(request)
+                    if generation.map_or(true, |expected| expected == request.generation) =>
+                {
+                    in_flight.requests.remove(&request_id)
+                }
+                _ => None,
+            }
+        };
+
+        let Some(request) = request else {
+            return false;
+        };
+
+        request.cancel.cancel();
+        (self.sender)(BridgeMessage {
+            request_id,
+            outcome,
+        });
+
+        true
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, InFlight> {
+        self.in_flight
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl std::fmt::Debug for DialogBridge {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DialogBridge")
+            .field("runner", &self.runner.is_some())
+            .field("in_flight", &self.in_flight)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(all(test, feature = "entry"))]
+mod tests {
+    use super::{BridgeMessage, DialogBridge, ErasedOutcome};
+    use crate::{
+        dialog::{Entry, Info},
+        runner::{MockResponse, MockRunner},
+        CommandRunner, ZenityDialog,
+    };
+    use std::{
+        ffi::{OsStr, OsString},
+        io,
+        process::Output,
+        sync::{mpsc, Mutex},
+        time::Duration,
+    };
+
+    /// A bridge whose messages are collected into a channel.
+    fn bridge(runner: impl CommandRunner + Send + Sync + 'static) -> (DialogBridge, Messages) {
+        let (sender, receiver) = mpsc::channel();
+        let sender = Mutex::new(sender);
+        let bridge = DialogBridge::with_runner(
+            move |message| sender.lock().unwrap().send(message).unwrap(),
+            runner,
+        );
+        (bridge, Messages(receiver))
+    }
+
+    struct Messages(mpsc::Receiver<BridgeMessage>);
+
+    impl Messages {
+        fn next(&self) -> BridgeMessage {
+            self.0.recv_timeout(Duration::from_secs(5)).unwrap()
+        }
+
+        fn assert_empty(&self) {
+            assert_eq!(self.0.recv_timeout(Duration::from_millis(50)).ok(), None);
+        }
+    }
+
+    /// A runner that holds every dialog open until it's released.
+    struct GatedRunner {
+        inner: MockRunner,
+        gate: Mutex<mpsc::Receiver<()>>,
+    }
+
+    impl GatedRunner {
+        fn new(inner: MockRunner) -> (Self, mpsc::Sender<()>) {
+            let (release, gate) = mpsc::channel();
+            let runner = Self {
+                inner,
+                gate: Mutex::new(gate),
+            };
+            (runner, release)
+        }
+    }
+
+    impl CommandRunner for GatedRunner {
+        fn run(&self, program: &OsStr, args: &[OsString]) -> io::Result<Output> {
+            self.gate.lock().unwrap().recv().unwrap();
+            self.inner.run(program, args)
+        }
+    }
+
+    #[test]
+    fn delivers_outcomes_from_the_runner() {
+        let runner = MockRunner::new()
+            .respond_to_arg("--entry", MockResponse::affirmed("alice\n"))
+            .respond_to_arg("--info", MockResponse::timed_out());
+        let (bridge, messages) = bridge(runner);
+
+        assert!(bridge.show(ZenityDialog::new(Entry::new()), 1));
+        assert_eq!(
+            messages.next(),
+            BridgeMessage {
+                request_id: 1,
+                outcome: ErasedOutcome::Affirmed {
+                    content: Some("alice".to_owned())
+                },
+            }
+        );
+
+        assert!(bridge.show(ZenityDialog::new(Info::new()), 2));
+        assert_eq!(
+            messages.next().outcome,
+            ErasedOutcome::TimedOut { content: None }
+        );
+        assert!(bridge.in_flight().is_empty());
+    }
+
+    #[test]
+    fn delivers_failures_as_strings() {
+        let (bridge, messages) = bridge(MockRunner::new());
+
+        bridge.show(ZenityDialog::new(Entry::new()), 7);
+        let message = messages.next();
+        assert_eq!(message.request_id, 7);
+        assert!(
+            matches!(message.outcome, ErasedOutcome::Failed(ref err) if !err.is_empty()),
+            "{:?}",
+            message
+        );
+    }
+
+    #[test]
+    fn refuses_ids_that_are_in_flight() {
+        let runner = MockRunner::new().respond_otherwise(MockResponse::rejected());
+        let (runner, release) = GatedRunner::new(runner);
+        let (bridge, messages) = bridge(runner);
+
+        assert!(bridge.show(ZenityDialog::new(Entry::new()), 1));
+        assert!(!bridge.show(ZenityDialog::new(Entry::new()), 1));
+        assert!(bridge.show(ZenityDialog::new(Entry::new()), 2));
+        let mut in_flight = bridge.in_flight();
+        in_flight.sort();
+        assert_eq!(in_flight, [1, 2]);
+
+        release.send(()).unwrap();
+        release.send(()).unwrap();
+        let mut delivered = vec![messages.next().request_id, messages.next().request_id];
+        delivered.sort();
+        assert_eq!(delivered, [1, 2]);
+        messages.assert_empty();
+        assert!(!bridge.is_in_flight(1));
+    }
+
+    #[test]
+    fn cancelled_requests_are_delivered_once() {
+        let runner = MockRunner::new().respond_otherwise(MockResponse::affirmed("late\n"));
+        let (runner, release) = GatedRunner::new(runner);
+        let (bridge, messages) = bridge(runner);
+
+        assert!(bridge.show(ZenityDialog::new(Entry::new()), 1));
+        assert!(bridge.cancel(1));
+        assert_eq!(
+            messages.next(),
+            BridgeMessage {
+                request_id: 1,
+                outcome: ErasedOutcome::Cancelled,
+            }
+        );
+        assert!(!bridge.cancel(1));
+
+        // The id can be reused while the cancelled worker is still running, and the worker's
+        // late outcome isn't delivered to the new request.
+        assert!(bridge.show(ZenityDialog::new(Entry::new()), 1));
+        release.send(()).unwrap();
+        release.send(()).unwrap();
+        assert_eq!(
+            messages.next(),
+            BridgeMessage {
+                request_id: 1,
+                outcome: ErasedOutcome::Affirmed {
+                    content: Some("late".to_owned())
+                },
+            }
+        );
+        messages.assert_empty();
+        assert!(bridge.in_flight().is_empty());
+    }
+}
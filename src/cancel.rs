@@ -0,0 +1,49 @@
+use std::{
+    sync::{Arc, Condvar, Mutex},
+    time::Duration,
+};
+
+/// A flag for cancelling dialogs from another thread. Clones share the same flag. See
+/// [crate::ZenityDialog::show_with_cancel].
+#[derive(Debug, Clone, Default)]
+pub struct CancelFlag {
+    inner: Arc<(Mutex<bool>, Condvar)>,
+}
+
+impl CancelFlag {
+    /// Create a flag that isn't cancelled.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Cancel every dialog waiting on the flag. The flag stays cancelled.
+    pub fn cancel(&self) {
+        let (ref cancelled, ref condvar) = *self.inner;
+        *cancelled
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = true;
+        condvar.notify_all();
+    }
+
+    /// Whether the flag has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        *self
+            .inner
+            .0
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Block until the flag is cancelled or the timeout elapses. Returns whether the flag is
+    /// cancelled.
+    pub(crate) fn wait_timeout(&self, timeout: Duration) -> bool {
+        let (ref cancelled, ref condvar) = *self.inner;
+        let guard = cancelled
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let (guard, _) = condvar
+            .wait_timeout_while(guard, timeout, |cancelled| !*cancelled)
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        *guard
+    }
+}
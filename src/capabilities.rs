@@ -0,0 +1,85 @@
+use crate::{probe, ZenitySettings};
+use std::{collections::BTreeSet, ffi::OsStr, path::Path};
+
+static CAPABILITIES: probe::ProbeCache<Capabilities> = probe::ProbeCache::new();
+
+/// The command line options an installed Zenity supports, as listed by `zenity --help-all`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Capabilities {
+    options: BTreeSet<String>,
+}
+
+impl Capabilities {
+    /// Parse the output of `zenity --help-all`. Every long option listed in the output is
+    /// recorded, whether it's shown alone, as in `--modal`, with a value, as in
+    /// `--title=TITLE`, or after a short alias, as in `-?, --help`.
+    pub fn parse(help: &str) -> Self {
+        let options = help
+            .lines()
+            .filter_map(|line| {
+                // Option lines are indented and start with the option, followed by its
+                // description after a run of spaces.
+                let line = line.trim_start();
+                line.starts_with('-').then_some(line)
+            })
+            .flat_map(|line| {
+                line.split_whitespace()
+                    .take_while(|token| token.starts_with('-'))
+                    .filter(|token| token.starts_with("--") && token.len() > 2)
+                    .map(|token| Self::normalize(token).to_owned())
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+
+        Self { options }
+    }
+
+    /// Whether the option is supported. The option may be given with or without the leading
+    /// dashes and with a value, as in `--title=Hello`.
+    pub fn supports(&self, option: &str) -> bool {
+        self.options.contains(Self::normalize(option))
+    }
+
+    /// The supported long options, without leading dashes.
+    pub fn options(&self) -> impl Iterator<Item = &str> {
+        self.options.iter().map(String::as_str)
+    }
+
+    /// The options in `args` that aren't supported, with leading dashes and without values.
+    /// Arguments that aren't options are ignored.
+    pub fn unsupported<'a>(&self, args: impl IntoIterator<Item = &'a OsStr>) -> Vec<String> {
+        args.into_iter()
+            .map(|arg| arg.to_string_lossy())
+            .filter(|arg| arg.starts_with("--") && !self.supports(arg))
+            .map(|arg| format!("--{}", Self::normalize(&arg)))
+            .collect()
+    }
+
+    /// The bare option name: without leading dashes, a trailing comma, or a value.
+    fn normalize(option: &str) -> &str {
+        let option = option.trim_start_matches('-').trim_end_matches(',');
+        match option.split_once('=') {
+            Some((name, _)) => name,
+            None => option,
+        }
+    }
+}
+
+/// The capabilities of the Zenity executable configured in the installed [ZenitySettings]. The
+/// result is cached per program path, including failures.
+pub fn capabilities() -> crate::Result<Capabilities> {
+    program_capabilities(ZenitySettings::current().resolved_program())
+}
+
+/// The capabilities of the provided Zenity executable, as listed by `--help-all`. The result is
+/// cached per program path, including failures.
+pub fn program_capabilities(program: impl AsRef<Path>) -> crate::Result<Capabilities> {
+    CAPABILITIES.get_or_probe(program.as_ref(), |program| {
+        probe::run(program, "--help-all").map(|help| Capabilities::parse(&help))
+    })
+}
+
+/// Drop the cached capabilities of the program, so that they're probed again on next use.
+pub(crate) fn forget_capabilities(program: &Path) {
+    CAPABILITIES.forget(program);
+}
@@ -1,24 +1,123 @@
+#[macro_use]
 mod application;
+#[macro_use]
+mod builders;
+#[macro_use]
+mod message;
 mod calendar;
+mod date;
+mod diff;
+mod dry_run;
+mod dyn_dialog;
 mod entry;
 mod error;
+#[cfg(feature = "file-selection")]
+mod file_selection;
+#[cfg(any(feature = "async-tokio", feature = "async-io"))]
+mod future;
+mod handle;
 mod info;
+#[cfg(feature = "list")]
+mod list;
+#[cfg(feature = "notification")]
+mod notification;
+mod output;
+#[cfg(feature = "question")]
+mod question;
+#[cfg(feature = "entry")]
+mod repeat;
+mod show_ext;
+#[cfg(feature = "serde")]
+mod spec;
+#[cfg(all(test, unix))]
+mod tests;
+#[cfg(feature = "entry")]
+mod typed_entry;
+#[cfg(feature = "entry")]
+mod validated;
+mod warning;
+mod wizard;
 
-use crate::Arg;
-pub use dialog::application::ZenityApplication;
+#[cfg(feature = "idle-detect")]
+use crate::IdleDeferral;
+#[cfg(feature = "sound")]
+use crate::SoundCue;
+use crate::{
+    arg::{duplicate_options, option_name, redact},
+    backend::{detected_backend, DialogBackend, Outcome, ZenityBackend},
+    signals::Tracked,
+    version::FlagStyle,
+    Arg, CommandRunner, ZenitySettings,
+};
+pub use dialog::application::{HasText, ToArgVector, ZenityApplication};
+pub use dialog::diff::{DialogDiff, DiffEntry};
+pub use dialog::dry_run::DryRun;
+pub use dialog::dyn_dialog::{DynDialog, DynOutput};
+pub use dialog::handle::DialogHandle;
+pub use dialog::output::{DialogDismissed, DialogOutput, RawOutput, ShownDialog};
+pub use dialog::show_ext::ShowExt;
+#[cfg(feature = "serde")]
+pub use dialog::spec::DialogSpec;
+pub use dialog::wizard::{Wizard, WizardOutcome};
 
 #[cfg(feature = "calendar")]
-pub use dialog::calendar::{Calendar, Month};
+pub use dialog::calendar::{Calendar, InvalidMonth, Month};
+#[cfg(feature = "calendar")]
+pub use dialog::date::{Date, InvalidDate};
 #[cfg(feature = "entry")]
-pub use dialog::entry::Entry;
+pub use dialog::entry::{Entry, EntryAnswer, EntryFocus};
 #[cfg(feature = "error")]
 pub use dialog::error::Error;
+#[cfg(feature = "file-selection")]
+pub use dialog::file_selection::{FileFilter, FileSelection};
 #[cfg(feature = "info")]
 pub use dialog::info::Info;
-use std::{fmt::Display, io, path::PathBuf, process::Command, time::Duration};
+#[cfg(feature = "list")]
+pub use dialog::list::List;
+#[cfg(feature = "notification")]
+pub use dialog::notification::Notification;
+#[cfg(feature = "question")]
+pub use dialog::question::{confirm, Question};
+#[cfg(feature = "entry")]
+pub use dialog::repeat::{RepeatOptions, RepeatResult, Termination};
+#[cfg(feature = "entry")]
+pub use dialog::typed_entry::TypedEntry;
+#[cfg(feature = "entry")]
+pub use dialog::validated::ValidatedOutcome;
+#[cfg(feature = "warning")]
+pub use dialog::warning::Warning;
+#[cfg(unix)]
+use std::os::unix::process::ExitStatusExt;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    env,
+    ffi::{OsStr, OsString},
+    fmt::Display,
+    io,
+    path::{Path, PathBuf},
+    process::{Command, ExitStatus, Output, Stdio},
+    time::{Duration, Instant},
+};
+
+/// The state of a dialog after [ZenityDialog::prepare].
+enum Prepared<R> {
+    /// The dialog was handled without spawning the backend.
+    Done(ZenityOutput<R>),
+    /// The backend is ready to be spawned.
+    Spawn(Command),
+}
 
 /// The configuration for a Zenity dialog.
-#[derive(Debug, Clone, PartialEq)]
+///
+/// With the `serde` feature, dialogs can be loaded from definition files; see
+/// [ZenityDialog::from_json_str]. Timeouts are given in seconds, and the settings without a
+/// public field, such as the environment, can't be loaded.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(::serde::Deserialize),
+    serde(default, deny_unknown_fields)
+)]
 pub struct ZenityDialog<T = Info>
 where
     T: ZenityApplication,
@@ -34,10 +133,45 @@ where
     /// Override default height of dialog
     pub height: Option<usize>,
     /// Duration after which the dialog automatically closes
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "spec::seconds"))]
     pub timeout: Option<Duration>,
-    /// Provide extra hint text to the user.
-    pub modal_hint: Option<String>,
-    additional_args: Vec<String>,
+    /// Duration after which the Zenity process is killed, whether or not it honors `timeout`
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "spec::seconds"))]
+    pub hard_timeout: Option<Duration>,
+    /// Whether the dialog should be rendered as modal.
+    pub modal: bool,
+    /// The Zenity executable to run, overriding [crate::ZenitySettings::program]
+    pub program: Option<PathBuf>,
+    /// The working directory of the Zenity process, against which relative paths resolve
+    pub current_dir: Option<PathBuf>,
+    /// A sound to play when the dialog is shown
+    #[cfg(feature = "sound")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub sound: Option<SoundCue>,
+    /// Defer showing the dialog while the user is idle
+    #[cfg(feature = "idle-detect")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub idle_deferral: Option<IdleDeferral>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    additional_args: Vec<Arg>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    overridden: BTreeSet<String>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    env: BTreeMap<OsString, Option<OsString>>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    env_clear: bool,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    gtk_theme: Option<String>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    prefer_dark: bool,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    allow_headless: bool,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    strict_capabilities: bool,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    flag_style: FlagStyle,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    die_with_parent: bool,
 }
 
 impl<T> Default for ZenityDialog<T>
@@ -52,8 +186,24 @@ where
             width: Default::default(),
             height: Default::default(),
             timeout: Default::default(),
-            modal_hint: Default::default(),
+            hard_timeout: Default::default(),
+            modal: Default::default(),
+            program: Default::default(),
+            current_dir: Default::default(),
+            #[cfg(feature = "sound")]
+            sound: Default::default(),
+            #[cfg(feature = "idle-detect")]
+            idle_deferral: Default::default(),
             additional_args: Default::default(),
+            overridden: Default::default(),
+            env: Default::default(),
+            env_clear: Default::default(),
+            gtk_theme: Default::default(),
+            prefer_dark: Default::default(),
+            allow_headless: Default::default(),
+            strict_capabilities: Default::default(),
+            flag_style: Default::default(),
+            die_with_parent: Default::default(),
         }
     }
 }
@@ -65,11 +215,9 @@ impl<T> ZenityDialog<T>
 where
     T: ZenityApplication + Default,
 {
-    /// Zenity returns zero when users select an afirmitive response.
-    const SUCCESS_CODE: i32 = 0;
-    /// Zenity returns 256 when users select a negative response.
-    const ERROR_CODE_1: i32 = 256;
-    const ERROR_CODE_2: i32 = 1;
+    /// How long a dialog with both a regular and a hard timeout is given beyond the regular
+    /// timeout to close by itself. See [ZenityDialog::with_hard_timeout].
+    pub const HARD_TIMEOUT_GRACE: Duration = Duration::from_secs(1);
 
     /// Construct a new Zenity instance. It expects an [Application], which determines which
     /// kind of dialog will be displayed.
@@ -80,34 +228,74 @@ where
         }
     }
 
-    /// Provide a custom title for the dialog.
-    pub fn with_title(mut self, title: impl Into<String>) -> Self {
-        self.title = Some(title.into());
+    /// Set the body text of the application, whatever its type. See [HasText].
+    pub fn with_body_text(mut self, text: impl Into<String>) -> Self
+    where
+        T: HasText,
+    {
+        self.set_body_text(text);
         self
     }
 
-    /// Override the default icon.
-    pub fn with_icon(mut self, icon: Icon) -> Self {
-        self.icon = Some(icon);
+    /// Set the body text of the application, in place. See [HasText].
+    pub fn set_body_text(&mut self, text: impl Into<String>) -> &mut Self
+    where
+        T: HasText,
+    {
+        HasText::set_text(&mut self.application, text.into());
         self
     }
 
-    /// Set a specific width for the dialog.
-    pub fn with_width(mut self, width: usize) -> Self {
-        self.width = Some(width);
-        self
+    /// Construct a new Zenity instance from anything that converts into the application, such
+    /// as the text of a message application. The application type can't be inferred from the
+    /// converted value, so name it, either on the dialog or by relying on the default of
+    /// [Info]:
+    ///
+    /// ```
+    /// # use zenity_dialog::{dialog::Question, ZenityDialog};
+    /// let info: ZenityDialog = ZenityDialog::from_application("Something went wrong");
+    /// let question = ZenityDialog::<Question>::from_application("Retry?");
+    /// # assert_eq!(info.application.text.as_deref(), Some("Something went wrong"));
+    /// # assert_eq!(question.application.text.as_deref(), Some("Retry?"));
+    /// ```
+    ///
+    /// [ZenityDialog::new] takes the application itself, so that its type is always inferred.
+    pub fn from_application(application: impl Into<T>) -> Self {
+        Self::new(application.into())
     }
 
-    /// Set a specific height for the dialog.
-    pub fn with_height(mut self, height: usize) -> Self {
-        self.height = Some(height);
-        self
-    }
-
-    /// Make the dialog close automatically after the duration has passed.
-    pub fn with_timeout(mut self, timeout: Duration) -> Self {
-        self.timeout = Some(timeout);
-        self
+    /// Replace the application while keeping every other setting of the dialog, such as its
+    /// title, icon, size, timeout, and additional arguments.
+    pub fn with_application<U>(self, application: U) -> ZenityDialog<U>
+    where
+        U: ZenityApplication,
+    {
+        ZenityDialog {
+            application,
+            title: self.title,
+            icon: self.icon,
+            width: self.width,
+            height: self.height,
+            timeout: self.timeout,
+            hard_timeout: self.hard_timeout,
+            modal: self.modal,
+            program: self.program,
+            current_dir: self.current_dir,
+            #[cfg(feature = "sound")]
+            sound: self.sound,
+            #[cfg(feature = "idle-detect")]
+            idle_deferral: self.idle_deferral,
+            additional_args: self.additional_args,
+            overridden: self.overridden,
+            env: self.env,
+            env_clear: self.env_clear,
+            gtk_theme: self.gtk_theme,
+            prefer_dark: self.prefer_dark,
+            allow_headless: self.allow_headless,
+            strict_capabilities: self.strict_capabilities,
+            flag_style: self.flag_style,
+            die_with_parent: self.die_with_parent,
+        }
     }
 
     /// Render an extra button with the provided text as a label.
@@ -117,206 +305,1131 @@ where
     ) -> ZenityDialogExtButton<T> {
         ZenityDialogExtButton {
             inner: self,
-            extra_button_label: extra_button_label.into(),
+            extra_button_labels: vec![extra_button_label.into()],
         }
     }
 
-    /// Render a hint displaying the provided text.
-    pub fn with_modal_hint(mut self, modal_hint: impl Into<String>) -> Self {
-        self.modal_hint = Some(modal_hint.into());
+    dialog_builders!();
+
+    /// The dialog the shared builder methods configure.
+    fn dialog_mut(&mut self) -> &mut ZenityDialog<T> {
         self
     }
 
-    /// Attach an additional custom argument. Used to handle arguments that aren't currently statically
-    /// supported. Use at your own risk. Note that this function will automatically prepend -- to the argument
-    /// so there is no need to provide it. However, if you do provide it, it will still work.
-    pub fn with_additional_arg(mut self, arg: impl Into<Arg>) -> Self {
-        let arg: Arg = arg.into();
-        self.additional_args.push(arg.to_string());
-        self
+    /// The option names, as in `--width`, that appear more than once in the arguments passed
+    /// to Zenity. Which of the duplicates Zenity uses depends on its version.
+    pub fn conflicts(&self) -> Vec<String> {
+        duplicate_options(&self.to_args())
     }
 
-    /// Like `with_additional_arg`, but takes a [Vec<Arg>]
-    pub fn with_additional_args(mut self, args: Vec<Arg>) -> Self {
-        self.additional_args
-            .append(&mut args.iter().map(Arg::to_string).collect::<Vec<String>>());
-        self
+    /// Compare the configuration of this dialog with another one, including the fields of the
+    /// application. Useful for tracking down why two dialogs built from the same code look
+    /// different.
+    pub fn diff(&self, other: &ZenityDialog<T>) -> DialogDiff {
+        DialogDiff::between(&self.field_map(), &other.field_map())
     }
 
-    /// Convert the settings into an argument vector.
-    fn get_argv(&mut self) -> Vec<String> {
-        let mut args = self.application.to_argv();
+    /// The configured fields of the dialog, keyed by field name.
+    fn field_map(&self) -> BTreeMap<String, String> {
+        let mut fields: BTreeMap<String, String> = self
+            .application
+            .field_map()
+            .into_iter()
+            .map(|(name, value)| (format!("application.{name}"), value))
+            .collect();
 
         if let Some(ref title) = self.title {
-            args.push(format!("--title={title}"));
+            fields.insert("title".to_string(), title.to_string());
         }
 
         if let Some(ref icon) = self.icon {
-            args.push(format!("--icon-name={icon}"));
+            fields.insert("icon".to_string(), format!("{icon:?}"));
         }
 
         if let Some(ref width) = self.width {
-            args.push(format!("--width={width}"));
+            fields.insert("width".to_string(), width.to_string());
         }
 
         if let Some(ref height) = self.height {
-            args.push(format!("--height={height}"));
+            fields.insert("height".to_string(), height.to_string());
         }
 
         if let Some(ref timeout) = self.timeout {
-            args.push(format!("--timeout={}", timeout.as_secs()));
+            fields.insert("timeout".to_string(), format!("{timeout:?}"));
+        }
+
+        if let Some(ref hard_timeout) = self.hard_timeout {
+            fields.insert("hard_timeout".to_string(), format!("{hard_timeout:?}"));
+        }
+
+        if self.modal {
+            fields.insert("modal".to_string(), true.to_string());
+        }
+
+        if let Some(ref program) = self.program {
+            fields.insert("program".to_string(), program.display().to_string());
+        }
+
+        if let Some(ref current_dir) = self.current_dir {
+            fields.insert("current_dir".to_string(), current_dir.display().to_string());
+        }
+
+        if self.env_clear {
+            fields.insert("env_clear".to_string(), true.to_string());
+        }
+
+        if let Some(ref gtk_theme) = self.gtk_theme {
+            fields.insert("gtk_theme".to_string(), gtk_theme.to_string());
+        }
+
+        if self.prefer_dark {
+            fields.insert("prefer_dark".to_string(), true.to_string());
+        }
+
+        if self.allow_headless {
+            fields.insert("allow_headless".to_string(), true.to_string());
+        }
+
+        if self.strict_capabilities {
+            fields.insert("strict_capabilities".to_string(), true.to_string());
+        }
+
+        if self.die_with_parent {
+            fields.insert("die_with_parent".to_string(), true.to_string());
+        }
+
+        if self.flag_style != FlagStyle::Auto {
+            fields.insert("flag_style".to_string(), format!("{:?}", self.flag_style));
         }
 
-        if let Some(ref modal_hint) = self.modal_hint {
-            args.push(format!("--modal={modal_hint}"));
+        for (key, value) in &self.env {
+            let value = match value {
+                Some(value) => value.to_string_lossy().into_owned(),
+                None => "<removed>".to_string(),
+            };
+            fields.insert(format!("env.{}", key.to_string_lossy()), value);
+        }
+
+        if !self.additional_args.is_empty() {
+            let additional_args: Vec<_> = self.additional_args.iter().map(Arg::to_string).collect();
+            fields.insert("additional_args".to_string(), additional_args.join(" "));
+        }
+
+        fields
+    }
+
+    /// Convert the settings into the argument vector passed to Zenity. The returned arguments
+    /// are exactly the ones, in the same order, that `show` executes, except that options
+    /// renamed in Zenity 4, as in `--icon-name`, are only renamed for the program's version
+    /// when the dialog is spawned. Until then they keep the names set by
+    /// [ZenityDialog::force_legacy_flags] or [ZenityDialog::force_modern_flags], or follow the
+    /// version of the configured program if it was already probed, or else keep the legacy
+    /// names, which Zenity 4 still accepts. Nothing is executed to build them.
+    pub fn to_args(&self) -> Vec<OsString> {
+        self.resolved_args(self.flag_style.resolve_cached(Path::new(&self.program())))
+    }
+
+    /// The arguments passed to the program, with the option names of the style and the
+    /// overrides and additional arguments applied. Every way of showing or describing the
+    /// dialog goes through here, so that they agree.
+    fn resolved_args(&self, style: FlagStyle) -> Vec<OsString> {
+        let mut args = self.generated_args();
+        args.retain(|arg| !self.is_overridden(arg));
+        args.extend(self.additional_args.iter().map(Arg::to_os_string));
+        style.translate(args)
+    }
+
+    /// Whether an argument generated from a typed setting is replaced by an override. Names
+    /// are compared across Zenity versions, so that overriding `icon` also replaces
+    /// `--icon-name`.
+    fn is_overridden(&self, arg: &OsStr) -> bool {
+        option_name(arg).is_some_and(|name| {
+            self.overridden
+                .iter()
+                .any(|overridden| FlagStyle::same_option(&name, overridden))
+        })
+    }
+
+    /// The arguments generated from the application and the typed settings, with the legacy
+    /// option names.
+    fn generated_args(&self) -> Vec<OsString> {
+        let base = self.absolute_current_dir();
+        let mut args = match base {
+            Some(ref base) => {
+                let mut application = self.application.clone();
+                application.resolve_relative_paths(base);
+                application.to_argv()
+            }
+            None => self.application.to_argv(),
         };
 
-        args.append(&mut self.additional_args);
+        if let Some(title) = self.title.as_deref().or(T::default_title()) {
+            args.push(format!("--title={title}").into());
+        }
+
+        if let Some(ref icon) = self.icon {
+            let mut arg = OsString::from("--icon-name=");
+            match (icon, &base) {
+                (Icon::IconPath(path), Some(base)) => arg.push(base.join(path)),
+                _ => arg.push(icon.as_os_str()),
+            }
+            args.push(arg);
+        }
+
+        if let Some(ref width) = self.width {
+            args.push(format!("--width={width}").into());
+        }
+
+        if let Some(ref height) = self.height {
+            args.push(format!("--height={height}").into());
+        }
+
+        if let Some(ref timeout) = self.timeout {
+            args.push(format!("--timeout={}", timeout.as_secs()).into());
+        }
+
+        if self.modal {
+            args.push("--modal".into());
+        };
 
         args
     }
 
+    /// The working directory set with `with_current_dir`, made absolute against the working
+    /// directory of this process.
+    fn absolute_current_dir(&self) -> Option<PathBuf> {
+        let current_dir = self.current_dir.as_ref()?;
+        match current_dir.is_absolute() {
+            true => Some(current_dir.clone()),
+            false => env::current_dir().ok().map(|cwd| cwd.join(current_dir)),
+        }
+    }
+
+    /// Describe the Zenity invocation `show` would make, without executing anything. See
+    /// [ZenityDialog::to_args] for the names of options renamed in Zenity 4.
+    pub fn dry_run(&self) -> DryRun {
+        DryRun {
+            program: self.program(),
+            args: self.to_args(),
+        }
+    }
+
+    /// Render the Zenity invocation `show` would make as a shell-quoted command line, for
+    /// logging. Also available through [Display]. The values of secret options, such as the
+    /// prefilled text of an [Entry] with hidden text, are replaced with `***`.
+    ///
+    /// ```
+    /// # use zenity_dialog::{dialog::Error, ZenityDialog};
+    /// let dialog = ZenityDialog::new(Error::new().with_text("Disk full")).force_modern_flags();
+    /// assert_eq!(
+    ///     dialog.to_command_string(),
+    ///     "zenity --error '--text=Disk full' --title=Error"
+    /// );
+    /// ```
+    pub fn to_command_string(&self) -> String {
+        let mut dry_run = self.dry_run();
+        redact(&mut dry_run.args, &self.application.secret_options());
+        dry_run.to_string()
+    }
+
     /// Render the dialog and wait for user response.
-    pub fn show(mut self) -> crate::Result<ZenityOutput<T::Return>> {
-        let args = self.get_argv();
-
-        let output =
-            Command::new("zenity")
-                .args(args)
-                .output()
-                .map_err(|err| match err.kind() {
-                    io::ErrorKind::NotFound => crate::error::Error::ZenityNotInstalled(err),
-                    _ => crate::Error::UnexpectedIoError(err),
-                })?;
-
-        let stdout = String::from_utf8(output.stdout)
-            .map_err(crate::Error::InvalidUtf8FromStdout)?
-            .trim()
-            .to_owned();
-
-        let code = output.status.code().ok_or(crate::Error::MissingExitCode)?;
-
-        let result = match (stdout.is_empty(), code) {
-            (true, Self::SUCCESS_CODE) => ZenityOutput::Affirmed { content: None },
-            (false, Self::SUCCESS_CODE) => ZenityOutput::Affirmed {
-                content: Some(self.application.parse(&stdout)?),
+    pub fn show(self) -> crate::Result<ZenityOutput<T::Return>> {
+        self.show_ref()
+    }
+
+    /// Like `show`, but doesn't consume the dialog so that it can be shown again, for example in
+    /// a retry loop.
+    pub fn show_ref(&self) -> crate::Result<ZenityOutput<T::Return>> {
+        self.show_with_backend(&self.backend())
+    }
+
+    /// Render the dialog with the first Zenity-compatible program found on `PATH`, as found by
+    /// [crate::backend::detect_backend], and wait for user response. The detection runs once
+    /// per process.
+    pub fn show_auto(&self) -> crate::Result<ZenityOutput<T::Return>> {
+        self.show_with_backend(Self::auto_backend()?)
+    }
+
+    /// Render the dialog without blocking, on the tokio runtime. The dialog is closed if the
+    /// future is dropped before it completes. Idle deferral and the terminal fallback still
+    /// block the calling thread.
+    #[cfg(feature = "async-tokio")]
+    pub fn show_async(
+        &self,
+    ) -> impl std::future::Future<Output = crate::Result<ZenityOutput<T::Return>>> + '_ {
+        future::ShowFuture::new(self, self.to_args(), future::spawn_tokio)
+    }
+
+    /// Render the dialog without blocking, on any runtime supported by `async-process`, such as
+    /// smol or async-std. The dialog is closed if the future is dropped before it completes.
+    /// Idle deferral and the terminal fallback still block the calling thread.
+    #[cfg(feature = "async-io")]
+    pub fn show_async_io(
+        &self,
+    ) -> impl std::future::Future<Output = crate::Result<ZenityOutput<T::Return>>> + '_ {
+        future::ShowFuture::new(self, self.to_args(), future::spawn_async_io)
+    }
+
+    /// Render the dialog with the provided backend instead of Zenity and wait for user
+    /// response.
+    pub fn show_with_backend(
+        &self,
+        backend: &impl DialogBackend,
+    ) -> crate::Result<ZenityOutput<T::Return>> {
+        self.run(backend, self.to_args())
+    }
+
+    /// Render the dialog with the session's Zenity executable instead of the configured one,
+    /// and wait for user response. See [crate::ZenitySession] for how the executable is kept up
+    /// to date.
+    pub fn show_with(
+        &self,
+        session: &crate::ZenitySession,
+    ) -> crate::Result<ZenityOutput<T::Return>> {
+        let result = self.show_with_backend(&session.backend());
+        session.observe(&result);
+        result
+    }
+
+    /// Render the dialog through the runner instead of spawning Zenity directly, and classify
+    /// its output as usual. The runner only receives the program and arguments: no display or
+    /// capability checks are made, and the process settings such as the environment are left
+    /// to the runner.
+    pub fn show_with_runner(
+        &self,
+        runner: &impl CommandRunner,
+    ) -> crate::Result<ZenityOutput<T::Return>> {
+        self.run_with_runner(runner, self.to_args())
+            .map(|shown| shown.output)
+    }
+
+    /// Like `show_ref`, but also returns what Zenity printed and its exit code, for example for
+    /// logging.
+    pub fn show_detailed(&self) -> crate::Result<ShownDialog<ZenityOutput<T::Return>>> {
+        self.validated()?;
+        self.run_detailed(&self.backend(), self.to_args())
+    }
+
+    /// Like `show_ref`, but skips [ZenityDialog::validate], passing the configuration to Zenity
+    /// as is.
+    pub fn show_unchecked(&self) -> crate::Result<ZenityOutput<T::Return>> {
+        self.run_unchecked(&self.backend(), self.to_args())
+    }
+
+    /// Check the configuration for values Zenity would silently ignore or misinterpret, such as
+    /// a width of zero, a timeout below one second, or a day that doesn't exist in the selected
+    /// month. Every problem is reported, including those found by the application's
+    /// [ZenityApplication::validate]. Showing the dialog runs these checks and fails with
+    /// [crate::Error::InvalidDialog]; see [ZenityDialog::show_unchecked] to skip them.
+    pub fn validate(&self) -> Result<(), Vec<crate::ValidationError>> {
+        let mut errors = Vec::new();
+
+        for (field, size) in [("width", self.width), ("height", self.height)] {
+            if size == Some(0) {
+                errors.push(crate::ValidationError::new(
+                    field,
+                    "must be greater than zero",
+                ));
+            }
+        }
+
+        if self.timeout.is_some_and(|timeout| timeout.as_secs() == 0) {
+            errors.push(crate::ValidationError::new(
+                "timeout",
+                "must be at least one second, as Zenity treats a timeout of zero as no timeout",
+            ));
+        }
+
+        if self.hard_timeout == Some(Duration::ZERO) {
+            errors.push(crate::ValidationError::new(
+                "hard_timeout",
+                "must be greater than zero",
+            ));
+        }
+
+        errors.extend(
+            self.additional_args
+                .iter()
+                .filter_map(|arg| arg.validate().err()),
+        );
+        errors.extend(self.application.validate());
+
+        match errors.is_empty() {
+            true => Ok(()),
+            false => Err(errors),
+        }
+    }
+
+    /// Validate the dialog before showing it.
+    fn validated(&self) -> crate::Result<()> {
+        self.validate().map_err(crate::Error::InvalidDialog)
+    }
+
+    /// Run the program through the runner with the provided arguments and classify its output.
+    fn run_with_runner(
+        &self,
+        runner: &impl CommandRunner,
+        args: Vec<OsString>,
+    ) -> crate::Result<ShownDialog<ZenityOutput<T::Return>>> {
+        self.validated()?;
+        let backend = self.backend();
+        let args = backend.translate_args(args);
+        let output = runner
+            .run(backend.program(), &args)
+            .map_err(|err| crate::Error::spawn(backend.program(), args, err))?;
+        self.finish_detailed(&backend, output)
+    }
+
+    /// Validate the dialog, then run the backend with the provided arguments and classify its
+    /// output.
+    fn run(
+        &self,
+        backend: &impl DialogBackend,
+        args: Vec<OsString>,
+    ) -> crate::Result<ZenityOutput<T::Return>> {
+        self.validated()?;
+        self.run_unchecked(backend, args)
+    }
+
+    /// Run the backend with the provided arguments and classify its output.
+    fn run_unchecked(
+        &self,
+        backend: &impl DialogBackend,
+        args: Vec<OsString>,
+    ) -> crate::Result<ZenityOutput<T::Return>> {
+        self.run_detailed(backend, args).map(|shown| shown.output)
+    }
+
+    /// Run the backend with the provided arguments and classify its output, keeping the raw
+    /// output alongside.
+    fn run_detailed(
+        &self,
+        backend: &impl DialogBackend,
+        args: Vec<OsString>,
+    ) -> crate::Result<ShownDialog<ZenityOutput<T::Return>>> {
+        #[cfg(all(feature = "serde", unix))]
+        let settings = ZenitySettings::current();
+
+        // An output of `None` is a dialog killed by the hard timeout.
+        #[cfg(all(feature = "serde", unix))]
+        let replayed = match settings.replay_from {
+            Some(ref path) => Some(crate::record::replay(
+                path,
+                &backend.translate_args(args.clone()),
+            )?),
+            None => None,
+        };
+        #[cfg(not(all(feature = "serde", unix)))]
+        let replayed = None;
+
+        let output = match replayed {
+            Some(output) => output,
+            None => match self.prepare(backend, args)? {
+                Prepared::Done(output) => return Ok(ShownDialog { output, raw: None }),
+                Prepared::Spawn(mut command) => {
+                    let child = command
+                        .stdin(Stdio::null())
+                        .stdout(Stdio::piped())
+                        .stderr(Stdio::piped())
+                        .spawn()
+                        .map_err(|err| crate::Error::spawn_command(&command, err))?;
+                    let _tracked = Tracked::new(child.id());
+
+                    let output = match self.effective_hard_timeout() {
+                        None => Some(
+                            child
+                                .wait_with_output()
+                                .map_err(crate::Error::UnexpectedIoError)?,
+                        ),
+                        Some(hard_timeout) => {
+                            handle::output_until(child, Instant::now() + hard_timeout)?
+                        }
+                    };
+
+                    #[cfg(all(feature = "serde", unix))]
+                    if let Some(ref path) = settings.record_to {
+                        let executed: Vec<OsString> =
+                            command.get_args().map(OsStr::to_owned).collect();
+                        crate::record::record(path, &executed, output.as_ref())
+                            .map_err(crate::Error::UnexpectedIoError)?;
+                    }
+
+                    output
+                }
             },
-            (true, Self::ERROR_CODE_1 | Self::ERROR_CODE_2) => {
-                ZenityOutput::Rejected { content: None }
+        };
+
+        match output {
+            Some(output) => self.finish_detailed(backend, output),
+            None => Ok(ShownDialog {
+                output: ZenityOutput::TimedOut { content: None },
+                raw: None,
+            }),
+        }
+    }
+
+    /// The hard timeout, extended to leave room for the regular timeout.
+    fn effective_hard_timeout(&self) -> Option<Duration> {
+        let hard_timeout = self.hard_timeout?;
+        Some(match self.timeout {
+            Some(timeout) => hard_timeout.max(timeout + Self::HARD_TIMEOUT_GRACE),
+            None => hard_timeout,
+        })
+    }
+
+    /// Everything that happens before the backend is spawned: waiting for the user, the
+    /// terminal fallback, and the checks that may prevent the dialog from being shown. Returns
+    /// the command to spawn, unless the dialog was already handled.
+    fn prepare(
+        &self,
+        backend: &impl DialogBackend,
+        args: Vec<OsString>,
+    ) -> crate::Result<Prepared<T::Return>> {
+        #[cfg(feature = "idle-detect")]
+        if let Some(ref idle_deferral) = self.idle_deferral {
+            idle_deferral.wait()?;
+        }
+
+        let has_display = self.has_display();
+
+        #[cfg(feature = "tty-fallback")]
+        if let Some(prompt) = self
+            .application
+            .tty_prompt()
+            .filter(|_| !has_display && crate::tty::is_interactive())
+        {
+            let title = self.title.as_deref().or(T::default_title());
+            let (code, stdout) = crate::tty::render_terminal(&prompt, title)
+                .map_err(crate::Error::UnexpectedIoError)?;
+            return self
+                .classify_output(backend, code, stdout, Vec::new())
+                .map(Prepared::Done);
+        }
+
+        if !has_display && !self.allow_headless {
+            Err(crate::Error::NoDisplay)?;
+        }
+
+        #[cfg(feature = "notify-fallback")]
+        if let Some(prefer_native) = self.application.native_notification() {
+            use crate::notify::{choose, NativeNotification, Notifier};
+
+            if choose(prefer_native, &crate::SystemRunner, backend.program()) == Notifier::Native {
+                let output = NativeNotification::from_args(&args)?.show()?;
+                return self.finish(backend, output).map(Prepared::Done);
             }
-            (false, Self::ERROR_CODE_1 | Self::ERROR_CODE_2) => ZenityOutput::Rejected {
-                content: Some(stdout),
+        }
+
+        // Probing the version starts a process, so it's left until the backend is spawned.
+        let args = self
+            .flag_style
+            .resolve(Path::new(backend.program()))
+            .translate(args);
+        self.check_capabilities(backend, &args)?;
+
+        #[cfg(feature = "sound")]
+        if let Some(ref sound) = self.sound {
+            sound.play();
+        }
+
+        let args = backend.translate_args(args);
+        if let Some(output) = backend.run(&args) {
+            return self.finish(backend, output?).map(Prepared::Done);
+        }
+
+        let mut command = Command::new(backend.program());
+        command.args(args);
+
+        if let Some(ref current_dir) = self.current_dir {
+            command.current_dir(current_dir);
+        }
+
+        if self.env_clear {
+            command.env_clear();
+        }
+
+        for (key, value) in self.child_env() {
+            match value {
+                Some(value) => command.env(key, value),
+                None => command.env_remove(key),
+            };
+        }
+
+        #[cfg(target_os = "linux")]
+        if self.die_with_parent {
+            Self::set_parent_death_signal(&mut command);
+        }
+
+        Ok(Prepared::Spawn(command))
+    }
+
+    /// Make the Zenity process receive SIGTERM when this process exits, and move it into its
+    /// own process group so that it isn't caught up in signals sent to ours.
+    #[cfg(target_os = "linux")]
+    fn set_parent_death_signal(command: &mut Command) {
+        use std::os::unix::process::CommandExt;
+
+        let parent = std::process::id();
+        // SAFETY: only async-signal-safe functions are called between fork and exec.
+        unsafe {
+            command.pre_exec(move || {
+                if libc::prctl(libc::PR_SET_PDEATHSIG, libc::SIGTERM) == -1
+                    || libc::setpgid(0, 0) == -1
+                {
+                    return Err(io::Error::last_os_error());
+                }
+
+                // The parent may have exited before the death signal was set up.
+                if libc::getppid() as u32 != parent {
+                    return Err(io::ErrorKind::BrokenPipe.into());
+                }
+
+                Ok(())
+            });
+        }
+    }
+
+    /// Decode and classify the output of the finished backend, keeping the raw output.
+    fn finish_detailed(
+        &self,
+        backend: &impl DialogBackend,
+        output: Output,
+    ) -> crate::Result<ShownDialog<ZenityOutput<T::Return>>> {
+        let raw = RawOutput::from(&output);
+        Ok(ShownDialog {
+            output: self.finish(backend, output)?,
+            raw: Some(raw),
+        })
+    }
+
+    /// Decode and classify the output of the finished backend.
+    fn finish(
+        &self,
+        backend: &impl DialogBackend,
+        output: Output,
+    ) -> crate::Result<ZenityOutput<T::Return>> {
+        let mut stdout =
+            String::from_utf8(output.stdout).map_err(crate::Error::InvalidUtf8FromStdout)?;
+        match self.application.trim_output() {
+            true => stdout = stdout.trim().to_owned(),
+            // Only the newline Zenity terminates its output with is removed.
+            false if stdout.ends_with('\n') => {
+                stdout.pop();
+            }
+            false => {}
+        }
+
+        let code = match output.status.code() {
+            Some(code) => code,
+            None => Err(Self::missing_exit_code(output.status, &output.stderr))?,
+        };
+
+        self.classify_output(backend, code, stdout, output.stderr)
+    }
+
+    /// Fail if Zenity is known not to support some of the options. Additional arguments are
+    /// only checked in strict mode. If the capabilities can't be determined, for example
+    /// because the program doesn't list its options, nothing is checked.
+    fn check_capabilities(
+        &self,
+        backend: &impl DialogBackend,
+        args: &[OsString],
+    ) -> crate::Result<()> {
+        // Only Zenity itself is known to list every option it supports. Clones such as qarma
+        // and matedialog are trusted to accept Zenity's options, and not probed at all.
+        if !backend.is_zenity() {
+            return Ok(());
+        }
+
+        let program = backend.program();
+        let Ok(capabilities) = crate::program_capabilities(program) else {
+            return Ok(());
+        };
+
+        if capabilities.options().next().is_none() {
+            return Ok(());
+        }
+
+        let additional_args: Vec<_> = self.additional_args.iter().map(Arg::to_os_string).collect();
+        let checked = args
+            .iter()
+            .filter(|arg| self.strict_capabilities || !additional_args.contains(arg))
+            .map(OsString::as_os_str);
+        let options = capabilities.unsupported(checked);
+
+        match options.is_empty() {
+            true => Ok(()),
+            false => Err(crate::Error::UnsupportedOption {
+                options,
+                installed_version: crate::program_version(program).ok(),
+            }),
+        }
+    }
+
+    /// Interpret the exit code and output of the dialog. Shared by the blocking and async
+    /// paths, so that they classify outputs identically.
+    fn classify_output(
+        &self,
+        backend: &impl DialogBackend,
+        code: i32,
+        stdout: String,
+        stderr: Vec<u8>,
+    ) -> crate::Result<ZenityOutput<T::Return>> {
+        // Whitespace only counts as content for applications that parse empty output.
+        let empty = match self.application.parses_empty_output() {
+            true => stdout.is_empty(),
+            false => stdout.trim().is_empty(),
+        };
+        let content = match empty {
+            true => None,
+            false => Some(stdout),
+        };
+
+        let outcome = match backend.classify_exit(code) {
+            Outcome::Rejected if content.is_some() && self.application.cancel_prints_answer() => {
+                Outcome::Affirmed
+            }
+            outcome => outcome,
+        };
+
+        let result = match outcome {
+            Outcome::Affirmed => ZenityOutput::Affirmed {
+                content: content
+                    .or_else(|| self.application.parses_empty_output().then(String::new))
+                    .map(|stdout| {
+                        let stderr = crate::stderr::filtered(&stderr);
+                        self.application
+                            .parse(&DialogOutput::new(stdout, stderr, code))
+                    })
+                    .transpose()?,
             },
-            _ => ZenityOutput::Unknown {
+            Outcome::Rejected => ZenityOutput::Rejected { content },
+            Outcome::TimedOut => ZenityOutput::TimedOut { content },
+            Outcome::Failed => Err(crate::Error::ZenityFailed {
                 exit_code: code,
-                stdout,
-                stderr: String::from_utf8(output.stderr).unwrap_or_default(),
+                stderr: crate::stderr::filtered(&stderr),
+            })?,
+            Outcome::Unknown => ZenityOutput::Unknown {
+                exit_code: code,
+                stdout: content.unwrap_or_default(),
+                stderr: crate::stderr::filtered(&stderr),
             },
         };
 
         Ok(result)
     }
+
+    /// Whether the Zenity process will find a display to connect to.
+    fn has_display(&self) -> bool {
+        self.has_display_with(|key| env::var_os(key))
+    }
+
+    /// Whether the Zenity process will find a display, reading this process's environment
+    /// through `var`.
+    fn has_display_with(&self, var: impl Fn(&str) -> Option<OsString>) -> bool {
+        let child_env = self.child_env();
+        crate::display::is_display_set(|key| match child_env.get(OsStr::new(key)) {
+            Some(value) => value.clone(),
+            None if self.env_clear => None,
+            None => var(key),
+        })
+    }
+
+    /// The environment changes for the Zenity process. Variables set explicitly with `with_env`
+    /// or `with_env_remove` override the ones derived from the theme settings.
+    fn child_env(&self) -> BTreeMap<OsString, Option<OsString>> {
+        let mut env = BTreeMap::new();
+
+        let gtk_theme = match (&self.gtk_theme, self.prefer_dark) {
+            (Some(theme), true) if !theme.contains(':') => Some(format!("{theme}:dark")),
+            (Some(theme), _) => Some(theme.clone()),
+            (None, true) => Some("Adwaita:dark".to_string()),
+            (None, false) => None,
+        };
+
+        if let Some(gtk_theme) = gtk_theme {
+            env.insert("GTK_THEME".into(), Some(gtk_theme.into()));
+        }
+
+        if self.prefer_dark {
+            env.insert("ADW_DEBUG_COLOR_SCHEME".into(), Some("prefer-dark".into()));
+            env.insert("GTK_APPLICATION_PREFER_DARK_THEME".into(), Some("1".into()));
+        }
+
+        env.extend(self.env.clone());
+        env
+    }
+
+    /// The backend detected on `PATH`.
+    fn auto_backend() -> crate::Result<&'static ZenityBackend> {
+        detected_backend().ok_or_else(|| crate::Error::ZenityNotInstalled {
+            program: ZenitySettings::DEFAULT_PROGRAM.into(),
+            args: Vec::new(),
+            source: io::ErrorKind::NotFound.into(),
+        })
+    }
+
+    /// The version of the Zenity executable this dialog runs, as reported by `--version`. The
+    /// result is cached per program path.
+    pub fn zenity_version(&self) -> crate::Result<crate::Version> {
+        crate::program_version(self.program())
+    }
+
+    /// The backend running the configured Zenity executable.
+    fn backend(&self) -> ZenityBackend {
+        ZenityBackend::with_program(self.program())
+    }
+
+    /// The executable to run, taking the crate-wide settings into account.
+    fn program(&self) -> PathBuf {
+        match self.program {
+            Some(ref program) => program.clone(),
+            None => ZenitySettings::current().resolved_program(),
+        }
+    }
+
+    /// Explain why the process did not return an exit code.
+    fn missing_exit_code(status: ExitStatus, stderr: &[u8]) -> crate::Error {
+        #[cfg(unix)]
+        if let Some(signal) = status.signal() {
+            return crate::Error::TerminatedBySignal {
+                signal,
+                stderr: crate::stderr::filtered(stderr),
+            };
+        }
+
+        #[cfg(not(unix))]
+        let _ = (status, stderr);
+
+        crate::Error::MissingExitCode
+    }
+}
+
+#[cfg(feature = "error")]
+impl ZenityDialog<Error> {
+    /// An error dialog with the text and the error icon.
+    ///
+    /// ```
+    /// # use zenity_dialog::ZenityDialog;
+    /// let dialog = ZenityDialog::error("Disk full")
+    ///     .with_title("Backup")
+    ///     .force_legacy_flags();
+    /// assert_eq!(
+    ///     dialog.to_command_string(),
+    ///     "zenity --error '--text=Disk full' --title=Backup --icon-name=error"
+    /// );
+    /// ```
+    pub fn error(text: impl Into<String>) -> Self {
+        ZenityDialog::new(Error::new().with_text(text)).with_icon(Icon::Error)
+    }
+}
+
+#[cfg(feature = "info")]
+impl ZenityDialog<Info> {
+    /// An informational dialog with the text and the information icon.
+    ///
+    /// ```
+    /// # use zenity_dialog::ZenityDialog;
+    /// let dialog = ZenityDialog::info("Backup complete").force_legacy_flags();
+    /// assert_eq!(
+    ///     dialog.to_command_string(),
+    ///     "zenity --info '--text=Backup complete' --title=Information --icon-name=info"
+    /// );
+    /// ```
+    pub fn info(text: impl Into<String>) -> Self {
+        ZenityDialog::new(Info::new().with_text(text)).with_icon(Icon::Info)
+    }
+}
+
+#[cfg(feature = "warning")]
+impl ZenityDialog<Warning> {
+    /// A warning dialog with the text and the warning icon.
+    ///
+    /// ```
+    /// # use zenity_dialog::ZenityDialog;
+    /// let dialog = ZenityDialog::warning("Battery low").force_legacy_flags();
+    /// assert_eq!(
+    ///     dialog.to_command_string(),
+    ///     "zenity --warning '--text=Battery low' --title=Warning --icon-name=warning"
+    /// );
+    /// ```
+    pub fn warning(text: impl Into<String>) -> Self {
+        ZenityDialog::new(Warning::new().with_text(text)).with_icon(Icon::Warning)
+    }
+}
+
+#[cfg(feature = "question")]
+impl ZenityDialog<Question> {
+    /// A question dialog with the text and the question icon.
+    ///
+    /// ```
+    /// # use zenity_dialog::ZenityDialog;
+    /// let dialog = ZenityDialog::question("Delete the backup?").force_legacy_flags();
+    /// assert_eq!(
+    ///     dialog.to_command_string(),
+    ///     "zenity --question '--text=Delete the backup?' --title=Question --icon-name=question"
+    /// );
+    /// ```
+    pub fn question(text: impl Into<String>) -> Self {
+        ZenityDialog::new(Question::new().with_text(text)).with_icon(Icon::Question)
+    }
+}
+
+#[cfg(feature = "entry")]
+impl ZenityDialog<Entry> {
+    /// A text entry dialog with the text as the input label and the question icon.
+    ///
+    /// ```
+    /// # use zenity_dialog::ZenityDialog;
+    /// let dialog = ZenityDialog::entry("Your name?")
+    ///     .with_width(400)
+    ///     .force_legacy_flags();
+    /// assert_eq!(
+    ///     dialog.to_command_string(),
+    ///     "zenity --entry '--text=Your name?' --title=Entry --icon-name=question --width=400"
+    /// );
+    /// ```
+    pub fn entry(text: impl Into<String>) -> Self {
+        ZenityDialog::new(Entry::new().with_text(text)).with_icon(Icon::Question)
+    }
 }
 
 /// Represents an instance of Zenity Dialog with an extra button configured.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
 pub struct ZenityDialogExtButton<T>
 where
     T: ZenityApplication,
 {
     inner: ZenityDialog<T>,
-    extra_button_label: String,
+    extra_button_labels: Vec<String>,
 }
 
 impl<T> ZenityDialogExtButton<T>
 where
     T: ZenityApplication,
 {
-    /// Provide a custom title for the dialog.
-    pub fn with_title(mut self, title: impl Into<String>) -> Self {
-        self.inner.title = Some(title.into());
-        self
+    /// Replace the application while keeping every other setting of the dialog, including the
+    /// extra button.
+    pub fn with_application<U>(self, application: U) -> ZenityDialogExtButton<U>
+    where
+        U: ZenityApplication,
+    {
+        ZenityDialogExtButton {
+            inner: self.inner.with_application(application),
+            extra_button_labels: self.extra_button_labels,
+        }
     }
 
-    /// Override the default icon.
-    pub fn with_icon(mut self, icon: Icon) -> Self {
-        self.inner.icon = Some(icon);
+    /// Render another extra button with the provided text as a label, after the ones already
+    /// added.
+    pub fn with_extra_button(mut self, extra_button_label: impl Into<String>) -> Self {
+        self.extra_button_labels.push(extra_button_label.into());
         self
     }
 
-    /// Set a specific width for the dialog.
-    pub fn with_width(mut self, width: usize) -> Self {
-        self.inner.width = Some(width);
-        self
+    dialog_builders!();
+
+    /// The dialog the shared builder methods configure.
+    fn dialog_mut(&mut self) -> &mut ZenityDialog<T> {
+        &mut self.inner
     }
 
-    /// Set a specific height for the dialog.
-    pub fn with_height(mut self, height: usize) -> Self {
-        self.inner.height = Some(height);
+    /// Configure the wrapped dialog with a builder method that isn't forwarded, such as one
+    /// that consumes the dialog.
+    pub fn map_inner(mut self, f: impl FnOnce(ZenityDialog<T>) -> ZenityDialog<T>) -> Self {
+        self.inner = f(self.inner);
         self
     }
 
-    /// Make the dialog close automatically after the duration has passed.
-    pub fn with_timeout(mut self, timeout: Duration) -> Self {
-        self.inner.timeout = Some(timeout);
-        self
+    /// Drop the extra buttons, returning the wrapped dialog.
+    pub fn into_inner(self) -> ZenityDialog<T> {
+        self.inner
     }
 
-    /// Render a hint displaying the provided text.
-    pub fn with_modal_hint(mut self, modal_hint: impl Into<String>) -> Self {
-        self.inner.modal_hint = Some(modal_hint.into());
-        self
+    /// The option names that appear more than once in the arguments passed to Zenity,
+    /// including the extra button. See [ZenityDialog::conflicts].
+    pub fn conflicts(&self) -> Vec<String> {
+        duplicate_options(&self.to_args())
     }
 
-    /// Attach an additional custom argument. Used to handle arguments that aren't currently statically
-    /// supported. Use at your own risk. Note that this function will automatically prepend -- to the argument
-    /// so there is no need to provide it. However, if you do provide it, it will still work.
-    pub fn with_additional_arg(mut self, arg: impl Into<Arg>) -> Self {
-        let arg: Arg = arg.into();
-        self.inner.additional_args.push(arg.to_string());
-        self
+    /// Display the dialog and wait for user response.
+    pub fn show(self) -> crate::Result<ZenityOutputExtButton<T::Return>> {
+        self.show_ref()
     }
 
-    /// Like `with_additional_arg`, but takes a [Vec<Arg>]
-    pub fn with_additional_args(mut self, args: Vec<Arg>) -> Self {
-        self.inner
-            .additional_args
-            .append(&mut args.iter().map(Arg::to_string).collect::<Vec<String>>());
-        self
+    /// Like `show`, but doesn't consume the dialog so that it can be shown again, for example in
+    /// a retry loop.
+    pub fn show_ref(&self) -> crate::Result<ZenityOutputExtButton<T::Return>> {
+        self.show_with_backend(&self.inner.backend())
     }
 
-    /// Display the dialog and wait for user response.
-    pub fn show(self) -> crate::Result<ZenityOutputExtButton<T::Return>> {
-        let inner = self
+    /// Render the dialog with the first Zenity-compatible program found on `PATH`, as found by
+    /// [crate::backend::detect_backend], and wait for user response. The detection runs once
+    /// per process.
+    pub fn show_auto(&self) -> crate::Result<ZenityOutputExtButton<T::Return>> {
+        self.show_with_backend(ZenityDialog::<T>::auto_backend()?)
+    }
+
+    /// Render the dialog without blocking, on the tokio runtime. The dialog is closed if the
+    /// future is dropped before it completes. Idle deferral and the terminal fallback still
+    /// block the calling thread.
+    #[cfg(feature = "async-tokio")]
+    pub fn show_async(
+        &self,
+    ) -> impl std::future::Future<Output = crate::Result<ZenityOutputExtButton<T::Return>>> + '_
+    {
+        future::ShowFutureExtButton::new(self, future::spawn_tokio)
+    }
+
+    /// Render the dialog without blocking, on any runtime supported by `async-process`, such as
+    /// smol or async-std. The dialog is closed if the future is dropped before it completes.
+    /// Idle deferral and the terminal fallback still block the calling thread.
+    #[cfg(feature = "async-io")]
+    pub fn show_async_io(
+        &self,
+    ) -> impl std::future::Future<Output = crate::Result<ZenityOutputExtButton<T::Return>>> + '_
+    {
+        future::ShowFutureExtButton::new(self, future::spawn_async_io)
+    }
+
+    /// Render the dialog through the runner instead of spawning Zenity directly. See
+    /// [ZenityDialog::show_with_runner].
+    pub fn show_with_runner(
+        &self,
+        runner: &impl CommandRunner,
+    ) -> crate::Result<ZenityOutputExtButton<T::Return>> {
+        let shown = self.inner.run_with_runner(runner, self.to_args())?;
+        Ok(self.detect_ext_button(shown))
+    }
+
+    /// Render the dialog with the session's Zenity executable instead of the configured one,
+    /// and wait for user response. See [crate::ZenitySession].
+    pub fn show_with(
+        &self,
+        session: &crate::ZenitySession,
+    ) -> crate::Result<ZenityOutputExtButton<T::Return>> {
+        let result = self.show_with_backend(&session.backend());
+        session.observe(&result);
+        result
+    }
+
+    /// Render the dialog with the provided backend instead of Zenity and wait for user
+    /// response.
+    pub fn show_with_backend(
+        &self,
+        backend: &impl DialogBackend,
+    ) -> crate::Result<ZenityOutputExtButton<T::Return>> {
+        self.inner.validated()?;
+        let shown = self.inner.run_detailed(backend, self.to_args())?;
+        Ok(self.detect_ext_button(shown))
+    }
+
+    /// Like `show_ref`, but also returns the raw output. See [ZenityDialog::show_detailed].
+    pub fn show_detailed(&self) -> crate::Result<ShownDialog<ZenityOutputExtButton<T::Return>>> {
+        self.inner.validated()?;
+        let shown = self
             .inner
-            .with_additional_arg(("--extra-button", self.extra_button_label.as_str()));
-        let extra_button_label = self.extra_button_label;
-        let result = inner.show();
+            .run_detailed(&self.inner.backend(), self.to_args())?;
+        Ok(ShownDialog {
+            raw: shown.raw.clone(),
+            output: self.detect_ext_button(shown),
+        })
+    }
 
-        let output = match result {
-            Ok(result) => result,
-            Err(err) => Err(err)?,
-        };
+    /// Like `show_ref`, but skips validation. See [ZenityDialog::show_unchecked].
+    pub fn show_unchecked(&self) -> crate::Result<ZenityOutputExtButton<T::Return>> {
+        let shown = self
+            .inner
+            .run_detailed(&self.inner.backend(), self.to_args())?;
+        Ok(self.detect_ext_button(shown))
+    }
 
-        let content = match output {
-            ZenityOutput::Rejected { ref content } => content,
-            other => return Ok(other.into()),
-        };
+    /// Check the configuration before showing it. See [ZenityDialog::validate].
+    pub fn validate(&self) -> Result<(), Vec<crate::ValidationError>> {
+        self.inner.validate()
+    }
 
-        let content = match content {
-            Some(content) => content.to_owned(),
-            None => return Ok(output.into()),
-        };
+    /// Report a click on an extra button, which Zenity signals by exiting with code 1 and
+    /// printing the button's label on a line of its own. A plain cancel exits with the same code
+    /// but prints nothing, whatever its label, so empty labels and labels equal to the cancel
+    /// label are told apart as well.
+    pub(crate) fn detect_ext_button(
+        &self,
+        shown: ShownDialog<ZenityOutput<T::Return>>,
+    ) -> ZenityOutputExtButton<T::Return> {
+        let clicked = shown
+            .raw
+            .filter(|raw| raw.exit_code == Some(1) && !raw.stdout.is_empty())
+            .and_then(|raw| {
+                let printed = raw.stdout.strip_suffix('\n').unwrap_or(&raw.stdout);
+                self.extra_button_labels
+                    .iter()
+                    .find(|label| label.as_str() == printed)
+                    .cloned()
+            });
 
-        match content == extra_button_label {
-            true => Ok(ZenityOutputExtButton::ExtButton { content }),
-            false => Ok(output.into()),
+        match clicked {
+            Some(content) => ZenityOutputExtButton::ExtButton { content },
+            None => shown.output.into(),
         }
     }
+
+    /// Describe the Zenity invocation `show` would make, without executing anything. See
+    /// [ZenityDialog::to_args] for the names of options renamed in Zenity 4.
+    pub fn dry_run(&self) -> DryRun {
+        DryRun {
+            args: self.to_args(),
+            ..self.inner.dry_run()
+        }
+    }
+
+    /// Render the Zenity invocation `show` would make as a shell-quoted command line, including
+    /// the extra buttons. See [ZenityDialog::to_command_string].
+    pub fn to_command_string(&self) -> String {
+        let mut dry_run = self.dry_run();
+        redact(&mut dry_run.args, &self.inner.application.secret_options());
+        dry_run.to_string()
+    }
+
+    /// Convert the settings into the argument vector passed to Zenity, including the extra
+    /// button. The returned arguments are exactly the ones, in the same order, that `show`
+    /// executes. See [ZenityDialog::to_args].
+    pub fn to_args(&self) -> Vec<OsString> {
+        let mut args = self.inner.to_args();
+        args.extend(
+            self.extra_button_labels
+                .iter()
+                .map(|label| Arg::from(("--extra-button", label.as_str())).to_os_string()),
+        );
+        args
+    }
+}
+
+impl<T> Display for ZenityDialog<T>
+where
+    T: ZenityApplication,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_command_string())
+    }
+}
+
+impl<T> Display for ZenityDialogExtButton<T>
+where
+    T: ZenityApplication,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_command_string())
+    }
 }
 
 /// Represents the user's response to the dialog.
-#[derive(Debug, Clone, PartialEq)]
+///
+/// With the `serde` feature, outputs are (de)serialized with the variant name as the tag, as in
+/// `{"Affirmed":{"content":"Yes"}}` or `"Dismissed"`. Variant names are kept stable, so that
+/// logged outputs stay readable.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub enum ZenityOutput<T>
 where
     T: Sized,
@@ -335,6 +1448,15 @@ where
         /// it is [None] for default values.
         content: Option<String>,
     },
+    /// The dialog was closed automatically because the configured timeout elapsed.
+    TimedOut {
+        /// Some dialogs print their current selection when they time out. If so, this
+        /// value will be [Some] and will contain the raw output. Otherwise, it is [None].
+        content: Option<String>,
+    },
+    /// The dialog was closed by this program before the user responded, for example with
+    /// [DialogHandle::kill].
+    Dismissed,
     /// In the case that Zenity returned an unexpected response, this contains
     /// the full details of the response so that the user may respond to it
     /// as needed. If you get this output, it indicates a bug in this library so please report it.
@@ -349,7 +1471,12 @@ where
 }
 
 /// Represents the user's response to the dialog.
-#[derive(Debug, Clone, PartialEq)]
+///
+/// With the `serde` feature, outputs are (de)serialized with the variant name as the tag, as in
+/// `{"Affirmed":{"content":"Yes"}}` or `"Dismissed"`. Variant names are kept stable, so that
+/// logged outputs stay readable.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub enum ZenityOutputExtButton<T>
 where
     T: Sized,
@@ -373,6 +1500,15 @@ where
         /// The content of the extra button.
         content: String,
     },
+    /// The dialog was closed automatically because the configured timeout elapsed.
+    TimedOut {
+        /// Some dialogs print their current selection when they time out. If so, this
+        /// value will be [Some] and will contain the raw output. Otherwise, it is [None].
+        content: Option<String>,
+    },
+    /// The dialog was closed by this program before the user responded, for example with
+    /// [DialogHandle::kill].
+    Dismissed,
     /// In the case that Zenity returned an unexpected response, this contains
     /// the full details of the response so that the user may respond to it
     /// as needed. If you get this output, it indicates a bug in this library so please report it.
@@ -391,6 +1527,8 @@ impl<T> From<ZenityOutput<T>> for ZenityOutputExtButton<T> {
         match value {
             ZenityOutput::Affirmed { content } => Self::Affirmed { content },
             ZenityOutput::Rejected { content } => Self::Rejected { content },
+            ZenityOutput::TimedOut { content } => Self::TimedOut { content },
+            ZenityOutput::Dismissed => Self::Dismissed,
             ZenityOutput::Unknown {
                 exit_code,
                 stdout,
@@ -405,8 +1543,14 @@ impl<T> From<ZenityOutput<T>> for ZenityOutputExtButton<T> {
 }
 
 /// Represents an icon. [Icon::Error], [Icon::Info], [Icon::Question], and [Icon::Warning] represent
-/// standard icons, while [Icon::IconPath] allows you to pass the path of a custom icon.
-#[derive(Debug, Clone, PartialEq)]
+/// standard icons, [Icon::Named] any icon of the current icon theme, and [Icon::IconPath] allows
+/// you to pass the path of a custom icon.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(::serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
 pub enum Icon {
     /// An error icon
     Error,
@@ -417,19 +1561,150 @@ pub enum Icon {
     /// A warning icon
     Warning,
     /// A path to a custom icon
+    #[cfg_attr(feature = "serde", serde(rename = "path"))]
     IconPath(PathBuf),
+    /// A freedesktop icon name, as in `dialog-password`, `folder`, or `network-error`, looked up
+    /// in the current icon theme
+    #[cfg_attr(feature = "serde", serde(rename = "name"))]
+    Named(String),
+}
+
+impl Icon {
+    /// An icon of the current icon theme, by its freedesktop name. Names are passed to Zenity as
+    /// is; see [Icon::validate_theme_icon] to catch typos during development.
+    ///
+    /// ```
+    /// # use zenity_dialog::dialog::Icon;
+    /// assert_eq!(Icon::from_name("dialog-password").to_string(), "dialog-password");
+    /// ```
+    pub fn from_name(name: impl Into<String>) -> Self {
+        Self::Named(name.into())
+    }
+
+    /// The icon as passed to Zenity: the name of a standard or named icon, or the path of a
+    /// custom icon, even if it isn't valid UTF-8.
+    pub fn as_os_str(&self) -> &OsStr {
+        match self {
+            Icon::Error => OsStr::new("error"),
+            Icon::Info => OsStr::new("info"),
+            Icon::Question => OsStr::new("question"),
+            Icon::Warning => OsStr::new("warning"),
+            Icon::IconPath(path) => path.as_os_str(),
+            Icon::Named(name) => OsStr::new(name),
+        }
+    }
+
+    /// Check that the icon can be found: a named icon must exist, in any size, in one of the
+    /// installed icon themes, and a custom icon's path must exist. The standard icons are always
+    /// valid. This searches the icon theme directories, so it is meant for development rather
+    /// than for every dialog shown.
+    pub fn validate_theme_icon(&self) -> Result<(), crate::ValidationError> {
+        match self {
+            Icon::Named(name) if !crate::icon_theme::contains(name) => Err(
+                crate::ValidationError::new("icon", format!("no icon named {name:?} is installed")),
+            ),
+            Icon::IconPath(path) if !path.exists() => Err(crate::ValidationError::new(
+                "icon",
+                format!("{} doesn't exist", path.display()),
+            )),
+            _ => Ok(()),
+        }
+    }
 }
 
+impl From<PathBuf> for Icon {
+    fn from(path: PathBuf) -> Self {
+        Icon::IconPath(path)
+    }
+}
+
+impl From<&Path> for Icon {
+    fn from(path: &Path) -> Self {
+        Icon::IconPath(path.to_owned())
+    }
+}
+
+/// Interprets the string, in this order:
+///
+/// 1. `error`, `info`, `question`, and `warning` are the standard icons.
+/// 2. A string containing `/`, or ending with an image extension such as `.png` or `.svg` in any
+///    case, is a path to a custom icon.
+/// 3. Anything else is a freedesktop icon name.
+///
+/// ```
+/// # use zenity_dialog::dialog::Icon;
+/// # use std::path::PathBuf;
+/// assert_eq!(Icon::from("warning"), Icon::Warning);
+/// assert_eq!(Icon::from("logo.svg"), Icon::IconPath(PathBuf::from("logo.svg")));
+/// assert_eq!(Icon::from("./warning"), Icon::IconPath(PathBuf::from("./warning")));
+/// assert_eq!(Icon::from("folder"), Icon::Named("folder".to_string()));
+/// ```
+impl From<&str> for Icon {
+    fn from(icon: &str) -> Self {
+        const IMAGE_EXTENSIONS: [&str; 8] = [
+            ".png", ".svg", ".xpm", ".jpg", ".jpeg", ".gif", ".ico", ".bmp",
+        ];
+
+        let lowercase = icon.to_ascii_lowercase();
+        match icon {
+            "error" => Icon::Error,
+            "info" => Icon::Info,
+            "question" => Icon::Question,
+            "warning" => Icon::Warning,
+            _ if icon.contains('/')
+                || IMAGE_EXTENSIONS
+                    .iter()
+                    .any(|extension| lowercase.ends_with(extension)) =>
+            {
+                Icon::IconPath(PathBuf::from(icon))
+            }
+            _ => Icon::Named(icon.to_owned()),
+        }
+    }
+}
+
+/// Interprets the string like the `&str` conversion.
+impl From<String> for Icon {
+    fn from(icon: String) -> Self {
+        Icon::from(icon.as_str())
+    }
+}
+
+/// Renders the icon as passed to Zenity. Paths that aren't valid UTF-8 are rendered lossily,
+/// so this is meant for logging; the arguments passed to Zenity keep the original path.
 impl Display for Icon {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let base = match self {
-            Icon::Error => "error",
-            Icon::Info => "info",
-            Icon::Question => "question",
-            Icon::Warning => "warning",
-            Icon::IconPath(path) => path.to_str().ok_or(std::fmt::Error)?,
-        };
-
-        write!(f, "{base}")
+        write!(f, "{}", self.as_os_str().to_string_lossy())
     }
 }
+
+// Dialogs are compared in tests and deduplicated in sets, so every application must keep them
+// comparable and hashable.
+const _: () = {
+    fn assert_impls<T: Eq + std::hash::Hash>() {}
+
+    #[allow(dead_code)]
+    fn assert_all() {
+        assert_impls::<Icon>();
+        #[cfg(feature = "calendar")]
+        assert_impls::<ZenityDialog<Calendar>>();
+        #[cfg(feature = "calendar")]
+        assert_impls::<Month>();
+        #[cfg(feature = "entry")]
+        assert_impls::<ZenityDialogExtButton<Entry>>();
+        #[cfg(feature = "error")]
+        assert_impls::<ZenityDialog<Error>>();
+        #[cfg(feature = "file-selection")]
+        assert_impls::<ZenityDialog<FileSelection>>();
+        #[cfg(feature = "info")]
+        assert_impls::<ZenityDialog<Info>>();
+        #[cfg(feature = "list")]
+        assert_impls::<ZenityDialog<List>>();
+        #[cfg(feature = "question")]
+        assert_impls::<ZenityDialog<Question>>();
+        #[cfg(feature = "warning")]
+        assert_impls::<ZenityDialog<Warning>>();
+        assert_impls::<ZenityOutput<String>>();
+        assert_impls::<ZenityOutputExtButton<String>>();
+    }
+};
@@ -1,3 +1,6 @@
+use super::DialogOutput;
+use std::{collections::BTreeMap, ffi::OsString, path::Path};
+
 /// Allows a struct or enum to be provided as a Zenity application.
 pub trait ZenityApplication: Clone + Default + ToArgVector {
     /// The type that Zenity returns. Usually it should be a string,
@@ -5,10 +8,185 @@ pub trait ZenityApplication: Clone + Default + ToArgVector {
     /// type.
     type Return: Clone;
 
-    /// Parse the raw output from Zenity into another type
-    fn parse(&self, stdout: &str) -> Result<Self::Return, crate::Error>;
+    /// Parse the output of a confirmed dialog into another type. Most applications only read
+    /// [DialogOutput::stdout], which is trimmed as decided by [ZenityApplication::trim_output].
+    /// It can also be called directly, with [DialogOutput::from_stdout], for example to check
+    /// output captured elsewhere.
+    ///
+    /// This is a stable entry point: for any input, implementations return either the parsed
+    /// value or [crate::Error::ParseResultFailure] holding a [crate::ParseError] with the text
+    /// that failed to parse, and never panic.
+    fn parse(&self, output: &DialogOutput) -> Result<Self::Return, crate::Error>;
+
+    /// The title used when the dialog is not given one explicitly. Defaults to [None], which
+    /// leaves the title up to Zenity.
+    fn default_title() -> Option<&'static str> {
+        None
+    }
+
+    /// Whether a confirmed dialog that printed nothing is parsed, yielding
+    /// `Affirmed { content: Some(..) }`, rather than reported as `Affirmed { content: None }`.
+    /// Applications for which an empty answer is meaningful, such as an empty text entry,
+    /// return `true`, possibly depending on their configuration. Defaults to `false`.
+    fn parses_empty_output(&self) -> bool {
+        false
+    }
+
+    /// Whether surrounding whitespace is removed from the output before it's classified and
+    /// parsed. Applications whose whitespace is meaningful, such as editable text, return
+    /// `false`, in which case only the newline Zenity terminates its output with is removed.
+    /// Defaults to `true`.
+    fn trim_output(&self) -> bool {
+        true
+    }
+
+    /// Whether output printed by a dialog that exited with the cancel code is its answer, which
+    /// is then parsed and reported as `Affirmed`, as for a question in switch mode, which prints
+    /// the label of the pressed button. A cancelled dialog that printed nothing stays
+    /// `Rejected`. Defaults to `false`.
+    fn cancel_prints_answer(&self) -> bool {
+        false
+    }
+
+    /// The configured fields of the application, keyed by field name. Only fields that differ
+    /// from their default are included: unset options, disabled flags and empty lists are
+    /// omitted. Used to compare dialogs with [crate::ZenityDialog::diff]. Defaults to an empty
+    /// map.
+    fn field_map(&self) -> BTreeMap<&'static str, String> {
+        BTreeMap::new()
+    }
+
+    /// Make the application's relative paths, such as the initial file of a file selection,
+    /// absolute by joining them to `base`, the working directory set with
+    /// [crate::ZenityDialog::with_current_dir]. Paths are joined rather than canonicalized, as
+    /// they may not exist yet. Defaults to leaving the application unchanged.
+    fn resolve_relative_paths(&mut self, _base: &Path) {}
+
+    /// Check the application's configuration for values Zenity would silently ignore or
+    /// misinterpret. Called by [crate::ZenityDialog::validate]. Defaults to no problems.
+    fn validate(&self) -> Vec<crate::ValidationError> {
+        Vec::new()
+    }
+
+    /// The names of the options, without the leading `--`, whose values must not be logged,
+    /// such as a prefilled password. Their values are redacted by
+    /// [crate::ZenityDialog::to_command_string]. Defaults to none.
+    fn secret_options(&self) -> Vec<&'static str> {
+        Vec::new()
+    }
+
+    /// How the application is rendered in a terminal when no display is available. Defaults to
+    /// [None], which runs Zenity as usual.
+    #[cfg(feature = "tty-fallback")]
+    fn tty_prompt(&self) -> Option<crate::TtyPrompt> {
+        None
+    }
+
+    /// Whether the application can be sent to the desktop's notification service instead of
+    /// Zenity, and whether that's preferred. `Some(true)` always sends it natively,
+    /// `Some(false)` only when Zenity isn't installed. Defaults to [None], which always runs
+    /// Zenity.
+    #[cfg(feature = "notify-fallback")]
+    fn native_notification(&self) -> Option<bool> {
+        None
+    }
+}
+
+/// An application with body text, so that generic code can set the text of any dialog bound on
+/// it. See [crate::ZenityDialog::with_body_text].
+///
+/// ```
+/// # use zenity_dialog::{dialog::{Entry, HasText, Info, Question, ZenityApplication}, ZenityDialog};
+/// fn branded<T: ZenityApplication + HasText>(text: &str) -> ZenityDialog<T> {
+///     ZenityDialog::default()
+///         .with_title("Acme Backup")
+///         .with_body_text(text)
+/// }
+///
+/// assert_eq!(branded::<Info>("Done").application.text(), Some("Done"));
+/// assert_eq!(branded::<Entry>("Name?").application.text(), Some("Name?"));
+/// assert_eq!(branded::<Question>("Retry?").application.text(), Some("Retry?"));
+/// ```
+pub trait HasText {
+    /// Set the body text.
+    fn set_text(&mut self, text: String);
+
+    /// The body text, if set.
+    fn text(&self) -> Option<&str>;
+}
+
+/// Implement [HasText] for applications storing their body text in a `text` field.
+macro_rules! impl_has_text {
+    ($($name:ty),* $(,)?) => {
+        $(
+            impl $crate::dialog::HasText for $name {
+                fn set_text(&mut self, text: String) {
+                    self.text = Some(text);
+                }
+
+                fn text(&self) -> Option<&str> {
+                    self.text.as_deref()
+                }
+            }
+        )*
+    };
 }
 
+/// Converts an application into the arguments passed to Zenity, and so the supertrait through
+/// which custom applications add dialogs or options the crate doesn't cover. The arguments must
+/// follow this contract:
+///
+/// - The first element selects the dialog, as in `--scale`.
+/// - Each element is one logical option, such as `--value=50`, or `--text=Hello world` with its
+///   value unquoted.
+/// - Values are passed to Zenity as is, never through a shell, so they must not be escaped.
+///
+/// The options shared by all dialogs, such as the title and timeout, are added by
+/// [crate::ZenityDialog]. With the `derive` feature, the arguments can be derived with
+/// `#[derive(ToArgVector)]`.
+///
+/// ```
+/// # use zenity_dialog::{dialog::{DialogOutput, ToArgVector, ZenityApplication}, ParseError, ZenityDialog};
+/// # use std::ffi::OsString;
+/// #[derive(Debug, Clone, Default)]
+/// struct Scale {
+///     text: Option<String>,
+///     value: i32,
+///     max_value: i32,
+/// }
+///
+/// impl ToArgVector for Scale {
+///     fn to_argv(&self) -> Vec<OsString> {
+///         let mut args = vec![OsString::from("--scale")];
+///         if let Some(ref text) = self.text {
+///             args.push(format!("--text={text}").into());
+///         }
+///
+///         args.push(format!("--value={}", self.value).into());
+///         args.push(format!("--max-value={}", self.max_value).into());
+///         args
+///     }
+/// }
+///
+/// impl ZenityApplication for Scale {
+///     type Return = i32;
+///
+///     fn parse(&self, output: &DialogOutput) -> zenity_dialog::Result<i32> {
+///         let stdout = output.stdout();
+///         stdout
+///             .parse()
+///             .map_err(|err| ParseError::new("scale", stdout, err).into())
+///     }
+/// }
+///
+/// let scale = Scale { text: Some("Volume".to_owned()), value: 50, max_value: 100 };
+/// assert_eq!(scale.parse(&DialogOutput::from_stdout("75")).unwrap(), 75);
+/// assert_eq!(
+///     ZenityDialog::new(scale).with_title("Settings").to_command_string(),
+///     "zenity --scale --text=Volume --value=50 --max-value=100 --title=Settings"
+/// );
+/// ```
 pub trait ToArgVector {
-    fn to_argv(&self) -> Vec<String>;
+    /// The arguments selecting and configuring the dialog, following the trait's contract.
+    fn to_argv(&self) -> Vec<OsString>;
 }
@@ -0,0 +1,344 @@
+/// Defines the builder methods shared by [super::ZenityDialog] and
+/// [super::ZenityDialogExtButton], so that every option is available on both. The implementing
+/// type provides `dialog_mut`, which returns the dialog to configure.
+///
+/// Every `with_*` builder taking a value has an in-place counterpart that mutates the dialog and
+/// returns it for chaining, for configuring a dialog conditionally without rebinding. It's named
+/// `set_*`, or `push_*` and `extend_*` for the builders adding arguments.
+/// Flags are enabled with consuming `with_*` builders taking no value, such as `with_modal`.
+macro_rules! dialog_builders {
+    () => {
+        /// Provide a custom title for the dialog. When no title is provided, the application's
+        /// [ZenityApplication::default_title] is used. Pass an empty string to suppress it.
+        pub fn with_title(mut self, title: impl Into<String>) -> Self {
+            self.set_title(title);
+            self
+        }
+
+        /// Set the title. See `with_title`.
+        pub fn set_title(&mut self, title: impl Into<String>) -> &mut Self {
+            self.dialog_mut().title = Some(title.into());
+            self
+        }
+
+        /// Override the default icon. Accepts an [Icon], a path, or a string, which is interpreted
+        /// as described on [Icon]'s `From<&str>` implementation.
+        pub fn with_icon(mut self, icon: impl Into<Icon>) -> Self {
+            self.set_icon(icon);
+            self
+        }
+
+        /// Override the default icon. See `with_icon`.
+        pub fn set_icon(&mut self, icon: impl Into<Icon>) -> &mut Self {
+            self.dialog_mut().icon = Some(icon.into());
+            self
+        }
+
+        /// Set a specific width for the dialog.
+        pub fn with_width(mut self, width: usize) -> Self {
+            self.set_width(width);
+            self
+        }
+
+        /// Set a specific width for the dialog. See `with_width`.
+        pub fn set_width(&mut self, width: usize) -> &mut Self {
+            self.dialog_mut().width = Some(width);
+            self
+        }
+
+        /// Set a specific height for the dialog.
+        pub fn with_height(mut self, height: usize) -> Self {
+            self.set_height(height);
+            self
+        }
+
+        /// Set a specific height for the dialog. See `with_height`.
+        pub fn set_height(&mut self, height: usize) -> &mut Self {
+            self.dialog_mut().height = Some(height);
+            self
+        }
+
+        /// Make the dialog close automatically after the duration has passed.
+        pub fn with_timeout(mut self, timeout: Duration) -> Self {
+            self.set_timeout(timeout);
+            self
+        }
+
+        /// Make the dialog close automatically after the duration has passed. See `with_timeout`.
+        pub fn set_timeout(&mut self, timeout: Duration) -> &mut Self {
+            self.dialog_mut().timeout = Some(timeout);
+            self
+        }
+
+        /// Kill the dialog once the duration has passed, reporting [ZenityOutput::TimedOut] even if
+        /// Zenity never exits on its own. When a regular timeout is also set, the dialog is given
+        /// [ZenityDialog::HARD_TIMEOUT_GRACE] beyond it to close by itself first. Applies to the
+        /// blocking `show` methods.
+        pub fn with_hard_timeout(mut self, hard_timeout: Duration) -> Self {
+            self.set_hard_timeout(hard_timeout);
+            self
+        }
+
+        /// Kill the dialog once the duration has passed. See `with_hard_timeout`.
+        pub fn set_hard_timeout(&mut self, hard_timeout: Duration) -> &mut Self {
+            self.dialog_mut().hard_timeout = Some(hard_timeout);
+            self
+        }
+
+        /// Render the dialog as modal.
+        pub fn with_modal(mut self) -> Self {
+            self.dialog_mut().modal = true;
+            self
+        }
+
+        /// Render the dialog as modal. Zenity's `--modal` option is a plain flag, so the provided
+        /// text is discarded.
+        #[deprecated(
+            since = "0.3.7",
+            note = "`--modal` takes no value; use `with_modal` instead"
+        )]
+        pub fn with_modal_hint(self, _modal_hint: impl Into<String>) -> Self {
+            self.with_modal()
+        }
+
+        /// Play a sound when the dialog is shown. The sound plays concurrently with the dialog and
+        /// is best-effort: if no sound player is available, a warning is logged and the dialog is
+        /// shown silently.
+        #[cfg(feature = "sound")]
+        pub fn with_sound(mut self, sound: SoundCue) -> Self {
+            self.set_sound(sound);
+            self
+        }
+
+        /// Play a sound when the dialog is shown. See `with_sound`.
+        #[cfg(feature = "sound")]
+        pub fn set_sound(&mut self, sound: SoundCue) -> &mut Self {
+            self.dialog_mut().sound = Some(sound);
+            self
+        }
+
+        /// Wait until the user is active before showing the dialog, checking every `poll` for at
+        /// most `max_defer`. If the user is still idle afterwards the dialog is shown anyway; use
+        /// [ZenityDialog::with_idle_deferral] for more control.
+        #[cfg(feature = "idle-detect")]
+        pub fn with_defer_while_idle(mut self, max_defer: Duration, poll: Duration) -> Self {
+            self.set_defer_while_idle(max_defer, poll);
+            self
+        }
+
+        /// Wait until the user is active before showing the dialog. See `with_defer_while_idle`.
+        #[cfg(feature = "idle-detect")]
+        pub fn set_defer_while_idle(&mut self, max_defer: Duration, poll: Duration) -> &mut Self {
+            self.set_idle_deferral(IdleDeferral::new(max_defer, poll))
+        }
+
+        /// Wait until the user is active before showing the dialog.
+        #[cfg(feature = "idle-detect")]
+        pub fn with_idle_deferral(mut self, idle_deferral: IdleDeferral) -> Self {
+            self.set_idle_deferral(idle_deferral);
+            self
+        }
+
+        /// Wait until the user is active before showing the dialog. See `with_idle_deferral`.
+        #[cfg(feature = "idle-detect")]
+        pub fn set_idle_deferral(&mut self, idle_deferral: IdleDeferral) -> &mut Self {
+            self.dialog_mut().idle_deferral = Some(idle_deferral);
+            self
+        }
+
+        /// Run the provided executable instead of `zenity`, for example a bundled copy or one that
+        /// isn't on `PATH`.
+        pub fn with_program(mut self, program: impl Into<PathBuf>) -> Self {
+            self.set_program(program);
+            self
+        }
+
+        /// Run the provided executable instead of `zenity`. See `with_program`.
+        pub fn set_program(&mut self, program: impl Into<PathBuf>) -> &mut Self {
+            self.dialog_mut().program = Some(program.into());
+            self
+        }
+
+        /// Run the Zenity process in the provided working directory. Relative paths passed to
+        /// Zenity, such as a custom icon or the initial file of a file selection, are made
+        /// absolute against it when the arguments are built, so that they resolve predictably
+        /// and show unambiguously in [ZenityDialog::dry_run]. A relative working directory is
+        /// itself resolved against the working directory of this process.
+        pub fn with_current_dir(mut self, current_dir: impl Into<PathBuf>) -> Self {
+            self.set_current_dir(current_dir);
+            self
+        }
+
+        /// Run the Zenity process in the provided working directory. See `with_current_dir`.
+        pub fn set_current_dir(&mut self, current_dir: impl Into<PathBuf>) -> &mut Self {
+            self.dialog_mut().current_dir = Some(current_dir.into());
+            self
+        }
+
+        /// Set an environment variable for the Zenity process only.
+        pub fn with_env(mut self, key: impl Into<OsString>, value: impl Into<OsString>) -> Self {
+            self.set_env(key, value);
+            self
+        }
+
+        /// Set an environment variable for the Zenity process only. See `with_env`.
+        pub fn set_env(
+            &mut self,
+            key: impl Into<OsString>,
+            value: impl Into<OsString>,
+        ) -> &mut Self {
+            self.dialog_mut().env.insert(key.into(), Some(value.into()));
+            self
+        }
+
+        /// Remove an environment variable for the Zenity process only.
+        pub fn with_env_remove(mut self, key: impl Into<OsString>) -> Self {
+            self.set_env_remove(key);
+            self
+        }
+
+        /// Remove an environment variable for the Zenity process only. See `with_env_remove`.
+        pub fn set_env_remove(&mut self, key: impl Into<OsString>) -> &mut Self {
+            self.dialog_mut().env.insert(key.into(), None);
+            self
+        }
+
+        /// Don't let the Zenity process inherit the environment of this process. Variables set with
+        /// `with_env` are still passed, regardless of the order in which the methods are called.
+        pub fn with_env_clear(mut self) -> Self {
+            self.dialog_mut().env_clear = true;
+            self
+        }
+
+        /// Render the dialog with the named GTK theme, as in `Adwaita:dark`, by setting `GTK_THEME`
+        /// for the Zenity process only. Variables set with `with_env` take precedence.
+        pub fn with_gtk_theme(mut self, gtk_theme: impl Into<String>) -> Self {
+            self.set_gtk_theme(gtk_theme);
+            self
+        }
+
+        /// Render the dialog with the named GTK theme. See `with_gtk_theme`.
+        pub fn set_gtk_theme(&mut self, gtk_theme: impl Into<String>) -> &mut Self {
+            self.dialog_mut().gtk_theme = Some(gtk_theme.into());
+            self
+        }
+
+        /// Ask for the dark variant of the theme, for both GTK 3 and libadwaita based versions of
+        /// Zenity. Only the environment of the Zenity process is changed. Variables set with
+        /// `with_env` take precedence.
+        pub fn prefer_dark(mut self) -> Self {
+            self.dialog_mut().prefer_dark = true;
+            self
+        }
+
+        /// Show the dialog on the provided X11 display, as in `:0`, by setting `DISPLAY` for the
+        /// Zenity process only.
+        pub fn with_display(mut self, display: impl Into<OsString>) -> Self {
+            self.set_display(display);
+            self
+        }
+
+        /// Show the dialog on the provided X11 display. See `with_display`.
+        pub fn set_display(&mut self, display: impl Into<OsString>) -> &mut Self {
+            self.set_env("DISPLAY", display)
+        }
+
+        /// Run Zenity even when neither `DISPLAY` nor `WAYLAND_DISPLAY` is set, instead of failing
+        /// with [crate::Error::NoDisplay]. Useful when the display is reached by other means.
+        pub fn allow_headless(mut self) -> Self {
+            self.dialog_mut().allow_headless = true;
+            self
+        }
+
+        /// Close the dialog when this process exits, even if it crashes or is killed, instead of
+        /// leaving the window open. The dialog is tied to the thread that shows it, so it also
+        /// closes if that thread exits first. Only has an effect on Linux.
+        pub fn die_with_parent(mut self) -> Self {
+            self.dialog_mut().die_with_parent = true;
+            self
+        }
+
+        /// Also check additional arguments against the [crate::Capabilities] of the installed
+        /// Zenity. Options set through the typed builders are always checked when the capabilities
+        /// can be determined, failing with [crate::Error::UnsupportedOption] instead of showing a
+        /// dialog that renders wrong; additional arguments are exempt unless this is set.
+        pub fn strict_capabilities(mut self) -> Self {
+            self.dialog_mut().strict_capabilities = true;
+            self
+        }
+
+        /// Emit the option names of Zenity 3, as in `--icon-name`, without probing the installed
+        /// version.
+        pub fn force_legacy_flags(mut self) -> Self {
+            self.dialog_mut().flag_style = FlagStyle::Legacy;
+            self
+        }
+
+        /// Emit the option names of Zenity 4, as in `--icon`, without probing the installed version.
+        pub fn force_modern_flags(mut self) -> Self {
+            self.dialog_mut().flag_style = FlagStyle::Modern;
+            self
+        }
+
+        /// Attach an additional custom argument. Used to handle arguments that aren't currently statically
+        /// supported. Use at your own risk. Note that this function will automatically prepend -- to the argument
+        /// so there is no need to provide it. However, if you do provide it, it will still work.
+        pub fn with_additional_arg(mut self, arg: impl Into<Arg>) -> Self {
+            self.push_additional_arg(arg);
+            self
+        }
+
+        /// Attach an additional custom argument. See `with_additional_arg`.
+        pub fn push_additional_arg(&mut self, arg: impl Into<Arg>) -> &mut Self {
+            self.dialog_mut().additional_args.push(arg.into());
+            self
+        }
+
+        /// Like `with_additional_arg`, but takes any number of arguments, such as an array of
+        /// names and pairs or an iterator of [Arg]s. They are attached in order.
+        pub fn with_additional_args<I, A>(mut self, args: I) -> Self
+        where
+            I: IntoIterator<Item = A>,
+            A: Into<Arg>,
+        {
+            self.extend_additional_args(args);
+            self
+        }
+
+        /// Attach additional custom arguments. See `with_additional_args`.
+        pub fn extend_additional_args<I, A>(&mut self, args: I) -> &mut Self
+        where
+            I: IntoIterator<Item = A>,
+            A: Into<Arg>,
+        {
+            self.dialog_mut()
+                .additional_args
+                .extend(args.into_iter().map(Into::into));
+            self
+        }
+
+        /// Attach an additional argument that replaces every other argument with the same name,
+        /// whether generated from a typed setting, as in `with_width`, or added earlier with
+        /// `with_additional_arg`. Options renamed in Zenity 4 match under either name, so
+        /// overriding `icon` also replaces `--icon-name`. The override is emitted last, so Zenity
+        /// receives it exactly once, regardless of the order of the calls. Additional arguments
+        /// added later aren't removed; see [ZenityDialog::conflicts].
+        pub fn with_arg_override(mut self, arg: impl Into<Arg>) -> Self {
+            self.set_arg_override(arg);
+            self
+        }
+
+        /// Attach an additional argument that replaces every other argument with the same name.
+        /// See `with_arg_override`.
+        pub fn set_arg_override(&mut self, arg: impl Into<Arg>) -> &mut Self {
+            let arg: Arg = arg.into();
+            let dialog = self.dialog_mut();
+            dialog
+                .additional_args
+                .retain(|additional| !FlagStyle::same_option(additional.name(), arg.name()));
+            dialog.overridden.insert(arg.name().to_owned());
+            dialog.additional_args.push(arg);
+            self
+        }
+    };
+}
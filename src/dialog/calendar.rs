@@ -1,24 +1,34 @@
-use super::{application::ToArgVector, ZenityApplication};
+#[cfg(not(feature = "chrono"))]
+use super::date::Date;
+use super::{application::ToArgVector, DialogOutput, ZenityApplication};
 #[cfg(feature = "chrono")]
-use chrono::NaiveDate;
+use chrono::{Datelike, Local, NaiveDate};
+use std::convert::TryFrom;
 use std::fmt::{Debug, Display};
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+use std::{collections::BTreeMap, ffi::OsString};
 
 /// Settings for a dialog that displays a calendar for date selection.
-/// With feature "chrono" enabled, the output will be automatically
-/// parsed into a NaiveDate.
-#[derive(Debug, Default, Clone)]
+/// The output is parsed into a [Date], or with feature "chrono" enabled, into a NaiveDate.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(::serde::Deserialize),
+    serde(default, deny_unknown_fields)
+)]
 pub struct Calendar {
     /// The body text
     pub text: Option<String>,
 
-    /// The numeric day of the month to display as the default input. If it is larger than what is possible for the
-    /// selected month, it is ignored.
+    /// The numeric day of the month to display as the default input. Zenity ignores a day the
+    /// selected month doesn't have, so [crate::ZenityDialog::validate] rejects it.
     pub day: Option<usize>,
 
     /// The month to display as default input
     pub month: Option<Month>,
 
-    /// The year to display as default input
+    /// The year to display as default input, within [Calendar::YEARS]
     pub year: Option<isize>,
 
     /// The output format for the date the user selects
@@ -29,52 +39,200 @@ impl ZenityApplication for Calendar {
     #[cfg(feature = "chrono")]
     type Return = NaiveDate;
     #[cfg(not(feature = "chrono"))]
-    type Return = String;
+    type Return = Date;
 
-    fn parse(&self, stdout: &str) -> Result<Self::Return, crate::Error> {
-        #[cfg(feature = "chrono")]
-        return NaiveDate::parse_from_str(
-            stdout,
-            self.format.as_deref().unwrap_or(Self::DEFAULT_DATE_FORMAT),
-        )
-        .map_err(|err| crate::Error::ParseResultFailure(anyhow::Error::new(err)));
+    fn parse(&self, output: &DialogOutput) -> Result<Self::Return, crate::Error> {
+        let stdout = output.stdout();
+        let format = self.date_format();
 
+        #[cfg(feature = "chrono")]
+        let parsed = NaiveDate::parse_from_str(stdout, format);
         #[cfg(not(feature = "chrono"))]
-        Ok(stdout.to_owned())
+        let parsed = Date::try_from(stdout);
+
+        parsed.map_err(|err| {
+            crate::ParseError::new("calendar", stdout, err)
+                .with_format(format)
+                .into()
+        })
+    }
+
+    fn default_title() -> Option<&'static str> {
+        Some("Calendar")
+    }
+
+    fn validate(&self) -> Vec<crate::ValidationError> {
+        self.validate_day()
+            .into_iter()
+            .chain(self.validate_year())
+            .chain(self.validate_format())
+            .collect()
+    }
+
+    fn field_map(&self) -> BTreeMap<&'static str, String> {
+        let mut fields = BTreeMap::new();
+        if let Some(ref text) = self.text {
+            fields.insert("text", text.to_string());
+        }
+
+        if let Some(ref day) = self.day {
+            fields.insert("day", day.to_string());
+        }
+
+        if let Some(ref month) = self.month {
+            fields.insert("month", format!("{month:?}"));
+        }
+
+        if let Some(ref year) = self.year {
+            fields.insert("year", year.to_string());
+        }
+
+        if let Some(ref format) = self.format {
+            fields.insert("format", format.to_string());
+        }
+
+        fields
     }
 }
 
+impl_has_text!(Calendar);
+
 impl ToArgVector for Calendar {
-    fn to_argv(&self) -> Vec<String> {
-        let mut args = vec!["--calendar".to_string()];
+    fn to_argv(&self) -> Vec<OsString> {
+        let mut args = vec![OsString::from("--calendar")];
 
         if let Some(ref text) = self.text {
-            args.push(format!("--text={text}"))
+            args.push(format!("--text={text}").into())
         };
 
         if let Some(ref day) = self.day {
-            args.push(format!("--day={day}"))
+            args.push(format!("--day={day}").into())
         };
 
         if let Some(ref month) = self.month {
-            args.push(format!("--month={month}"))
+            args.push(format!("--month={month}").into())
         };
 
         if let Some(ref year) = self.year {
-            args.push(format!("--year={year}"))
+            args.push(format!("--year={year}").into())
         };
 
-        if let Some(ref format) = self.format {
-            args.push(format!("--date-format={format}"));
-        }
+        args.push(format!("--date-format={}", self.date_format()).into());
 
         args
     }
 }
 
 impl Calendar {
+    /// The format passed to Zenity and used to parse its output when none is set. Without an
+    /// explicit format, Zenity would print dates in the user's locale.
+    pub const DEFAULT_DATE_FORMAT: &'static str = "%Y-%m-%d";
+
+    /// The years a calendar can preselect. Zenity wraps or ignores years outside this range
+    /// inconsistently, so [crate::ZenityDialog::validate] rejects them.
+    pub const YEARS: RangeInclusive<isize> = 1..=9999;
+
+    /// The format passed to Zenity: the configured one, or [Calendar::DEFAULT_DATE_FORMAT], so
+    /// that the output and the parser always agree.
+    fn date_format(&self) -> &str {
+        self.format.as_deref().unwrap_or(Self::DEFAULT_DATE_FORMAT)
+    }
+
+    fn validate_day(&self) -> Option<crate::ValidationError> {
+        let day = self.day?;
+        // An invalid year is reported on its own, and shouldn't also fail February 29th.
+        let year = self.year.filter(|year| Self::YEARS.contains(year));
+
+        match (day, self.month) {
+            (1..=31, None) => None,
+            (1..=31, Some(month)) if day as u32 <= month.days(year) => None,
+            (1..=31, Some(month)) => Some(crate::ValidationError::new(
+                "day",
+                match year {
+                    Some(year) => format!("{} {year} has no day {day}", month.name()),
+                    None => format!("{} has no day {day}", month.name()),
+                },
+            )),
+            _ => Some(crate::ValidationError::new(
+                "day",
+                "must be between 1 and 31",
+            )),
+        }
+    }
+
+    fn validate_year(&self) -> Option<crate::ValidationError> {
+        let year = self.year?;
+        match Self::YEARS.contains(&year) {
+            true => None,
+            false => Some(crate::ValidationError::new(
+                "year",
+                format!(
+                    "must be between {} and {}",
+                    Self::YEARS.start(),
+                    Self::YEARS.end()
+                ),
+            )),
+        }
+    }
+
+    /// Without chrono, only the default format can be parsed into a [Date].
+    #[cfg(not(feature = "chrono"))]
+    fn validate_format(&self) -> Option<crate::ValidationError> {
+        match self.format.as_deref()? {
+            Self::DEFAULT_DATE_FORMAT => None,
+            format => Some(crate::ValidationError::new(
+                "format",
+                format!("{format:?} can only be parsed with the \"chrono\" feature"),
+            )),
+        }
+    }
+
+    /// Check that chrono can parse what Zenity prints with the format back into the selected
+    /// date. Names of weekdays and months are rejected, as Zenity prints them in the user's
+    /// language while chrono only parses English ones.
     #[cfg(feature = "chrono")]
-    const DEFAULT_DATE_FORMAT: &'static str = "%d/%m/%y";
+    fn validate_format(&self) -> Option<crate::ValidationError> {
+        use chrono::format::{Fixed, Item, StrftimeItems};
+        use std::fmt::Write;
+
+        let format = self.format.as_deref()?;
+        let mut items = StrftimeItems::new(format);
+        let localized = items.any(|item| {
+            matches!(
+                item,
+                Item::Fixed(
+                    Fixed::ShortWeekdayName
+                        | Fixed::LongWeekdayName
+                        | Fixed::ShortMonthName
+                        | Fixed::LongMonthName
+                        | Fixed::LowerAmPm
+                        | Fixed::UpperAmPm
+                )
+            )
+        });
+
+        if localized {
+            return Some(crate::ValidationError::new(
+                "format",
+                format!("{format:?} contains names, which Zenity prints in the user's language"),
+            ));
+        }
+
+        // Any date with distinct day, month, and year digits would do.
+        let sample = NaiveDate::from_ymd_opt(2031, 12, 25).expect("the sample date is valid");
+        // Formatting fails instead of panicking when written rather than converted to a string.
+        let mut printed = String::new();
+        let round_trips = write!(printed, "{}", sample.format(format)).is_ok()
+            && NaiveDate::parse_from_str(&printed, format) == Ok(sample);
+
+        match round_trips {
+            true => None,
+            false => Some(crate::ValidationError::new(
+                "format",
+                format!("{format:?} isn't a date format chrono can parse back into a date"),
+            )),
+        }
+    }
 
     /// Default implementation
     pub fn new() -> Self {
@@ -83,46 +241,128 @@ impl Calendar {
 
     /// Set body text
     pub fn with_text(mut self, text: impl Into<String>) -> Self {
+        self.set_text(text);
+        self
+    }
+
+    /// Set body text, in place.
+    pub fn set_text(&mut self, text: impl Into<String>) -> &mut Self {
         self.text = Some(text.into());
         self
     }
 
-    /// Set the day
+    /// Set body text to markup
+    pub fn with_markup(self, markup: crate::Markup) -> Self {
+        self.with_text(markup.build())
+    }
+
+    /// Preselect the date, setting the day, month, and year.
+    ///
+    /// ```
+    /// # extern crate chrono;
+    /// # use zenity_dialog::dialog::{Calendar, Month};
+    /// # use chrono::NaiveDate;
+    /// let calendar = Calendar::new().with_date(NaiveDate::from_ymd_opt(2026, 10, 17).unwrap());
+    /// assert_eq!(
+    ///     (calendar.day, calendar.month, calendar.year),
+    ///     (Some(17), Some(Month::October), Some(2026))
+    /// );
+    /// ```
+    #[cfg(feature = "chrono")]
+    pub fn with_date(mut self, date: NaiveDate) -> Self {
+        self.set_date(date);
+        self
+    }
+
+    /// Preselect the date, in place.
+    #[cfg(feature = "chrono")]
+    pub fn set_date(&mut self, date: NaiveDate) -> &mut Self {
+        self.day = Some(date.day() as usize);
+        self.month = Month::try_from(date.month()).ok();
+        self.year = Some(date.year() as isize);
+        self
+    }
+
+    /// A calendar with today's date, in the local time zone, preselected.
+    #[cfg(feature = "chrono")]
+    pub fn today() -> Self {
+        Self::new().with_date(Local::now().date_naive())
+    }
+
+    /// Set the day. [crate::ZenityDialog::validate] rejects days over 31 and days the selected
+    /// month doesn't have, including February 29th outside leap years.
     pub fn with_day(mut self, day: impl Into<usize>) -> Self {
+        self.set_day(day);
+        self
+    }
+
+    /// Set the day, in place.
+    pub fn set_day(&mut self, day: impl Into<usize>) -> &mut Self {
         self.day = Some(day.into());
         self
     }
 
     /// Set the month
     pub fn with_month(mut self, month: impl Into<Month>) -> Self {
+        self.set_month(month);
+        self
+    }
+
+    /// Set the month, in place.
+    pub fn set_month(&mut self, month: impl Into<Month>) -> &mut Self {
         self.month = Some(month.into());
         self
     }
 
-    /// Set the month
+    /// Set the year
     pub fn with_year(mut self, year: impl Into<isize>) -> Self {
+        self.set_year(year);
+        self
+    }
+
+    /// Set the year, in place.
+    pub fn set_year(&mut self, year: impl Into<isize>) -> &mut Self {
         self.year = Some(year.into());
         self
     }
 
-    #[cfg(not(feature = "chrono"))]
     /// Set the format for the returned date.
     /// The default depends on the user locale or be set with the strftime style.
-    /// For example %A %d/%m/%y. Note that this feature is disabled when using
-    /// the "chrono" features as it can interfere with Chrono parsing the output.
+    /// For example %A %d/%m/%y. The same format is used to parse the output, which only
+    /// supports [Calendar::DEFAULT_DATE_FORMAT] without the "chrono" feature. With it,
+    /// [crate::ZenityDialog::validate] rejects formats chrono can't parse back into a date,
+    /// such as ones with weekday or month names.
     pub fn with_format(mut self, format: impl Into<String>) -> Self {
+        self.set_format(format);
+        self
+    }
+
+    /// Set the format for the returned date, in place. See [Calendar::with_format].
+    pub fn set_format(&mut self, format: impl Into<String>) -> &mut Self {
         self.format = Some(format.into());
         self
     }
 }
 
-/// Represents a calendar month for [Application::Calendar]
-#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+/// Represents a calendar month for [Calendar]. Converts from its number with [TryFrom], and
+/// from its English name or three-letter abbreviation, in any case, with [FromStr]. [Display]
+/// renders its number, as passed to Zenity.
+///
+/// ```
+/// # use zenity_dialog::dialog::Month;
+/// # use std::convert::TryFrom;
+/// assert_eq!("feb".parse(), Ok(Month::February));
+/// assert_eq!(Month::try_from(2u8), Ok(Month::February));
+/// assert_eq!((Month::February.number(), Month::February.name()), (2, "February"));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(::serde::Deserialize))]
 pub enum Month {
     /// January
     January = 1,
-    /// Feburary
-    Feburary = 2,
+    /// February
+    #[cfg_attr(feature = "serde", serde(alias = "Feburary"))]
+    February = 2,
     /// March
     March = 3,
     /// April
@@ -145,23 +385,322 @@ pub enum Month {
     December = 12,
 }
 
+#[allow(non_upper_case_globals)]
+impl Month {
+    /// The former, misspelled name of [Month::February].
+    #[deprecated(since = "0.3.7", note = "use `Month::February` instead")]
+    pub const Feburary: Month = Month::February;
+
+    /// Every month, in order.
+    pub const ALL: [Month; 12] = [
+        Month::January,
+        Month::February,
+        Month::March,
+        Month::April,
+        Month::May,
+        Month::June,
+        Month::July,
+        Month::August,
+        Month::September,
+        Month::October,
+        Month::November,
+        Month::December,
+    ];
+
+    /// The number of the month, from 1 for January to 12 for December.
+    pub fn number(self) -> u32 {
+        self as u32
+    }
+
+    /// The number of days in the month of the year, or in any year when it's [None], in which
+    /// case February has 29 days.
+    ///
+    /// ```
+    /// # use zenity_dialog::dialog::Month;
+    /// assert_eq!(Month::February.days(Some(2024)), 29);
+    /// assert_eq!(Month::February.days(Some(2100)), 28);
+    /// ```
+    pub fn days(self, year: Option<isize>) -> u32 {
+        match self {
+            Month::February => match year {
+                Some(year) if !is_leap_year(year) => 28,
+                _ => 29,
+            },
+            Month::April | Month::June | Month::September | Month::November => 30,
+            _ => 31,
+        }
+    }
+
+    /// The English name of the month, as in `January`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Month::January => "January",
+            Month::February => "February",
+            Month::March => "March",
+            Month::April => "April",
+            Month::May => "May",
+            Month::June => "June",
+            Month::July => "July",
+            Month::August => "August",
+            Month::September => "September",
+            Month::October => "October",
+            Month::November => "November",
+            Month::December => "December",
+        }
+    }
+}
+
+impl TryFrom<u32> for Month {
+    type Error = InvalidMonth;
+
+    /// Convert the number of the month, from 1 for January to 12 for December.
+    fn try_from(number: u32) -> Result<Self, Self::Error> {
+        number
+            .checked_sub(1)
+            .and_then(|index| Month::ALL.get(index as usize))
+            .copied()
+            .ok_or_else(|| InvalidMonth(number.to_string()))
+    }
+}
+
+impl TryFrom<u8> for Month {
+    type Error = InvalidMonth;
+
+    /// Convert the number of the month, from 1 for January to 12 for December.
+    fn try_from(number: u8) -> Result<Self, Self::Error> {
+        Month::try_from(u32::from(number))
+    }
+}
+
+impl FromStr for Month {
+    type Err = InvalidMonth;
+
+    /// Parse the English name of the month or its three-letter abbreviation, in any case, as in
+    /// `March`, `mar`, or `MAR`.
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Month::ALL
+            .iter()
+            .copied()
+            .find(|month| {
+                name.eq_ignore_ascii_case(month.name())
+                    || name.eq_ignore_ascii_case(&month.name()[..3])
+            })
+            .ok_or_else(|| InvalidMonth(name.to_owned()))
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<chrono::Month> for Month {
+    fn from(month: chrono::Month) -> Self {
+        Month::try_from(month.number_from_month()).expect("chrono months are between 1 and 12")
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<Month> for chrono::Month {
+    fn from(month: Month) -> Self {
+        chrono::Month::try_from(month.number() as u8).expect("months are between 1 and 12")
+    }
+}
+
+fn is_leap_year(year: isize) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+/// A value that doesn't name a [Month].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, thiserror::Error)]
+#[error("{0:?} is not a month")]
+pub struct InvalidMonth(String);
+
 impl Display for Month {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let as_int = match self {
-            Month::January => 1,
-            Month::Feburary => 2,
-            Month::March => 3,
-            Month::April => 4,
-            Month::May => 5,
-            Month::June => 6,
-            Month::July => 7,
-            Month::August => 8,
-            Month::September => 9,
-            Month::October => 10,
-            Month::November => 11,
-            Month::December => 12,
+        write!(f, "{}", self.number())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn argv(calendar: &Calendar) -> Vec<String> {
+        calendar
+            .to_argv()
+            .into_iter()
+            .map(|arg| arg.into_string().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn the_preselected_date_is_passed_as_numbers() {
+        let calendar = Calendar::new()
+            .with_day(9usize)
+            .with_month(Month::March)
+            .with_year(2026isize);
+        assert_eq!(
+            argv(&calendar),
+            [
+                "--calendar",
+                "--day=9",
+                "--month=3",
+                "--year=2026",
+                "--date-format=%Y-%m-%d"
+            ]
+        );
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn a_date_preselects_its_day_month_and_year() {
+        let date = NaiveDate::from_ymd_opt(2026, 3, 9).unwrap();
+        assert_eq!(
+            Calendar::new().with_date(date),
+            Calendar::new()
+                .with_day(9usize)
+                .with_month(Month::March)
+                .with_year(2026isize)
+        );
+
+        // The largest day and month survive the conversion too.
+        let date = NaiveDate::from_ymd_opt(1999, 12, 31).unwrap();
+        let args = argv(&Calendar::new().with_date(date));
+        assert_eq!(args[1..4], ["--day=31", "--month=12", "--year=1999"]);
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn custom_formats_round_trip_through_zenity() {
+        let date = NaiveDate::from_ymd_opt(2031, 2, 7).unwrap();
+        for format in ["%Y-%m-%d", "%d.%m.%Y", "%m/%d/%Y", "%Y%m%d", "%d %m %y"] {
+            let calendar = Calendar::new().with_format(format);
+            assert!(argv(&calendar).contains(&format!("--date-format={format}")));
+            assert!(calendar.validate().is_empty(), "{}", format);
+
+            // What Zenity prints with the format is parsed back with it.
+            let printed = date.format(format).to_string();
+            let output = DialogOutput::from_stdout(printed);
+            assert_eq!(calendar.parse(&output).unwrap(), date, "{format}");
+        }
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn output_in_another_format_fails_naming_the_format() {
+        let calendar = Calendar::new().with_format("%d.%m.%Y");
+        let err = calendar
+            .parse(&DialogOutput::from_stdout("2031-02-07"))
+            .unwrap_err();
+        assert!(err.to_string().contains("%d.%m.%Y"), "{}", err);
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn formats_with_names_are_rejected() {
+        for format in ["%A %d/%m/%Y", "%d %b %Y", "%d %B %Y"] {
+            let problems = Calendar::new().with_format(format).validate();
+            assert_eq!(problems.len(), 1, "{format}");
+            assert!(problems[0].to_string().contains("language"), "{}", format);
+        }
+    }
+
+    /// Without a date format, Zenity would print the date in the user's locale, which the
+    /// parser can't know.
+    #[test]
+    fn the_default_date_format_is_always_passed_and_parsed() {
+        let calendar = Calendar::new();
+        assert_eq!(argv(&calendar), ["--calendar", "--date-format=%Y-%m-%d"]);
+
+        let date = calendar
+            .parse(&DialogOutput::from_stdout("2026-10-17"))
+            .unwrap();
+        assert_eq!(date.to_string(), "2026-10-17");
+
+        // Such as an en_US date, printed when the format isn't passed.
+        assert!(calendar
+            .parse(&DialogOutput::from_stdout("10/17/2026"))
+            .is_err());
+    }
+
+    #[test]
+    fn months_convert_from_their_numbers() {
+        for (index, &month) in Month::ALL.iter().enumerate() {
+            let number = index as u32 + 1;
+            assert_eq!(month.number(), number);
+            assert_eq!(Month::try_from(number), Ok(month));
+            assert_eq!(Month::try_from(number as u8), Ok(month));
+            assert_eq!(month.to_string(), number.to_string());
+        }
+
+        for number in [0, 13, u32::MAX] {
+            assert_eq!(
+                Month::try_from(number),
+                Err(InvalidMonth(number.to_string()))
+            );
+        }
+        assert!(Month::try_from(0u8).is_err());
+        assert!(Month::try_from(13u8).is_err());
+    }
+
+    #[test]
+    fn months_parse_from_their_names_and_abbreviations() {
+        for &month in Month::ALL.iter() {
+            let name = month.name();
+            assert_eq!(name.parse(), Ok(month));
+            assert_eq!(name.to_lowercase().parse(), Ok(month));
+            assert_eq!(name.to_uppercase().parse(), Ok(month));
+            assert_eq!(name[..3].parse(), Ok(month));
+            assert_eq!(name[..3].to_uppercase().parse(), Ok(month));
+        }
+
+        assert_eq!(Month::February.name(), "February");
+        for name in ["", "Ja", "Janu", "Feburary", "1", " March"] {
+            assert_eq!(
+                name.parse::<Month>(),
+                Err(InvalidMonth(name.to_owned())),
+                "{:?}",
+                name
+            );
+        }
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn the_misspelled_february_is_still_february() {
+        assert_eq!(Month::Feburary, Month::February);
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn months_convert_to_and_from_chrono() {
+        for &month in Month::ALL.iter() {
+            let converted = chrono::Month::from(month);
+            assert_eq!(converted.number_from_month(), month.number());
+            assert_eq!(Month::from(converted), month);
+        }
+    }
+
+    #[test]
+    fn february_29th_is_only_valid_in_leap_years() {
+        let leap_day = |year: isize| {
+            Calendar::new()
+                .with_day(29usize)
+                .with_month(Month::February)
+                .with_year(year)
+                .validate()
         };
 
-        write!(f, "{as_int}")
+        for year in [2024, 2000, 4] {
+            assert!(leap_day(year).is_empty(), "{}", year);
+        }
+
+        for year in [2023, 2100, 1900] {
+            let problems = leap_day(year);
+            assert_eq!(
+                problems,
+                [crate::ValidationError::new(
+                    "day",
+                    format!("February {year} has no day 29")
+                )]
+            );
+        }
     }
 }
@@ -0,0 +1,186 @@
+use super::calendar::Month;
+#[cfg(feature = "chrono")]
+use chrono::{Datelike, NaiveDate};
+use std::convert::TryFrom;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// A calendar date, as selected in a [super::Calendar] without the "chrono" feature. Converts
+/// from and displays as the ISO format `YYYY-MM-DD`, which is what the calendar asks Zenity to
+/// print.
+///
+/// ```
+/// # use zenity_dialog::dialog::{Date, Month};
+/// # use std::convert::TryFrom;
+/// let date = Date::try_from("2024-02-29").unwrap();
+/// assert_eq!((date.year(), date.month(), date.day()), (2024, Month::February, 29));
+/// assert_eq!(date.to_string(), "2024-02-29");
+/// assert!(Date::try_from("2023-02-29").is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    year: i32,
+    month: Month,
+    day: u8,
+}
+
+impl Date {
+    /// The date, or an error if the month doesn't have the day in that year.
+    pub fn new(year: i32, month: Month, day: u8) -> Result<Self, InvalidDate> {
+        match day >= 1 && u32::from(day) <= month.days(Some(year as isize)) {
+            true => Ok(Self { year, month, day }),
+            false => Err(InvalidDate::new(
+                format!("{year}-{}-{day}", month.number()),
+                format!("{} {year} has no day {day}", month.name()),
+            )),
+        }
+    }
+
+    /// The year.
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    /// The month.
+    pub fn month(&self) -> Month {
+        self.month
+    }
+
+    /// The day of the month, starting at 1.
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+}
+
+impl TryFrom<&str> for Date {
+    type Error = InvalidDate;
+
+    /// Parse a date in the ISO format `YYYY-MM-DD`.
+    fn try_from(text: &str) -> Result<Self, Self::Error> {
+        let parse = || -> Result<Date, String> {
+            let mut parts = text.splitn(3, '-');
+            let (Some(year), Some(month), Some(day)) = (parts.next(), parts.next(), parts.next())
+            else {
+                return Err("expected the format YYYY-MM-DD".to_owned());
+            };
+
+            let month =
+                Month::try_from(number::<u32>(month, "month")?).map_err(|err| err.to_string())?;
+            let day = number::<u8>(day, "day")?;
+            Date::new(number(year, "year")?, month, day).map_err(|err| err.reason)
+        };
+
+        parse().map_err(|reason| InvalidDate::new(text, reason))
+    }
+}
+
+/// Parse a part of a date, which must consist only of digits.
+fn number<N: FromStr>(part: &str, name: &str) -> Result<N, String> {
+    match !part.is_empty() && part.bytes().all(|byte| byte.is_ascii_digit()) {
+        true => part
+            .parse()
+            .map_err(|_| format!("the {name} {part} is out of range")),
+        false => Err(format!("the {name} {part:?} isn't a number")),
+    }
+}
+
+impl FromStr for Date {
+    type Err = InvalidDate;
+
+    /// Parse a date in the ISO format `YYYY-MM-DD`. See [Date::try_from].
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        Date::try_from(text)
+    }
+}
+
+impl Display for Date {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:04}-{:02}-{:02}",
+            self.year,
+            self.month.number(),
+            self.day
+        )
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<Date> for NaiveDate {
+    fn from(date: Date) -> Self {
+        NaiveDate::from_ymd_opt(date.year, date.month.number(), u32::from(date.day))
+            .expect("dates are valid on construction")
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<NaiveDate> for Date {
+    fn from(date: NaiveDate) -> Self {
+        Self {
+            year: date.year(),
+            month: Month::try_from(date.month()).expect("chrono months are between 1 and 12"),
+            day: date.day() as u8,
+        }
+    }
+}
+
+/// Text that isn't a valid [Date].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, thiserror::Error)]
+#[error("{input:?} is not a date: {reason}")]
+pub struct InvalidDate {
+    input: String,
+    reason: String,
+}
+
+impl InvalidDate {
+    fn new(input: impl Into<String>, reason: impl Into<String>) -> Self {
+        Self {
+            input: input.into(),
+            reason: reason.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reason(text: &str) -> String {
+        Date::try_from(text).unwrap_err().reason
+    }
+
+    #[test]
+    fn parses_iso_dates() {
+        let date: Date = "0987-06-05".parse().unwrap();
+        assert_eq!(
+            (date.year(), date.month(), date.day()),
+            (987, Month::June, 5)
+        );
+        assert_eq!(date.to_string(), "0987-06-05");
+    }
+
+    #[test]
+    fn rejects_invalid_months() {
+        assert_eq!(reason("2024-00-10"), "\"0\" is not a month");
+        assert_eq!(reason("2024-13-10"), "\"13\" is not a month");
+        assert_eq!(reason("2024-Feb-10"), "the month \"Feb\" isn't a number");
+    }
+
+    #[test]
+    fn rejects_invalid_days() {
+        assert_eq!(reason("2024-05-00"), "May 2024 has no day 0");
+        assert_eq!(reason("2024-04-31"), "April 2024 has no day 31");
+        assert_eq!(reason("2023-02-29"), "February 2023 has no day 29");
+        assert_eq!(reason("2024-05-300"), "the day 300 is out of range");
+        assert_eq!(reason("2024-05-1x"), "the day \"1x\" isn't a number");
+    }
+
+    #[test]
+    fn rejects_text_in_another_format() {
+        assert_eq!(reason("17/10/2026"), "expected the format YYYY-MM-DD");
+        assert_eq!(
+            Date::try_from("").unwrap_err().to_string(),
+            "\"\" is not a date: expected the format YYYY-MM-DD"
+        );
+    }
+}
@@ -0,0 +1,112 @@
+use std::{collections::BTreeMap, fmt::Display};
+
+/// The differences between the configurations of two dialogs, as produced by
+/// [crate::ZenityDialog::diff]. Entries are ordered by field name. Fields of the application
+/// are prefixed with `application.`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DialogDiff {
+    /// The fields that differ between the two dialogs.
+    pub entries: Vec<DiffEntry>,
+}
+
+/// A single field that differs between two dialog configurations.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffEntry {
+    /// The field is only set on the other dialog.
+    Added {
+        /// The name of the field
+        field: String,
+        /// The value on the other dialog
+        value: String,
+    },
+    /// The field is only set on this dialog.
+    Removed {
+        /// The name of the field
+        field: String,
+        /// The value on this dialog
+        value: String,
+    },
+    /// The field is set on both dialogs, but to different values.
+    Changed {
+        /// The name of the field
+        field: String,
+        /// The value on this dialog
+        old: String,
+        /// The value on the other dialog
+        new: String,
+    },
+}
+
+impl DialogDiff {
+    /// Compare two field maps.
+    pub(crate) fn between(
+        this: &BTreeMap<String, String>,
+        other: &BTreeMap<String, String>,
+    ) -> Self {
+        let mut entries = Vec::new();
+
+        for (field, old) in this {
+            match other.get(field) {
+                Some(new) if new != old => entries.push(DiffEntry::Changed {
+                    field: field.clone(),
+                    old: old.clone(),
+                    new: new.clone(),
+                }),
+                Some(_) => {}
+                None => entries.push(DiffEntry::Removed {
+                    field: field.clone(),
+                    value: old.clone(),
+                }),
+            }
+        }
+
+        for (field, value) in other {
+            if !this.contains_key(field) {
+                entries.push(DiffEntry::Added {
+                    field: field.clone(),
+                    value: value.clone(),
+                });
+            }
+        }
+
+        entries.sort_by(|a, b| a.field().cmp(b.field()));
+
+        Self { entries }
+    }
+
+    /// Whether the two dialogs are configured identically.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl DiffEntry {
+    /// The name of the field that differs.
+    pub fn field(&self) -> &str {
+        match self {
+            DiffEntry::Added { field, .. }
+            | DiffEntry::Removed { field, .. }
+            | DiffEntry::Changed { field, .. } => field,
+        }
+    }
+}
+
+impl Display for DialogDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for entry in &self.entries {
+            writeln!(f, "{entry}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for DiffEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DiffEntry::Added { field, value } => write!(f, "+ {field}: {value:?}"),
+            DiffEntry::Removed { field, value } => write!(f, "- {field}: {value:?}"),
+            DiffEntry::Changed { field, old, new } => write!(f, "~ {field}: {old:?} -> {new:?}"),
+        }
+    }
+}
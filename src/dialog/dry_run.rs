@@ -0,0 +1,52 @@
+use std::{ffi::OsString, fmt::Display, path::PathBuf};
+
+/// The Zenity invocation a dialog would make, as produced by [crate::ZenityDialog::dry_run].
+/// Its [Display] implementation renders a shell-quoted command line suitable for copying into a
+/// terminal. A program or arguments that aren't valid UTF-8 are rendered lossily.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DryRun {
+    /// The program that would be executed
+    pub program: PathBuf,
+    /// The arguments that would be passed to the program
+    pub args: Vec<OsString>,
+}
+
+impl Display for DryRun {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let args: Vec<_> = self.args.iter().map(|arg| arg.to_string_lossy()).collect();
+        let line = crate::shell::command_line(
+            &self.program.to_string_lossy(),
+            args.iter().map(AsRef::as_ref),
+        );
+        write!(f, "{line}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DryRun;
+
+    #[test]
+    fn displays_a_shell_quoted_command_line() {
+        let dry_run = DryRun {
+            program: "/opt/my apps/zenity".into(),
+            args: vec!["--info".into(), r#"--title=it's "quoted" $HOME"#.into()],
+        };
+        assert_eq!(
+            dry_run.to_string(),
+            r#"'/opt/my apps/zenity' --info '--title=it'\''s "quoted" $HOME'"#
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn displays_arguments_that_arent_utf8_lossily() {
+        use std::{ffi::OsString, os::unix::ffi::OsStringExt};
+
+        let dry_run = DryRun {
+            program: "zenity".into(),
+            args: vec![OsString::from_vec(b"--filename=caf\xe9".to_vec())],
+        };
+        assert_eq!(dry_run.to_string(), "zenity '--filename=caf\u{fffd}'");
+    }
+}
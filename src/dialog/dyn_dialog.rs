@@ -0,0 +1,202 @@
+#[cfg(feature = "calendar")]
+use super::calendar::Calendar;
+#[cfg(feature = "entry")]
+use super::entry::Entry;
+#[cfg(feature = "error")]
+use super::error::Error;
+#[cfg(feature = "file-selection")]
+use super::file_selection::FileSelection;
+#[cfg(feature = "info")]
+use super::info::Info;
+#[cfg(feature = "list")]
+use super::list::List;
+#[cfg(feature = "notification")]
+use super::notification::Notification;
+#[cfg(feature = "question")]
+use super::question::Question;
+#[cfg(feature = "warning")]
+use super::warning::Warning;
+#[cfg(feature = "serde")]
+use super::DialogSpec;
+use super::{ZenityApplication, ZenityDialog, ZenityOutput};
+use crate::CommandRunner;
+
+/// Defines [DynDialog] and [DynOutput] with a variant for each built-in application, and the
+/// conversions and show methods dispatching to the typed dialog.
+macro_rules! dyn_dialog {
+    ($($(#[$cfg:meta])* $name:ident),* $(,)?) => {
+        /// A dialog of any built-in application type, for when the type is only known at
+        /// runtime, such as when it's picked from a configuration, or to keep dialogs of
+        /// different types in one collection. Typed dialogs convert into it with [From].
+        ///
+        /// ```no_run
+        /// # use zenity_dialog::{dialog::{DynDialog, Entry, Info, Question}, ZenityDialog};
+        /// # fn main() -> zenity_dialog::Result<()> {
+        /// let dialogs: Vec<DynDialog> = vec![
+        ///     ZenityDialog::new(Info::new().with_text("Welcome")).into(),
+        ///     ZenityDialog::new(Entry::new().with_text("Your name?")).into(),
+        ///     ZenityDialog::new(Question::new().with_text("Continue?")).into(),
+        /// ];
+        /// for dialog in &dialogs {
+        ///     println!("{:?}", dialog.show_ref()?.content());
+        /// }
+        /// # Ok(())
+        /// # }
+        /// ```
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub enum DynDialog {
+            $(
+                #[doc = concat!("A [", stringify!($name), "] dialog")]
+                $(#[$cfg])*
+                $name(ZenityDialog<$name>),
+            )*
+        }
+
+        /// The content of a [DynDialog], with a variant holding each application's
+        /// [ZenityApplication::Return].
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub enum DynOutput {
+            $(
+                #[doc = concat!("The content of a [", stringify!($name), "] dialog")]
+                $(#[$cfg])*
+                $name(<$name as ZenityApplication>::Return),
+            )*
+        }
+
+        impl DynDialog {
+            /// Render the dialog and wait for user response. See [ZenityDialog::show].
+            pub fn show(self) -> crate::Result<ZenityOutput<DynOutput>> {
+                self.show_ref()
+            }
+
+            /// Like `show`, but doesn't consume the dialog. See [ZenityDialog::show_ref].
+            pub fn show_ref(&self) -> crate::Result<ZenityOutput<DynOutput>> {
+                match *self {
+                    $(
+                        $(#[$cfg])*
+                        DynDialog::$name(ref dialog) => Ok(dialog.show_ref()?.map(DynOutput::$name)),
+                    )*
+                }
+            }
+
+            /// Render the dialog through the runner. See [ZenityDialog::show_with_runner].
+            pub fn show_with_runner(
+                &self,
+                runner: &impl CommandRunner,
+            ) -> crate::Result<ZenityOutput<DynOutput>> {
+                match *self {
+                    $(
+                        $(#[$cfg])*
+                        DynDialog::$name(ref dialog) => {
+                            Ok(dialog.show_with_runner(runner)?.map(DynOutput::$name))
+                        }
+                    )*
+                }
+            }
+
+            /// Check the configuration. See [ZenityDialog::validate].
+            pub fn validate(&self) -> Result<(), Vec<crate::ValidationError>> {
+                match *self {
+                    $(
+                        $(#[$cfg])*
+                        DynDialog::$name(ref dialog) => dialog.validate(),
+                    )*
+                }
+            }
+
+            /// The command line the dialog would run. See [ZenityDialog::to_command_string].
+            pub fn to_command_string(&self) -> String {
+                match *self {
+                    $(
+                        $(#[$cfg])*
+                        DynDialog::$name(ref dialog) => dialog.to_command_string(),
+                    )*
+                }
+            }
+        }
+
+        $(
+            $(#[$cfg])*
+            impl From<ZenityDialog<$name>> for DynDialog {
+                fn from(dialog: ZenityDialog<$name>) -> Self {
+                    DynDialog::$name(dialog)
+                }
+            }
+        )*
+
+        #[cfg(feature = "serde")]
+        impl From<DialogSpec> for DynDialog {
+            fn from(spec: DialogSpec) -> Self {
+                match spec {
+                    $(
+                        $(#[$cfg])*
+                        DialogSpec::$name(dialog) => DynDialog::$name(dialog),
+                    )*
+                }
+            }
+        }
+    };
+}
+
+dyn_dialog! {
+    #[cfg(feature = "calendar")]
+    Calendar,
+    #[cfg(feature = "entry")]
+    Entry,
+    #[cfg(feature = "error")]
+    Error,
+    #[cfg(feature = "file-selection")]
+    FileSelection,
+    #[cfg(feature = "info")]
+    Info,
+    #[cfg(feature = "list")]
+    List,
+    #[cfg(feature = "notification")]
+    Notification,
+    #[cfg(feature = "question")]
+    Question,
+    #[cfg(feature = "warning")]
+    Warning,
+}
+
+#[cfg(all(test, feature = "entry", feature = "question"))]
+mod tests {
+    use super::{DynDialog, DynOutput, Entry, Info, Question};
+    use crate::{
+        runner::{MockResponse, MockRunner},
+        ZenityDialog, ZenityOutput,
+    };
+
+    #[test]
+    fn dialogs_of_different_types_are_shown_from_one_collection() {
+        let runner = MockRunner::new()
+            .respond_to_arg("--entry", MockResponse::affirmed("Ada\n"))
+            .respond_to_arg("--question", MockResponse::rejected())
+            .respond_to_arg("--info", MockResponse::affirmed(""));
+        let dialogs: Vec<DynDialog> = vec![
+            ZenityDialog::new(Info::new().with_text("Welcome")).into(),
+            ZenityDialog::new(Entry::new().with_text("Your name?")).into(),
+            ZenityDialog::new(Question::new().with_text("Continue?")).into(),
+        ];
+
+        let outputs: Vec<_> = dialogs
+            .iter()
+            .map(|dialog| dialog.show_with_runner(&runner).unwrap())
+            .collect();
+
+        assert_eq!(outputs[0], ZenityOutput::Affirmed { content: None });
+        match outputs[1] {
+            ZenityOutput::Affirmed {
+                content: Some(DynOutput::Entry(ref answer)),
+            } => assert_eq!(answer.text, "Ada"),
+            ref other => panic!("unexpected output: {:?}", other),
+        }
+        assert_eq!(outputs[2], ZenityOutput::Rejected { content: None });
+
+        let invocations = runner.invocations();
+        assert_eq!(invocations.len(), 3);
+        assert!(invocations[0].has_arg("--text=Welcome"));
+        assert!(invocations[1].has_arg("--text=Your name?"));
+        assert!(invocations[2].has_arg("--text=Continue?"));
+    }
+}
@@ -1,7 +1,15 @@
-use super::{application::ToArgVector, ZenityApplication};
+use super::{application::ToArgVector, DialogOutput, ZenityApplication};
+use std::fmt::Display;
+use std::{collections::BTreeMap, ffi::OsString};
 
-/// Settings for a dialog with a single text input.
-#[derive(Debug, Clone, Default)]
+/// Settings for a dialog with a single text input. An empty submission is reported as an
+/// [EntryAnswer] with empty text, unless [Entry::with_empty_as_none] is set.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(::serde::Deserialize),
+    serde(default, deny_unknown_fields)
+)]
 pub struct Entry {
     /// The body text
     pub text: Option<String>,
@@ -9,29 +17,108 @@ pub struct Entry {
     pub entry_text: Option<String>,
     /// Prevent word wrap
     pub hide_text: bool,
+    /// How an answer matching the prefilled text is reported
+    pub focus: EntryFocus,
+    /// Report an empty or whitespace-only submission as `Affirmed { content: None }`
+    pub empty_as_none: bool,
+    /// Keep the leading and trailing whitespace of the submission
+    pub preserve_whitespace: bool,
 }
 
 impl ZenityApplication for Entry {
-    type Return = String;
+    type Return = EntryAnswer;
 
-    fn parse(&self, stdout: &str) -> Result<Self::Return, crate::Error> {
-        Ok(stdout.to_owned())
+    fn parse(&self, output: &DialogOutput) -> Result<Self::Return, crate::Error> {
+        let stdout = output.stdout();
+        let accepted_default = match (self.focus, &self.entry_text) {
+            (EntryFocus::AcceptOnEnterWithPrefill, Some(entry_text)) => entry_text == stdout,
+            _ => false,
+        };
+
+        Ok(EntryAnswer {
+            text: stdout.to_owned(),
+            accepted_default,
+        })
+    }
+
+    fn default_title() -> Option<&'static str> {
+        Some("Entry")
+    }
+
+    /// An empty submission is reported as an empty answer, unless it's configured to be
+    /// reported as missing content.
+    fn parses_empty_output(&self) -> bool {
+        !self.empty_as_none
+    }
+
+    fn trim_output(&self) -> bool {
+        !self.preserve_whitespace
+    }
+
+    fn field_map(&self) -> BTreeMap<&'static str, String> {
+        let mut fields = BTreeMap::new();
+        if let Some(ref text) = self.text {
+            fields.insert("text", text.to_string());
+        }
+
+        if let Some(ref entry_text) = self.entry_text {
+            fields.insert("entry_text", entry_text.to_string());
+        }
+
+        if self.hide_text {
+            fields.insert("hide_text", true.to_string());
+        }
+
+        if self.focus != EntryFocus::default() {
+            fields.insert("focus", format!("{:?}", self.focus));
+        }
+
+        if self.empty_as_none {
+            fields.insert("empty_as_none", true.to_string());
+        }
+
+        if self.preserve_whitespace {
+            fields.insert("preserve_whitespace", true.to_string());
+        }
+
+        fields
+    }
+
+    fn secret_options(&self) -> Vec<&'static str> {
+        match self.hide_text {
+            true => vec!["entry-text"],
+            false => Vec::new(),
+        }
+    }
+
+    #[cfg(feature = "tty-fallback")]
+    fn tty_prompt(&self) -> Option<crate::TtyPrompt> {
+        Some(crate::TtyPrompt::Line {
+            text: self
+                .text
+                .as_deref()
+                .map(|text| crate::tty::TtyPrompt::plain(text, true)),
+            default: self.entry_text.clone(),
+            secret: self.hide_text,
+        })
     }
 }
 
+impl_has_text!(Entry);
+
 impl ToArgVector for Entry {
-    fn to_argv(&self) -> Vec<String> {
-        let mut args = vec!["--entry".to_string()];
+    fn to_argv(&self) -> Vec<OsString> {
+        let mut args = vec![OsString::from("--entry")];
         if let Some(ref text) = self.text {
-            args.push(format!("--text={text}"))
+            args.push(format!("--text={text}").into())
         };
 
         if let Some(ref entry_text) = self.entry_text {
-            args.push(format!("--entry-text={entry_text}"))
+            args.push(format!("--entry-text={entry_text}").into())
         };
 
         if self.hide_text {
-            args.push("--hide-text".to_string());
+            args.push("--hide-text".into());
         }
 
         args
@@ -46,19 +133,235 @@ impl Entry {
 
     /// Override default input label.
     pub fn with_text(mut self, text: impl Into<String>) -> Self {
+        self.set_text(text);
+        self
+    }
+
+    /// Override default input label, in place.
+    pub fn set_text(&mut self, text: impl Into<String>) -> &mut Self {
         self.text = Some(text.into());
         self
     }
 
+    /// Override default input label with markup.
+    pub fn with_markup(self, markup: crate::Markup) -> Self {
+        self.with_text(markup.build())
+    }
+
     /// Prefill the input with the given text.
     pub fn with_entry_text(mut self, entry_text: impl Into<String>) -> Self {
+        self.set_entry_text(entry_text);
+        self
+    }
+
+    /// Prefill the input with the given text, in place.
+    pub fn set_entry_text(&mut self, entry_text: impl Into<String>) -> &mut Self {
         self.entry_text = Some(entry_text.into());
         self
     }
 
     /// Hide the content of the text input, as for a password input.
-    pub fn set_hide_text(mut self) -> Self {
+    pub fn with_hide_text(mut self) -> Self {
         self.hide_text = true;
         self
     }
+
+    /// The former name of `with_hide_text`.
+    #[deprecated(since = "0.3.7", note = "renamed to `with_hide_text`")]
+    pub fn set_hide_text(self) -> Self {
+        self.with_hide_text()
+    }
+
+    /// Report a submission that is empty or only whitespace as `Affirmed { content: None }`,
+    /// as when the user confirms without typing, instead of as an empty answer.
+    pub fn with_empty_as_none(mut self) -> Self {
+        self.empty_as_none = true;
+        self
+    }
+
+    /// Keep the leading and trailing whitespace the user typed, instead of trimming the
+    /// submission. Whitespace-only submissions still count as empty with `with_empty_as_none`.
+    pub fn with_preserve_whitespace(mut self) -> Self {
+        self.preserve_whitespace = true;
+        self
+    }
+
+    /// Choose how an answer matching the prefilled text is reported. See [EntryFocus].
+    pub fn with_focus(mut self, focus: EntryFocus) -> Self {
+        self.set_focus(focus);
+        self
+    }
+
+    /// Choose how an answer matching the prefilled text is reported, in place.
+    pub fn set_focus(&mut self, focus: EntryFocus) -> &mut Self {
+        self.focus = focus;
+        self
+    }
+}
+
+/// Controls the intended initial focus of an [Entry] dialog. Zenity always focuses the text
+/// field, so this can't move the keyboard focus itself; instead it determines whether submitting
+/// the prefilled text unchanged is reported as accepting the default.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(::serde::Deserialize))]
+pub enum EntryFocus {
+    /// Focus the text field. Answers are never marked as an accepted default.
+    #[default]
+    Field,
+    /// Treat the dialog as if the OK button had focus: when the entry is prefilled and the user
+    /// submits it unchanged, the answer is marked with [EntryAnswer::accepted_default].
+    AcceptOnEnterWithPrefill,
+}
+
+/// The text the user submitted through an [Entry] dialog.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct EntryAnswer {
+    /// The submitted text
+    pub text: String,
+    /// Whether the user accepted the prefilled text unchanged. Only ever set when the dialog was
+    /// configured with [EntryFocus::AcceptOnEnterWithPrefill].
+    pub accepted_default: bool,
+}
+
+impl From<EntryAnswer> for String {
+    fn from(value: EntryAnswer) -> Self {
+        value.text
+    }
+}
+
+impl AsRef<str> for EntryAnswer {
+    fn as_ref(&self) -> &str {
+        &self.text
+    }
+}
+
+impl Display for EntryAnswer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        runner::{MockResponse, MockRunner},
+        ZenityDialog, ZenityOutput,
+    };
+
+    fn answer(entry: &Entry, stdout: &str) -> EntryAnswer {
+        entry.parse(&DialogOutput::from_stdout(stdout)).unwrap()
+    }
+
+    #[test]
+    fn unedited_prefill_is_an_accepted_default() {
+        let entry = Entry::new()
+            .with_entry_text("8080")
+            .with_focus(EntryFocus::AcceptOnEnterWithPrefill);
+        assert_eq!(
+            answer(&entry, "8080"),
+            EntryAnswer {
+                text: "8080".to_owned(),
+                accepted_default: true,
+            }
+        );
+    }
+
+    #[test]
+    fn edited_prefill_isnt_an_accepted_default() {
+        let entry = Entry::new()
+            .with_entry_text("8080")
+            .with_focus(EntryFocus::AcceptOnEnterWithPrefill);
+        assert!(!answer(&entry, "8081").accepted_default);
+        assert!(!answer(&entry, "").accepted_default);
+    }
+
+    #[test]
+    fn answers_without_a_prefill_arent_accepted_defaults() {
+        let entry = Entry::new().with_focus(EntryFocus::AcceptOnEnterWithPrefill);
+        assert!(!answer(&entry, "").accepted_default);
+        assert!(!answer(&entry, "8080").accepted_default);
+    }
+
+    /// What the user submitting `stdout` through the dialog is reported as.
+    fn submitted(entry: Entry, stdout: &str) -> ZenityOutput<String> {
+        let runner = MockRunner::new().respond_otherwise(MockResponse::affirmed(stdout));
+        ZenityDialog::new(entry)
+            .show_with_runner(&runner)
+            .unwrap()
+            .map(String::from)
+    }
+
+    #[test]
+    fn empty_submissions_can_be_reported_as_missing() {
+        let entry = Entry::new().with_empty_as_none();
+        assert_eq!(
+            submitted(entry.clone(), ""),
+            ZenityOutput::Affirmed { content: None }
+        );
+        assert_eq!(
+            submitted(entry.clone(), "   \n"),
+            ZenityOutput::Affirmed { content: None }
+        );
+        assert_eq!(
+            submitted(entry, " Ada \n"),
+            ZenityOutput::Affirmed {
+                content: Some("Ada".to_owned()),
+            }
+        );
+    }
+
+    #[test]
+    fn empty_submissions_are_empty_answers_by_default() {
+        assert_eq!(
+            submitted(Entry::new(), ""),
+            ZenityOutput::Affirmed {
+                content: Some(String::new()),
+            }
+        );
+        assert_eq!(
+            submitted(Entry::new(), "   \n"),
+            ZenityOutput::Affirmed {
+                content: Some(String::new()),
+            }
+        );
+    }
+
+    #[test]
+    fn whitespace_can_be_preserved() {
+        assert_eq!(
+            submitted(Entry::new().with_preserve_whitespace(), "  padded  \n"),
+            ZenityOutput::Affirmed {
+                content: Some("  padded  ".to_owned()),
+            }
+        );
+    }
+
+    #[test]
+    fn preserved_whitespace_only_submissions_are_still_empty() {
+        let entry = Entry::new().with_preserve_whitespace().with_empty_as_none();
+        assert_eq!(
+            submitted(entry.clone(), "   \n"),
+            ZenityOutput::Affirmed { content: None }
+        );
+        assert_eq!(
+            submitted(entry, "  padded  \n"),
+            ZenityOutput::Affirmed {
+                content: Some("  padded  ".to_owned()),
+            }
+        );
+    }
+
+    #[test]
+    fn field_focus_never_marks_an_accepted_default() {
+        let entry = Entry::new().with_entry_text("8080");
+        assert!(!answer(&entry, "8080").accepted_default);
+    }
+
+    #[test]
+    fn answers_convert_into_their_text() {
+        let text: String = answer(&Entry::new(), "Alice").into();
+        assert_eq!(text, "Alice");
+    }
 }
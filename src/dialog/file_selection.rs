@@ -0,0 +1,205 @@
+use super::{application::ToArgVector, DialogOutput, ZenityApplication};
+use std::{
+    collections::BTreeMap,
+    ffi::OsString,
+    fmt::Display,
+    path::{Path, PathBuf},
+};
+
+/// Configuration for a dialog that lets the user select files or directories, to open or to
+/// save. The selection is returned as paths, one for each selected file.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(::serde::Deserialize),
+    serde(default, deny_unknown_fields)
+)]
+pub struct FileSelection {
+    /// The file or directory selected when the dialog opens. A path ending in `/` opens the
+    /// directory without selecting a file.
+    pub filename: Option<PathBuf>,
+    /// Allow selecting multiple files
+    pub multiple: bool,
+    /// Select directories instead of files
+    pub directory: bool,
+    /// Select a file to save to, which may not exist yet
+    pub save: bool,
+    /// The filters the user can choose from to limit the files shown
+    pub file_filters: Vec<FileFilter>,
+}
+
+impl ZenityApplication for FileSelection {
+    type Return = Vec<PathBuf>;
+
+    fn parse(&self, output: &DialogOutput) -> Result<Self::Return, crate::Error> {
+        let stdout = output.stdout();
+        Ok(stdout
+            .split(Self::SEPARATOR)
+            .filter(|path| !path.is_empty())
+            .map(PathBuf::from)
+            .collect())
+    }
+
+    fn default_title() -> Option<&'static str> {
+        Some("Select a file")
+    }
+
+    /// Paths may begin or end with whitespace.
+    fn trim_output(&self) -> bool {
+        false
+    }
+
+    fn field_map(&self) -> BTreeMap<&'static str, String> {
+        let mut fields = BTreeMap::new();
+        if let Some(ref filename) = self.filename {
+            fields.insert("filename", filename.display().to_string());
+        }
+
+        if self.multiple {
+            fields.insert("multiple", true.to_string());
+        }
+
+        if self.directory {
+            fields.insert("directory", true.to_string());
+        }
+
+        if self.save {
+            fields.insert("save", true.to_string());
+        }
+
+        if !self.file_filters.is_empty() {
+            fields.insert("file_filters", format!("{:?}", self.file_filters));
+        }
+
+        fields
+    }
+
+    /// Keeps a trailing `/`, which opens a directory without selecting a file.
+    fn resolve_relative_paths(&mut self, base: &Path) {
+        if let Some(ref mut filename) = self.filename {
+            if filename.is_relative() {
+                *filename = base.join(&*filename);
+            }
+        }
+    }
+}
+
+impl ToArgVector for FileSelection {
+    fn to_argv(&self) -> Vec<OsString> {
+        let mut args = vec![OsString::from("--file-selection")];
+        if let Some(ref filename) = self.filename {
+            let mut arg = OsString::from("--filename=");
+            arg.push(filename);
+            args.push(arg);
+        }
+
+        if self.multiple {
+            args.push("--multiple".into());
+        }
+
+        if self.directory {
+            args.push("--directory".into());
+        }
+
+        if self.save {
+            args.push("--save".into());
+        }
+
+        args.push(format!("--separator={}", Self::SEPARATOR).into());
+
+        for filter in &self.file_filters {
+            args.push(format!("--file-filter={filter}").into());
+        }
+
+        args
+    }
+}
+
+impl FileSelection {
+    /// Separates the selected files in Zenity's output. File names rarely contain it, unlike
+    /// Zenity's default separator `|`.
+    const SEPARATOR: char = '\n';
+
+    /// Create a default configuration, which selects a single file to open.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Select the file or directory when the dialog opens.
+    pub fn with_filename(mut self, filename: impl AsRef<Path>) -> Self {
+        self.set_filename(filename);
+        self
+    }
+
+    /// Select the file or directory when the dialog opens, in place.
+    pub fn set_filename(&mut self, filename: impl AsRef<Path>) -> &mut Self {
+        self.filename = Some(filename.as_ref().to_path_buf());
+        self
+    }
+
+    /// Offer the filter to limit the files shown. The first filter is applied initially.
+    pub fn with_file_filter(mut self, filter: FileFilter) -> Self {
+        self.push_file_filter(filter);
+        self
+    }
+
+    /// Offer the filter, in place.
+    pub fn push_file_filter(&mut self, filter: FileFilter) -> &mut Self {
+        self.file_filters.push(filter);
+        self
+    }
+
+    /// Allow selecting multiple files.
+    pub fn with_multiple(mut self) -> Self {
+        self.multiple = true;
+        self
+    }
+
+    /// Select directories instead of files.
+    pub fn with_directory(mut self) -> Self {
+        self.directory = true;
+        self
+    }
+
+    /// Select a file to save to, which may not exist yet.
+    pub fn with_save(mut self) -> Self {
+        self.save = true;
+        self
+    }
+}
+
+/// A named set of shell-style patterns limiting the files a [FileSelection] shows.
+///
+/// ```
+/// # use zenity_dialog::dialog::FileFilter;
+/// let filter = FileFilter::new("Images", ["*.png", "*.jpg"]);
+/// assert_eq!(filter.to_string(), "Images | *.png *.jpg");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(::serde::Deserialize))]
+pub struct FileFilter {
+    /// The name shown to the user
+    pub name: String,
+    /// The patterns of the file names shown, as in `*.png`
+    pub patterns: Vec<String>,
+}
+
+impl FileFilter {
+    /// A filter showing the files matching any of the patterns.
+    pub fn new(
+        name: impl Into<String>,
+        patterns: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            patterns: patterns.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// The filter as passed to Zenity's `--file-filter`.
+impl Display for FileFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} | {}", self.name, self.patterns.join(" "))
+    }
+}
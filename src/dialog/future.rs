@@ -0,0 +1,188 @@
+use super::{Prepared, ShownDialog, ZenityApplication, ZenityDialog, ZenityDialogExtButton};
+use crate::{backend::ZenityBackend, signals::Tracked, ZenityOutput, ZenityOutputExtButton};
+use std::{
+    ffi::OsString,
+    future::Future,
+    io,
+    path::PathBuf,
+    pin::Pin,
+    process::{Command, Output, Stdio},
+    task::{Context, Poll},
+};
+
+/// The output of a spawned backend, collected by an async runtime.
+pub(crate) type OutputFuture = Pin<Box<dyn Future<Output = io::Result<Output>> + Send>>;
+
+/// A spawned backend: its output and process id.
+pub(crate) type Spawned = (OutputFuture, Option<u32>);
+
+/// Resolves to the outcome of a dialog shown asynchronously. The backend is spawned by the
+/// runtime-specific `spawn` function, and its output is classified exactly as in the blocking
+/// path.
+pub(crate) struct ShowFuture<'a, T>
+where
+    T: ZenityApplication,
+{
+    dialog: &'a ZenityDialog<T>,
+    backend: ZenityBackend,
+    state: State<T::Return>,
+}
+
+enum State<R> {
+    Running {
+        output: OutputFuture,
+        /// Keeps the backend registered for signal forwarding until it's done.
+        _tracked: Option<Tracked>,
+    },
+    Done(Option<crate::Result<ShownDialog<ZenityOutput<R>>>>),
+}
+
+// No field is ever pinned in place: the output future is pinned on the heap.
+impl<T> Unpin for ShowFuture<'_, T> where T: ZenityApplication {}
+
+impl<'a, T> ShowFuture<'a, T>
+where
+    T: ZenityApplication,
+{
+    /// Prepare the dialog with the provided arguments and spawn the backend with `spawn`, which
+    /// receives a command with piped output. Errors are reported when the future is polled.
+    pub(crate) fn new(
+        dialog: &'a ZenityDialog<T>,
+        args: Vec<OsString>,
+        spawn: impl FnOnce(Command) -> io::Result<Spawned>,
+    ) -> Self {
+        let backend = dialog.backend();
+        let prepared = dialog
+            .validated()
+            .and_then(|()| dialog.prepare(&backend, args));
+        let state = match prepared {
+            Ok(Prepared::Done(output)) => State::Done(Some(Ok(ShownDialog { output, raw: None }))),
+            Ok(Prepared::Spawn(mut command)) => {
+                command
+                    .stdin(Stdio::null())
+                    .stdout(Stdio::piped())
+                    .stderr(Stdio::piped());
+                // The command is consumed by `spawn`, so keep what's needed to report failures.
+                let program = PathBuf::from(command.get_program());
+                let args = command.get_args().map(ToOwned::to_owned).collect();
+                match spawn(command) {
+                    Ok((output, pid)) => State::Running {
+                        output,
+                        _tracked: pid.map(Tracked::new),
+                    },
+                    Err(err) => State::Done(Some(Err(crate::Error::spawn(program, args, err)))),
+                }
+            }
+            Err(err) => State::Done(Some(Err(err))),
+        };
+
+        Self {
+            dialog,
+            backend,
+            state,
+        }
+    }
+}
+
+impl<T> ShowFuture<'_, T>
+where
+    T: ZenityApplication,
+{
+    /// Poll the backend, keeping its raw output alongside the classified one.
+    fn poll_detailed(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<crate::Result<ShownDialog<ZenityOutput<T::Return>>>> {
+        match self.state {
+            State::Running { ref mut output, .. } => match output.as_mut().poll(cx) {
+                Poll::Ready(output) => {
+                    // Stop tracking the reaped backend right away.
+                    self.state = State::Done(None);
+                    Poll::Ready(
+                        output
+                            .map_err(crate::Error::UnexpectedIoError)
+                            .and_then(|output| self.dialog.finish_detailed(&self.backend, output)),
+                    )
+                }
+                Poll::Pending => Poll::Pending,
+            },
+            State::Done(ref mut result) => {
+                Poll::Ready(result.take().expect("ShowFuture polled after completion"))
+            }
+        }
+    }
+}
+
+impl<T> Future for ShowFuture<'_, T>
+where
+    T: ZenityApplication,
+{
+    type Output = crate::Result<ZenityOutput<T::Return>>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.get_mut()
+            .poll_detailed(cx)
+            .map(|shown| shown.map(|shown| shown.output))
+    }
+}
+
+/// Resolves to the outcome of a dialog with an extra button shown asynchronously.
+pub(crate) struct ShowFutureExtButton<'a, T>
+where
+    T: ZenityApplication,
+{
+    dialog: &'a ZenityDialogExtButton<T>,
+    inner: ShowFuture<'a, T>,
+}
+
+impl<'a, T> ShowFutureExtButton<'a, T>
+where
+    T: ZenityApplication,
+{
+    pub(crate) fn new(
+        dialog: &'a ZenityDialogExtButton<T>,
+        spawn: impl FnOnce(Command) -> io::Result<Spawned>,
+    ) -> Self {
+        let inner = ShowFuture::new(&dialog.inner, dialog.to_args(), spawn);
+
+        Self { dialog, inner }
+    }
+}
+
+impl<T> Future for ShowFutureExtButton<'_, T>
+where
+    T: ZenityApplication,
+{
+    type Output = crate::Result<ZenityOutputExtButton<T::Return>>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        this.inner
+            .poll_detailed(cx)
+            .map(|shown| shown.map(|shown| this.dialog.detect_ext_button(shown)))
+    }
+}
+
+/// Spawn the command on tokio, killing the backend if the future is dropped.
+#[cfg(feature = "async-tokio")]
+pub(crate) fn spawn_tokio(command: Command) -> io::Result<Spawned> {
+    let child = tokio::process::Command::from(command)
+        .kill_on_drop(true)
+        .spawn()?;
+    let pid = child.id();
+    Ok((Box::pin(child.wait_with_output()), pid))
+}
+
+/// Spawn the command with `async-process`, killing the backend if the future is dropped.
+#[cfg(feature = "async-io")]
+pub(crate) fn spawn_async_io(command: Command) -> io::Result<Spawned> {
+    // async-process doesn't carry over the stdio configuration of the converted command.
+    let child = async_process::Command::from(command)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()?;
+    let pid = child.id();
+    Ok((Box::pin(child.output()), Some(pid)))
+}
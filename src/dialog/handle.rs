@@ -0,0 +1,329 @@
+use super::{Prepared, ZenityApplication, ZenityDialog};
+use crate::{backend::ZenityBackend, signals::Tracked, CancelFlag, ZenityOutput};
+use std::{
+    io::{self, Read},
+    process::{Child, Output, Stdio},
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+/// How often a running dialog is checked for having closed.
+const POLL: Duration = Duration::from_millis(20);
+
+/// A dialog that is being shown in the background. See [ZenityDialog::spawn].
+///
+/// Dropping the handle closes the dialog, unless it was detached with [DialogHandle::detach].
+pub struct DialogHandle<T>
+where
+    T: ZenityApplication,
+{
+    dialog: ZenityDialog<T>,
+    backend: ZenityBackend,
+    state: State<T::Return>,
+}
+
+enum State<R> {
+    /// The backend is running. Its output is read on separate threads, so that a dialog with
+    /// large output can't block on a full pipe.
+    Running {
+        child: Child,
+        stdout: JoinHandle<io::Result<Vec<u8>>>,
+        stderr: JoinHandle<io::Result<Vec<u8>>>,
+        tracked: Tracked,
+    },
+    /// The backend exited on its own.
+    Exited(Output),
+    /// The dialog was handled without spawning the backend.
+    Handled(ZenityOutput<R>),
+    /// The backend was killed with [DialogHandle::kill].
+    Dismissed,
+}
+
+impl<T> ZenityDialog<T>
+where
+    T: ZenityApplication,
+{
+    /// Show the dialog in the background, returning a handle to wait for the user's response or
+    /// to close the dialog programmatically.
+    pub fn spawn(&self) -> crate::Result<DialogHandle<T>> {
+        self.validated()?;
+        let backend = self.backend();
+        let state = match self.prepare(&backend, self.to_args())? {
+            Prepared::Done(output) => State::Handled(output),
+            Prepared::Spawn(mut command) => {
+                let mut child = command
+                    .stdin(Stdio::null())
+                    .stdout(Stdio::piped())
+                    .stderr(Stdio::piped())
+                    .spawn()
+                    .map_err(|err| crate::Error::spawn_command(&command, err))?;
+
+                State::Running {
+                    stdout: read_to_end(child.stdout.take()),
+                    stderr: read_to_end(child.stderr.take()),
+                    tracked: Tracked::new(child.id()),
+                    child,
+                }
+            }
+        };
+
+        Ok(DialogHandle {
+            dialog: self.clone(),
+            backend,
+            state,
+        })
+    }
+}
+
+impl<T> ZenityDialog<T>
+where
+    T: ZenityApplication,
+{
+    /// Render the dialog and wait for user response, closing it with
+    /// [ZenityOutput::Dismissed] as soon as the flag is cancelled. If the user responds before
+    /// the flag is cancelled, the response wins. A flag that is already cancelled dismisses the
+    /// dialog without showing it.
+    pub fn show_with_cancel(&self, flag: &CancelFlag) -> crate::Result<ZenityOutput<T::Return>> {
+        if flag.is_cancelled() {
+            return Ok(ZenityOutput::Dismissed);
+        }
+
+        let mut handle = self.spawn()?;
+        loop {
+            if let Some(output) = handle.try_wait()? {
+                return Ok(output);
+            }
+
+            if flag.wait_timeout(POLL) {
+                handle.kill()?;
+                return handle.wait();
+            }
+        }
+    }
+}
+
+impl<T> DialogHandle<T>
+where
+    T: ZenityApplication,
+{
+    /// Wait for the dialog to close and return the user's response, or
+    /// [ZenityOutput::Dismissed] if it was killed.
+    pub fn wait(&mut self) -> crate::Result<ZenityOutput<T::Return>> {
+        self.collect()?;
+        self.output()
+    }
+
+    /// Return the user's response if the dialog has closed, or [None] if it's still open.
+    pub fn try_wait(&mut self) -> crate::Result<Option<ZenityOutput<T::Return>>> {
+        if let State::Running { ref mut child, .. } = self.state {
+            match child.try_wait().map_err(crate::Error::UnexpectedIoError)? {
+                Some(_) => self.collect()?,
+                None => return Ok(None),
+            }
+        }
+
+        self.output().map(Some)
+    }
+
+    /// Close the dialog. Waiting on the handle afterwards returns [ZenityOutput::Dismissed],
+    /// unless the dialog had already closed on its own, in which case the user's response is
+    /// kept. Killing a closed dialog does nothing.
+    pub fn kill(&mut self) -> crate::Result<()> {
+        if let State::Running { ref mut child, .. } = self.state {
+            if child
+                .try_wait()
+                .map_err(crate::Error::UnexpectedIoError)?
+                .is_some()
+            {
+                return self.collect();
+            }
+
+            child.kill().map_err(crate::Error::UnexpectedIoError)?;
+            child.wait().map_err(crate::Error::UnexpectedIoError)?;
+            self.state = State::Dismissed;
+        }
+
+        Ok(())
+    }
+
+    /// Let the dialog stay open after the handle is gone, as a fire-and-forget window. The
+    /// user's response is discarded.
+    pub fn detach(mut self) {
+        let state = std::mem::replace(&mut self.state, State::Dismissed);
+        if let State::Running { mut child, .. } = state {
+            // Reap the dialog in the background so it doesn't linger as a zombie.
+            thread::spawn(move || child.wait());
+        }
+    }
+
+    /// Wait for the backend to exit, if it's running, and collect its output.
+    fn collect(&mut self) -> crate::Result<()> {
+        let state = std::mem::replace(&mut self.state, State::Dismissed);
+        if let State::Running {
+            mut child,
+            stdout,
+            stderr,
+            tracked,
+        } = state
+        {
+            let status = child.wait().map_err(crate::Error::UnexpectedIoError)?;
+            drop(tracked);
+            self.state = State::Exited(Output {
+                status,
+                stdout: join(stdout)?,
+                stderr: join(stderr)?,
+            });
+        } else {
+            self.state = state;
+        }
+
+        Ok(())
+    }
+
+    /// The outcome of the closed dialog.
+    fn output(&self) -> crate::Result<ZenityOutput<T::Return>> {
+        match self.state {
+            State::Running { .. } => unreachable!("the dialog is collected before its output"),
+            State::Exited(ref output) => self.dialog.finish(&self.backend, output.clone()),
+            State::Handled(ref output) => Ok(output.clone()),
+            State::Dismissed => Ok(ZenityOutput::Dismissed),
+        }
+    }
+}
+
+impl<T> Drop for DialogHandle<T>
+where
+    T: ZenityApplication,
+{
+    fn drop(&mut self) {
+        // There's no one left to report a failure to.
+        let _ = self.kill();
+    }
+}
+
+/// Wait for the child to exit and collect its output, killing it if it's still running at the
+/// deadline. Returns [None] if the child was killed.
+pub(super) fn output_until(mut child: Child, deadline: Instant) -> crate::Result<Option<Output>> {
+    let stdout = read_to_end(child.stdout.take());
+    let stderr = read_to_end(child.stderr.take());
+
+    let status = loop {
+        if let Some(status) = child.try_wait().map_err(crate::Error::UnexpectedIoError)? {
+            break status;
+        }
+
+        let now = Instant::now();
+        if now >= deadline {
+            child.kill().map_err(crate::Error::UnexpectedIoError)?;
+            child.wait().map_err(crate::Error::UnexpectedIoError)?;
+            return Ok(None);
+        }
+
+        thread::sleep(POLL.min(deadline - now));
+    };
+
+    Ok(Some(Output {
+        status,
+        stdout: join(stdout)?,
+        stderr: join(stderr)?,
+    }))
+}
+
+/// Read the pipe to its end on a separate thread.
+fn read_to_end(pipe: Option<impl Read + Send + 'static>) -> JoinHandle<io::Result<Vec<u8>>> {
+    thread::spawn(move || {
+        let mut buf = Vec::new();
+        if let Some(mut pipe) = pipe {
+            pipe.read_to_end(&mut buf)?;
+        }
+        Ok(buf)
+    })
+}
+
+fn join(reader: JoinHandle<io::Result<Vec<u8>>>) -> crate::Result<Vec<u8>> {
+    reader
+        .join()
+        .expect("the reader thread doesn't panic")
+        .map_err(crate::Error::UnexpectedIoError)
+}
+
+#[cfg(all(test, unix, feature = "entry"))]
+mod tests {
+    use crate::{
+        dialog::Entry, harness::TestZenity, settings::lock_for_test, ZenityDialog, ZenityOutput,
+    };
+    use std::time::{Duration, Instant};
+
+    fn entry(zenity: &TestZenity) -> ZenityDialog<Entry> {
+        ZenityDialog::new(Entry::new())
+            .with_program(zenity.program())
+            .allow_headless()
+    }
+
+    #[test]
+    fn waits_for_the_user_to_close_the_dialog() {
+        let _lock = lock_for_test();
+        let zenity = TestZenity::affirms_with("Ada\n").unwrap();
+        zenity.set_delay(Duration::from_millis(200)).unwrap();
+
+        let mut handle = entry(&zenity).spawn().unwrap();
+        assert!(handle.try_wait().unwrap().is_none());
+        assert_eq!(
+            handle.wait().unwrap().map(String::from),
+            ZenityOutput::Affirmed {
+                content: Some("Ada".to_owned()),
+            }
+        );
+
+        // The response is kept once the dialog has closed.
+        handle.kill().unwrap();
+        assert!(matches!(
+            handle.try_wait().unwrap(),
+            Some(ZenityOutput::Affirmed { .. })
+        ));
+    }
+
+    #[test]
+    fn large_output_doesnt_block_the_dialog() {
+        let _lock = lock_for_test();
+        let text = "x".repeat(1 << 20);
+        let zenity = TestZenity::affirms_with(&text).unwrap();
+        zenity.set_stderr("y".repeat(1 << 20)).unwrap();
+
+        let mut handle = entry(&zenity).spawn().unwrap();
+        let deadline = Instant::now() + Duration::from_secs(10);
+        let output = loop {
+            if let Some(output) = handle.try_wait().unwrap() {
+                break output;
+            }
+            assert!(Instant::now() < deadline, "the dialog never closed");
+            std::thread::sleep(Duration::from_millis(10));
+        };
+
+        assert_eq!(
+            output.map(String::from),
+            ZenityOutput::Affirmed {
+                content: Some(text),
+            }
+        );
+    }
+
+    #[test]
+    fn killing_the_dialog_dismisses_it() {
+        let _lock = lock_for_test();
+        let zenity = TestZenity::affirms_with("Ada\n").unwrap();
+        zenity.set_delay(Duration::from_secs(5)).unwrap();
+
+        let started = Instant::now();
+        let mut handle = entry(&zenity).spawn().unwrap();
+        assert!(handle.try_wait().unwrap().is_none());
+
+        handle.kill().unwrap();
+        assert_eq!(handle.wait().unwrap(), ZenityOutput::Dismissed);
+        assert!(started.elapsed() < Duration::from_secs(5));
+
+        // Killing again does nothing.
+        handle.kill().unwrap();
+        assert_eq!(handle.try_wait().unwrap(), Some(ZenityOutput::Dismissed));
+    }
+}
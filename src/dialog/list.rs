@@ -0,0 +1,295 @@
+use super::{application::ToArgVector, DialogOutput, ZenityApplication};
+use std::{collections::BTreeMap, ffi::OsString};
+
+/// Configuration for a dialog that lets the user select rows of a table. The selection is
+/// returned as the printed column of each selected row, by default the first one.
+///
+/// In checklist and radiolist mode, the first column holds the check boxes or radio buttons,
+/// and each row starts with `TRUE` or `FALSE` to set its initial state.
+///
+/// ```
+/// # use zenity_dialog::{dialog::List, ZenityDialog};
+/// let list = List::new()
+///     .with_column("Fruit")
+///     .with_row(["Apple"])
+///     .with_row(["Pear"])
+///     .with_hide_header();
+/// assert_eq!(
+///     ZenityDialog::new(list).to_command_string(),
+///     "zenity --list --column=Fruit --hide-header '--separator=\n' Apple Pear '--title=Select items'"
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(::serde::Deserialize),
+    serde(default, deny_unknown_fields)
+)]
+pub struct List {
+    /// The body text
+    pub text: Option<String>,
+    /// The column headers
+    pub columns: Vec<String>,
+    /// The rows, with a cell for each column
+    pub rows: Vec<Vec<String>>,
+    /// Show check boxes in the first column, allowing multiple rows to be selected
+    pub checklist: bool,
+    /// Show radio buttons in the first column
+    pub radiolist: bool,
+    /// Allow selecting multiple rows
+    pub multiple: bool,
+    /// Hide the column headers
+    pub hide_header: bool,
+    /// The column, starting at 1, whose cell is printed for each selected row
+    pub print_column: Option<usize>,
+    /// The column, starting at 1, that isn't shown
+    pub hide_column: Option<usize>,
+}
+
+impl List {
+    /// Separates the selected rows in Zenity's output, unlike Zenity's default separator `|`,
+    /// which cells may contain.
+    const SEPARATOR: char = '\n';
+
+    /// Create a default configuration.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Set the body text.
+    pub fn with_text(mut self, text: impl Into<String>) -> Self {
+        self.set_text(text);
+        self
+    }
+
+    /// Set the body text, in place.
+    pub fn set_text(&mut self, text: impl Into<String>) -> &mut Self {
+        self.text = Some(text.into());
+        self
+    }
+
+    /// Add a column with the header.
+    pub fn with_column(mut self, column: impl Into<String>) -> Self {
+        self.push_column(column);
+        self
+    }
+
+    /// Add a column with the header, in place.
+    pub fn push_column(&mut self, column: impl Into<String>) -> &mut Self {
+        self.columns.push(column.into());
+        self
+    }
+
+    /// Add a row with a cell for each column.
+    pub fn with_row(mut self, row: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.push_row(row);
+        self
+    }
+
+    /// Add a row with a cell for each column, in place.
+    pub fn push_row(&mut self, row: impl IntoIterator<Item = impl Into<String>>) -> &mut Self {
+        self.rows.push(row.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Print the column, starting at 1, for each selected row.
+    pub fn with_print_column(mut self, column: usize) -> Self {
+        self.set_print_column(column);
+        self
+    }
+
+    /// Print the column for each selected row, in place.
+    pub fn set_print_column(&mut self, column: usize) -> &mut Self {
+        self.print_column = Some(column);
+        self
+    }
+
+    /// Hide the column, starting at 1, for example to carry an identifier for each row.
+    pub fn with_hide_column(mut self, column: usize) -> Self {
+        self.set_hide_column(column);
+        self
+    }
+
+    /// Hide the column, in place.
+    pub fn set_hide_column(&mut self, column: usize) -> &mut Self {
+        self.hide_column = Some(column);
+        self
+    }
+
+    /// Show check boxes in the first column.
+    pub fn with_checklist(mut self) -> Self {
+        self.checklist = true;
+        self
+    }
+
+    /// Show radio buttons in the first column.
+    pub fn with_radiolist(mut self) -> Self {
+        self.radiolist = true;
+        self
+    }
+
+    /// Allow selecting multiple rows.
+    pub fn with_multiple(mut self) -> Self {
+        self.multiple = true;
+        self
+    }
+
+    /// Hide the column headers.
+    pub fn with_hide_header(mut self) -> Self {
+        self.hide_header = true;
+        self
+    }
+}
+
+impl ZenityApplication for List {
+    type Return = Vec<String>;
+
+    fn parse(&self, output: &DialogOutput) -> Result<Self::Return, crate::Error> {
+        let stdout = output.stdout();
+        Ok(stdout
+            .split(Self::SEPARATOR)
+            .filter(|row| !row.is_empty())
+            .map(str::to_owned)
+            .collect())
+    }
+
+    fn default_title() -> Option<&'static str> {
+        Some("Select items")
+    }
+
+    /// Cells may begin or end with whitespace.
+    fn trim_output(&self) -> bool {
+        false
+    }
+
+    fn field_map(&self) -> BTreeMap<&'static str, String> {
+        let mut fields = BTreeMap::new();
+        if let Some(ref text) = self.text {
+            fields.insert("text", text.to_string());
+        }
+
+        if !self.columns.is_empty() {
+            fields.insert("columns", format!("{:?}", self.columns));
+        }
+
+        if !self.rows.is_empty() {
+            fields.insert("rows", format!("{:?}", self.rows));
+        }
+
+        if self.checklist {
+            fields.insert("checklist", true.to_string());
+        }
+
+        if self.radiolist {
+            fields.insert("radiolist", true.to_string());
+        }
+
+        if self.multiple {
+            fields.insert("multiple", true.to_string());
+        }
+
+        if self.hide_header {
+            fields.insert("hide_header", true.to_string());
+        }
+
+        if let Some(print_column) = self.print_column {
+            fields.insert("print_column", print_column.to_string());
+        }
+
+        if let Some(hide_column) = self.hide_column {
+            fields.insert("hide_column", hide_column.to_string());
+        }
+
+        fields
+    }
+
+    fn validate(&self) -> Vec<crate::ValidationError> {
+        let mut errors = Vec::new();
+        if self.columns.is_empty() {
+            errors.push(crate::ValidationError::new(
+                "columns",
+                "must contain at least one column",
+            ));
+        }
+
+        if let Some(row) = self
+            .rows
+            .iter()
+            .position(|row| row.len() != self.columns.len())
+        {
+            errors.push(crate::ValidationError::new(
+                "rows",
+                format!(
+                    "row {row} has {} cells, but the list has {} columns",
+                    self.rows[row].len(),
+                    self.columns.len()
+                ),
+            ));
+        }
+
+        for (field, column) in [
+            ("print_column", self.print_column),
+            ("hide_column", self.hide_column),
+        ] {
+            if column.is_some_and(|column| column == 0 || column > self.columns.len()) {
+                errors.push(crate::ValidationError::new(
+                    field,
+                    format!("must be between 1 and {}", self.columns.len()),
+                ));
+            }
+        }
+
+        if self.checklist && self.radiolist {
+            errors.push(crate::ValidationError::new(
+                "radiolist",
+                "can't be combined with checklist",
+            ));
+        }
+
+        errors
+    }
+}
+
+impl_has_text!(List);
+
+impl ToArgVector for List {
+    fn to_argv(&self) -> Vec<OsString> {
+        let mut args = vec![OsString::from("--list")];
+        if let Some(ref text) = self.text {
+            args.push(format!("--text={text}").into())
+        };
+
+        for column in &self.columns {
+            args.push(format!("--column={column}").into());
+        }
+
+        if self.checklist {
+            args.push("--checklist".into());
+        }
+
+        if self.radiolist {
+            args.push("--radiolist".into());
+        }
+
+        if self.multiple {
+            args.push("--multiple".into());
+        }
+
+        if self.hide_header {
+            args.push("--hide-header".into());
+        }
+
+        if let Some(print_column) = self.print_column {
+            args.push(format!("--print-column={print_column}").into());
+        }
+
+        if let Some(hide_column) = self.hide_column {
+            args.push(format!("--hide-column={hide_column}").into());
+        }
+
+        args.push(format!("--separator={}", Self::SEPARATOR).into());
+        args.extend(self.rows.iter().flatten().map(OsString::from));
+
+        args
+    }
+}
@@ -0,0 +1,268 @@
+/// Defines a message application, such as [super::Info], [super::Error], and [super::Warning],
+/// which show text with a single button and differ only in their Zenity option and default
+/// title. Defining them all here keeps their options from drifting apart.
+macro_rules! message_application {
+    (
+        $(#[$attr:meta])*
+        $name:ident, $option:literal, $title:literal
+    ) => {
+        $(#[$attr])*
+        #[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+        #[cfg_attr(
+            feature = "serde",
+            derive(::serde::Deserialize),
+            serde(default, deny_unknown_fields)
+        )]
+        pub struct $name {
+            /// The body text
+            pub text: Option<String>,
+            /// Custom label for ok button
+            pub ok_label: Option<String>,
+            /// Prevent word wrap
+            pub no_wrap: bool,
+            /// Disable markup support
+            pub no_markup: bool,
+            /// Show ellipses for texts that are too long to display
+            pub ellipsize: bool,
+            /// Escape markup in the body text, so that it's shown as typed
+            pub escape_text: bool,
+        }
+
+        impl $crate::dialog::ZenityApplication for $name {
+            type Return = String;
+
+            fn parse(
+                &self,
+                output: &$crate::dialog::DialogOutput,
+            ) -> Result<Self::Return, $crate::Error> {
+                Ok(output.stdout().to_owned())
+            }
+
+            fn default_title() -> Option<&'static str> {
+                Some($title)
+            }
+
+            fn field_map(&self) -> ::std::collections::BTreeMap<&'static str, String> {
+                let mut fields = ::std::collections::BTreeMap::new();
+                if let Some(ref text) = self.text {
+                    fields.insert("text", text.to_string());
+                }
+
+                if let Some(ref ok_label) = self.ok_label {
+                    fields.insert("ok_label", ok_label.to_string());
+                }
+
+                if self.no_wrap {
+                    fields.insert("no_wrap", true.to_string());
+                }
+
+                if self.no_markup {
+                    fields.insert("no_markup", true.to_string());
+                }
+
+                if self.ellipsize {
+                    fields.insert("ellipsize", true.to_string());
+                }
+
+                if self.escape_text {
+                    fields.insert("escape_text", true.to_string());
+                }
+
+                fields
+            }
+
+            #[cfg(feature = "tty-fallback")]
+            fn tty_prompt(&self) -> Option<$crate::TtyPrompt> {
+                Some($crate::TtyPrompt::Message {
+                    text: self
+                        .text
+                        .as_deref()
+                        .map(|text| {
+                            let markup = !self.no_markup && !self.escape_text;
+                            $crate::tty::TtyPrompt::plain(text, markup)
+                        }),
+                })
+            }
+        }
+
+        impl $crate::dialog::ToArgVector for $name {
+            fn to_argv(&self) -> Vec<::std::ffi::OsString> {
+                let mut args = vec![::std::ffi::OsString::from($option)];
+                if let Some(text) = self.argv_text() {
+                    args.push(format!("--text={text}").into())
+                };
+
+                if let Some(ref ok_label) = self.ok_label {
+                    args.push(format!("--ok-label={ok_label}").into())
+                };
+
+                if self.no_wrap {
+                    args.push("--no-wrap".into());
+                }
+
+                if self.no_markup {
+                    args.push("--no-markup".into())
+                }
+
+                if self.ellipsize {
+                    args.push("--ellipsize".into())
+                }
+
+                args
+            }
+        }
+
+        impl $name {
+            /// Create a default configuration.
+            pub fn new() -> Self {
+                Default::default()
+            }
+
+            /// Set the body text.
+            pub fn with_text(mut self, text: impl Into<String>) -> Self {
+                self.set_text(text);
+                self
+            }
+
+            /// Set the body text, in place.
+            pub fn set_text(&mut self, text: impl Into<String>) -> &mut Self {
+                self.text = Some(text.into());
+                self
+            }
+
+            /// Set the body text to the markup, which is passed to Zenity as is, without
+            /// `with_escape_text` escaping its tags.
+            pub fn with_markup(mut self, markup: $crate::Markup) -> Self {
+                self.set_markup(markup);
+                self
+            }
+
+            /// Set the body text to the markup, in place. See `with_markup`.
+            pub fn set_markup(&mut self, markup: $crate::Markup) -> &mut Self {
+                self.text = Some(markup.build());
+                self.escape_text = false;
+                self
+            }
+
+            /// Replace the default ok button label.
+            pub fn with_ok_label(mut self, ok_label: impl Into<String>) -> Self {
+                self.set_ok_label(ok_label);
+                self
+            }
+
+            /// Replace the default ok button label, in place.
+            pub fn set_ok_label(&mut self, ok_label: impl Into<String>) -> &mut Self {
+                self.ok_label = Some(ok_label.into());
+                self
+            }
+
+            /// Prevent text from wrapping.
+            pub fn with_no_wrap(mut self) -> Self {
+                self.no_wrap = true;
+                self
+            }
+
+            /// Disable markup support.
+            pub fn with_no_markup(mut self) -> Self {
+                self.no_markup = true;
+                self
+            }
+
+            /// If text is too long to fit, display an ellipses.
+            pub fn with_ellipsize(mut self) -> Self {
+                self.ellipsize = true;
+                self
+            }
+
+            /// Escape markup in the body text when passing it to Zenity, so that characters
+            /// such as `<` and `&` are shown as typed while markup stays enabled, unlike with
+            /// `with_no_markup`. See [crate::escape_markup].
+            pub fn with_escape_text(mut self) -> Self {
+                self.escape_text = true;
+                self
+            }
+
+            /// The body text as passed to Zenity. Escaping is skipped when markup is disabled,
+            /// as the entities would be shown as typed.
+            fn argv_text(&self) -> Option<::std::borrow::Cow<'_, str>> {
+                let text = self.text.as_deref()?;
+                Some(match self.escape_text && !self.no_markup {
+                    true => $crate::escape_markup(text).into(),
+                    false => text.into(),
+                })
+            }
+        }
+
+        /// The application with the text as its body text.
+        impl From<&str> for $name {
+            fn from(text: &str) -> Self {
+                Self::new().with_text(text)
+            }
+        }
+
+        /// The application with the text as its body text.
+        impl From<String> for $name {
+            fn from(text: String) -> Self {
+                Self::new().with_text(text)
+            }
+        }
+
+        impl_has_text!($name);
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{application::ToArgVector, error::Error, info::Info, warning::Warning};
+    use std::ffi::OsString;
+
+    /// The arguments of the message application with every option set.
+    macro_rules! configured_argv {
+        ($name:ident) => {
+            $name::new()
+                .with_text("Disk <b>full</b>")
+                .with_ok_label("Got it")
+                .with_no_wrap()
+                .with_ellipsize()
+                .with_escape_text()
+                .to_argv()
+        };
+    }
+
+    #[test]
+    fn message_applications_pass_the_same_options() {
+        let info = configured_argv!(Info);
+        assert_eq!(
+            info,
+            [
+                "--info",
+                "--text=Disk &lt;b&gt;full&lt;/b&gt;",
+                "--ok-label=Got it",
+                "--no-wrap",
+                "--ellipsize"
+            ]
+            .map(OsString::from)
+        );
+
+        let error = configured_argv!(Error);
+        let warning = configured_argv!(Warning);
+        assert_eq!(error[0], "--error");
+        assert_eq!(warning[0], "--warning");
+        assert_eq!(error[1..], info[1..]);
+        assert_eq!(warning[1..], info[1..]);
+    }
+
+    #[test]
+    fn message_applications_can_disable_markup() {
+        for argv in [
+            Info::new().with_text("a <b>").with_no_markup().to_argv(),
+            Error::new().with_text("a <b>").with_no_markup().to_argv(),
+            Warning::new().with_text("a <b>").with_no_markup().to_argv(),
+        ] {
+            assert_eq!(
+                argv[1..],
+                ["--text=a <b>", "--no-markup"].map(OsString::from)
+            );
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use super::{application::ToArgVector, DialogOutput, ZenityApplication};
+use std::{collections::BTreeMap, ffi::OsString};
+
+/// Configuration for a notification in the desktop's notification area. Zenity exits once the
+/// notification is shown, printing nothing. The icon and timeout are set on the dialog, with
+/// [crate::ZenityDialog::with_icon] and [crate::ZenityDialog::with_timeout].
+///
+/// With the `notify-fallback` feature, the notification is sent to the desktop's notification
+/// service directly, without Zenity, when Zenity isn't installed or when
+/// [Notification::prefer_native] is set.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(::serde::Deserialize),
+    serde(default, deny_unknown_fields)
+)]
+pub struct Notification {
+    /// The text of the notification
+    pub text: Option<String>,
+    /// Keep the notification icon and read commands from stdin, instead of showing a single
+    /// notification
+    pub listen: bool,
+    /// Send the notification to the desktop's notification service even when Zenity is
+    /// installed. Only has an effect with the `notify-fallback` feature.
+    pub prefer_native: bool,
+}
+
+impl ZenityApplication for Notification {
+    type Return = ();
+
+    fn parse(&self, _: &DialogOutput) -> Result<Self::Return, crate::Error> {
+        Ok(())
+    }
+
+    fn field_map(&self) -> BTreeMap<&'static str, String> {
+        let mut fields = BTreeMap::new();
+        if let Some(ref text) = self.text {
+            fields.insert("text", text.to_string());
+        }
+
+        if self.listen {
+            fields.insert("listen", true.to_string());
+        }
+
+        if self.prefer_native {
+            fields.insert("prefer_native", true.to_string());
+        }
+
+        fields
+    }
+
+    #[cfg(feature = "notify-fallback")]
+    fn native_notification(&self) -> Option<bool> {
+        Some(self.prefer_native)
+    }
+}
+
+impl ToArgVector for Notification {
+    fn to_argv(&self) -> Vec<OsString> {
+        let mut args = vec![OsString::from("--notification")];
+        if let Some(ref text) = self.text {
+            args.push(format!("--text={text}").into());
+        }
+
+        if self.listen {
+            args.push("--listen".into());
+        }
+
+        args
+    }
+}
+
+impl_has_text!(Notification);
+
+impl Notification {
+    /// Create a default configuration.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Set the text of the notification.
+    pub fn with_text(mut self, text: impl Into<String>) -> Self {
+        self.text = Some(text.into());
+        self
+    }
+
+    /// Keep the notification icon and read commands from stdin. Zenity's stdin is closed, so
+    /// this is mostly useful with custom runners. The notification service can't listen, so
+    /// the fallback fails with [crate::Error::UnsupportedByBackend].
+    pub fn with_listen(mut self) -> Self {
+        self.listen = true;
+        self
+    }
+
+    /// Send the notification to the desktop's notification service even when Zenity is
+    /// installed. Only has an effect with the `notify-fallback` feature.
+    pub fn prefer_native(mut self) -> Self {
+        self.prefer_native = true;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn passes_the_text_and_listen_mode() {
+        let notification = Notification::new().with_text("Backup done").with_listen();
+        assert_eq!(
+            notification.to_argv(),
+            ["--notification", "--text=Backup done", "--listen"]
+        );
+    }
+
+    #[test]
+    fn the_native_preference_isnt_passed_to_zenity() {
+        assert_eq!(
+            Notification::new().prefer_native().to_argv(),
+            ["--notification"]
+        );
+    }
+}
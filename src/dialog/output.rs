@@ -0,0 +1,707 @@
+use super::{ZenityOutput, ZenityOutputExtButton};
+use std::process::Output;
+
+/// A dialog's classified output together with what Zenity actually printed. See
+/// [crate::ZenityDialog::show_detailed].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ShownDialog<O> {
+    /// The classified output, as returned by `show`
+    pub output: O,
+    /// What Zenity printed and how it exited, or [None] if the dialog was handled without
+    /// Zenity exiting by itself, as when the terminal fallback was used or the hard timeout
+    /// killed it
+    pub raw: Option<RawOutput>,
+}
+
+/// What Zenity printed and how it exited.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RawOutput {
+    /// The exit code, or [None] if Zenity was terminated by a signal
+    pub exit_code: Option<i32>,
+    /// Everything Zenity printed to stdout, untrimmed. Bytes that aren't valid UTF-8 are
+    /// replaced.
+    pub stdout: String,
+    /// Everything Zenity printed to stderr. Bytes that aren't valid UTF-8 are replaced.
+    pub stderr: String,
+    /// What remains of stderr after removing the noise matched by
+    /// [crate::ZenitySettings::stderr_filter]. Errors and [ZenityOutput::Unknown] report this
+    /// text.
+    pub filtered_stderr: String,
+}
+
+impl From<&Output> for RawOutput {
+    fn from(output: &Output) -> Self {
+        Self {
+            exit_code: output.status.code(),
+            stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
+            stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+            filtered_stderr: crate::stderr::filtered(&output.stderr),
+        }
+    }
+}
+
+/// What a confirmed dialog printed and how it exited, as passed to
+/// [super::ZenityApplication::parse]. Most applications only need [DialogOutput::stdout], but
+/// the exit code and stderr are there for those that can't be interpreted from stdout alone.
+///
+/// ```
+/// # use zenity_dialog::dialog::{DialogOutput, Entry, ZenityApplication};
+/// let answer = Entry::new().parse(&DialogOutput::from_stdout("Alice")).unwrap();
+/// assert_eq!(answer.text, "Alice");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct DialogOutput {
+    stdout: String,
+    stderr: String,
+    exit_code: i32,
+}
+
+impl DialogOutput {
+    /// The output of a dialog that printed `stdout` and `stderr` and exited with the code.
+    pub fn new(stdout: impl Into<String>, stderr: impl Into<String>, exit_code: i32) -> Self {
+        Self {
+            stdout: stdout.into(),
+            stderr: stderr.into(),
+            exit_code,
+        }
+    }
+
+    /// The output of a dialog that printed `stdout`, nothing to stderr, and exited with code 0,
+    /// for example to parse output captured elsewhere.
+    pub fn from_stdout(stdout: impl Into<String>) -> Self {
+        Self::new(stdout, String::new(), 0)
+    }
+
+    /// What Zenity printed to stdout, trimmed as decided by
+    /// [super::ZenityApplication::trim_output].
+    pub fn stdout(&self) -> &str {
+        &self.stdout
+    }
+
+    /// What Zenity printed to stderr, without the noise matched by
+    /// [crate::ZenitySettings::stderr_filter].
+    pub fn stderr(&self) -> &str {
+        &self.stderr
+    }
+
+    /// The exit code, which is the cancel code for applications whose
+    /// [super::ZenityApplication::cancel_prints_answer] is `true`.
+    pub fn exit_code(&self) -> i32 {
+        self.exit_code
+    }
+}
+
+/// Why a dialog closed without the user confirming it. Returned by
+/// [ZenityOutput::into_result], so that flows that only care about confirmation can bail out
+/// with `?`.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum DialogDismissed {
+    /// The user cancelled the dialog
+    #[error("The dialog was cancelled")]
+    Rejected {
+        /// The text the dialog printed, such as the label of the clicked button
+        content: Option<String>,
+    },
+    /// The user clicked the extra button, whose label this is
+    #[error("The extra button {0:?} was clicked")]
+    ExtButton(String),
+    /// The dialog's timeout elapsed
+    #[error("The dialog timed out")]
+    TimedOut {
+        /// The selection the dialog printed when it timed out
+        content: Option<String>,
+    },
+    /// The dialog was closed by this program
+    #[error("The dialog was dismissed")]
+    Dismissed,
+    /// Zenity exited with an unexpected code
+    #[error("The dialog exited with unexpected code {exit_code}: {stderr}")]
+    Unknown {
+        /// The returned exit code.
+        exit_code: i32,
+        /// The content Zenity returned to stdout.
+        stdout: String,
+        /// The content Zenity returned to stderr.
+        stderr: String,
+    },
+}
+
+impl<T> ZenityOutput<T> {
+    /// Whether the user confirmed the dialog.
+    pub fn is_affirmed(&self) -> bool {
+        matches!(self, Self::Affirmed { .. })
+    }
+
+    /// Whether the user cancelled the dialog.
+    pub fn is_rejected(&self) -> bool {
+        matches!(self, Self::Rejected { .. })
+    }
+
+    /// Whether the dialog closed because its timeout elapsed.
+    pub fn is_timed_out(&self) -> bool {
+        matches!(self, Self::TimedOut { .. })
+    }
+
+    /// Whether the dialog was closed by this program.
+    pub fn is_dismissed(&self) -> bool {
+        matches!(self, Self::Dismissed)
+    }
+
+    /// The content of a confirmed dialog, or [None] if the user didn't confirm it. A confirmed
+    /// dialog that printed nothing yields `Some(None)`.
+    ///
+    /// ```
+    /// # use zenity_dialog::ZenityOutput;
+    /// let output: ZenityOutput<String> = ZenityOutput::Affirmed { content: None };
+    /// assert_eq!(output.affirmed(), Some(None));
+    /// ```
+    pub fn affirmed(self) -> Option<Option<T>> {
+        match self {
+            Self::Affirmed { content } => Some(content),
+            _ => None,
+        }
+    }
+
+    /// The content of a confirmed dialog, or [None] if the user didn't confirm it or it printed
+    /// nothing.
+    pub fn content(self) -> Option<T> {
+        self.affirmed().flatten()
+    }
+
+    /// The text a cancelled dialog printed, such as the label of the clicked button, or [None]
+    /// if the user didn't cancel it or it printed nothing.
+    pub fn rejected_text(&self) -> Option<&str> {
+        match self {
+            Self::Rejected { content } => content.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// The content of a confirmed dialog, or why the dialog closed otherwise.
+    ///
+    /// ```no_run
+    /// # use zenity_dialog::{dialog::Entry, ZenityDialog};
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let answer = ZenityDialog::new(Entry::new().with_text("Your name?"))
+    ///     .show()?
+    ///     .into_result()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn into_result(self) -> Result<Option<T>, DialogDismissed> {
+        ZenityOutputExtButton::from(self).into_result()
+    }
+
+    /// Transform the content of a confirmed dialog, leaving the other outputs untouched. A
+    /// confirmed dialog without content stays without content.
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> ZenityOutput<U> {
+        match self.try_map(|content| Ok::<U, std::convert::Infallible>(f(content))) {
+            Ok(output) => output,
+            Err(never) => match never {},
+        }
+    }
+
+    /// Like [ZenityOutput::map], but the transformation may fail, in which case its error is
+    /// returned.
+    pub fn try_map<U, E>(self, f: impl FnOnce(T) -> Result<U, E>) -> Result<ZenityOutput<U>, E> {
+        Ok(match self {
+            Self::Affirmed { content } => ZenityOutput::Affirmed {
+                content: content.map(f).transpose()?,
+            },
+            Self::Rejected { content } => ZenityOutput::Rejected { content },
+            Self::TimedOut { content } => ZenityOutput::TimedOut { content },
+            Self::Dismissed => ZenityOutput::Dismissed,
+            Self::Unknown {
+                exit_code,
+                stdout,
+                stderr,
+            } => ZenityOutput::Unknown {
+                exit_code,
+                stdout,
+                stderr,
+            },
+        })
+    }
+}
+
+impl<T> ZenityOutputExtButton<T> {
+    /// Whether the user confirmed the dialog.
+    pub fn is_affirmed(&self) -> bool {
+        matches!(self, Self::Affirmed { .. })
+    }
+
+    /// Whether the user cancelled the dialog.
+    pub fn is_rejected(&self) -> bool {
+        matches!(self, Self::Rejected { .. })
+    }
+
+    /// Whether the user clicked the extra button.
+    pub fn is_ext_button(&self) -> bool {
+        matches!(self, Self::ExtButton { .. })
+    }
+
+    /// Whether the dialog closed because its timeout elapsed.
+    pub fn is_timed_out(&self) -> bool {
+        matches!(self, Self::TimedOut { .. })
+    }
+
+    /// Whether the dialog was closed by this program.
+    pub fn is_dismissed(&self) -> bool {
+        matches!(self, Self::Dismissed)
+    }
+
+    /// The content of a confirmed dialog. See [ZenityOutput::affirmed].
+    pub fn affirmed(self) -> Option<Option<T>> {
+        match self {
+            Self::Affirmed { content } => Some(content),
+            _ => None,
+        }
+    }
+
+    /// The content of a confirmed dialog that printed something. See [ZenityOutput::content].
+    pub fn content(self) -> Option<T> {
+        self.affirmed().flatten()
+    }
+
+    /// The text a cancelled dialog printed. See [ZenityOutput::rejected_text].
+    pub fn rejected_text(&self) -> Option<&str> {
+        match self {
+            Self::Rejected { content } => content.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// The label of the extra button, or [None] if the user didn't click it.
+    pub fn ext_button_label(&self) -> Option<&str> {
+        match self {
+            Self::ExtButton { content } => Some(content),
+            _ => None,
+        }
+    }
+
+    /// The content of a confirmed dialog, or why the dialog closed otherwise, including a click
+    /// on the extra button. See [ZenityOutput::into_result].
+    pub fn into_result(self) -> Result<Option<T>, DialogDismissed> {
+        match self {
+            Self::Affirmed { content } => Ok(content),
+            Self::Rejected { content } => Err(DialogDismissed::Rejected { content }),
+            Self::ExtButton { content } => Err(DialogDismissed::ExtButton(content)),
+            Self::TimedOut { content } => Err(DialogDismissed::TimedOut { content }),
+            Self::Dismissed => Err(DialogDismissed::Dismissed),
+            Self::Unknown {
+                exit_code,
+                stdout,
+                stderr,
+            } => Err(DialogDismissed::Unknown {
+                exit_code,
+                stdout,
+                stderr,
+            }),
+        }
+    }
+
+    /// Transform the content of a confirmed dialog. See [ZenityOutput::map].
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> ZenityOutputExtButton<U> {
+        match self.try_map(|content| Ok::<U, std::convert::Infallible>(f(content))) {
+            Ok(output) => output,
+            Err(never) => match never {},
+        }
+    }
+
+    /// Transform the content of a confirmed dialog, which may fail. See
+    /// [ZenityOutput::try_map].
+    pub fn try_map<U, E>(
+        self,
+        f: impl FnOnce(T) -> Result<U, E>,
+    ) -> Result<ZenityOutputExtButton<U>, E> {
+        Ok(match self {
+            Self::Affirmed { content } => ZenityOutputExtButton::Affirmed {
+                content: content.map(f).transpose()?,
+            },
+            Self::Rejected { content } => ZenityOutputExtButton::Rejected { content },
+            Self::ExtButton { content } => ZenityOutputExtButton::ExtButton { content },
+            Self::TimedOut { content } => ZenityOutputExtButton::TimedOut { content },
+            Self::Dismissed => ZenityOutputExtButton::Dismissed,
+            Self::Unknown {
+                exit_code,
+                stdout,
+                stderr,
+            } => ZenityOutputExtButton::Unknown {
+                exit_code,
+                stdout,
+                stderr,
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DialogDismissed;
+    use crate::{ZenityOutput, ZenityOutputExtButton};
+
+    fn unknown() -> ZenityOutput<String> {
+        ZenityOutput::Unknown {
+            exit_code: 255,
+            stdout: String::new(),
+            stderr: "Gtk-WARNING".to_owned(),
+        }
+    }
+
+    /// Every output, each with content where it can have some.
+    fn outputs() -> Vec<ZenityOutput<String>> {
+        vec![
+            ZenityOutput::Affirmed {
+                content: Some("Ada".to_owned()),
+            },
+            ZenityOutput::Rejected {
+                content: Some("Quit".to_owned()),
+            },
+            ZenityOutput::TimedOut {
+                content: Some("Row".to_owned()),
+            },
+            ZenityOutput::Dismissed,
+            unknown(),
+        ]
+    }
+
+    /// Every output, each with content where it can have some.
+    fn ext_button_outputs() -> Vec<ZenityOutputExtButton<String>> {
+        let mut outputs: Vec<_> = outputs()
+            .into_iter()
+            .map(ZenityOutputExtButton::from)
+            .collect();
+        outputs.insert(
+            2,
+            ZenityOutputExtButton::ExtButton {
+                content: "Help".to_owned(),
+            },
+        );
+        outputs
+    }
+
+    #[test]
+    fn exactly_one_predicate_holds_for_each_output() {
+        let predicates = outputs()
+            .iter()
+            .map(|output| {
+                [
+                    output.is_affirmed(),
+                    output.is_rejected(),
+                    output.is_timed_out(),
+                    output.is_dismissed(),
+                ]
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            predicates,
+            [
+                [true, false, false, false],
+                [false, true, false, false],
+                [false, false, true, false],
+                [false, false, false, true],
+                [false, false, false, false],
+            ]
+        );
+    }
+
+    #[test]
+    fn exactly_one_predicate_holds_for_each_output_with_an_extra_button() {
+        let predicates = ext_button_outputs()
+            .iter()
+            .map(|output| {
+                [
+                    output.is_affirmed(),
+                    output.is_rejected(),
+                    output.is_ext_button(),
+                    output.is_timed_out(),
+                    output.is_dismissed(),
+                ]
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            predicates,
+            [
+                [true, false, false, false, false],
+                [false, true, false, false, false],
+                [false, false, true, false, false],
+                [false, false, false, true, false],
+                [false, false, false, false, true],
+                [false, false, false, false, false],
+            ]
+        );
+    }
+
+    #[test]
+    fn only_a_confirmed_dialog_has_content() {
+        let affirmed: Vec<_> = outputs().into_iter().map(ZenityOutput::affirmed).collect();
+        assert_eq!(
+            affirmed,
+            [Some(Some("Ada".to_owned())), None, None, None, None]
+        );
+
+        let content: Vec<_> = outputs().into_iter().map(ZenityOutput::content).collect();
+        assert_eq!(content, [Some("Ada".to_owned()), None, None, None, None]);
+
+        // A confirmed dialog that printed nothing is still confirmed.
+        let empty = ZenityOutput::<String>::Affirmed { content: None };
+        assert_eq!(empty.clone().affirmed(), Some(None));
+        assert_eq!(empty.content(), None);
+    }
+
+    #[test]
+    fn only_a_confirmed_dialog_with_an_extra_button_has_content() {
+        let affirmed: Vec<_> = ext_button_outputs()
+            .into_iter()
+            .map(ZenityOutputExtButton::affirmed)
+            .collect();
+        assert_eq!(
+            affirmed,
+            [Some(Some("Ada".to_owned())), None, None, None, None, None]
+        );
+
+        let content: Vec<_> = ext_button_outputs()
+            .into_iter()
+            .map(ZenityOutputExtButton::content)
+            .collect();
+        assert_eq!(
+            content,
+            [Some("Ada".to_owned()), None, None, None, None, None]
+        );
+
+        let empty = ZenityOutputExtButton::<String>::Affirmed { content: None };
+        assert_eq!(empty.clone().affirmed(), Some(None));
+        assert_eq!(empty.content(), None);
+    }
+
+    #[test]
+    fn only_a_cancelled_dialog_has_rejected_text() {
+        let texts: Vec<_> = outputs()
+            .iter()
+            .map(|output| output.rejected_text().map(str::to_owned))
+            .collect();
+        assert_eq!(texts, [None, Some("Quit".to_owned()), None, None, None]);
+
+        let texts: Vec<_> = ext_button_outputs()
+            .iter()
+            .map(|output| output.rejected_text().map(str::to_owned))
+            .collect();
+        assert_eq!(
+            texts,
+            [None, Some("Quit".to_owned()), None, None, None, None]
+        );
+
+        let silent = ZenityOutput::<String>::Rejected { content: None };
+        assert_eq!(silent.rejected_text(), None);
+    }
+
+    #[test]
+    fn only_a_click_on_the_extra_button_has_its_label() {
+        let labels: Vec<_> = ext_button_outputs()
+            .iter()
+            .map(|output| output.ext_button_label().map(str::to_owned))
+            .collect();
+        assert_eq!(
+            labels,
+            [None, None, Some("Help".to_owned()), None, None, None]
+        );
+    }
+
+    #[test]
+    fn only_a_confirmed_dialog_is_ok() {
+        let results: Vec<_> = outputs()
+            .into_iter()
+            .map(ZenityOutput::into_result)
+            .collect();
+        assert_eq!(
+            results,
+            [
+                Ok(Some("Ada".to_owned())),
+                Err(DialogDismissed::Rejected {
+                    content: Some("Quit".to_owned()),
+                }),
+                Err(DialogDismissed::TimedOut {
+                    content: Some("Row".to_owned()),
+                }),
+                Err(DialogDismissed::Dismissed),
+                Err(DialogDismissed::Unknown {
+                    exit_code: 255,
+                    stdout: String::new(),
+                    stderr: "Gtk-WARNING".to_owned(),
+                }),
+            ]
+        );
+
+        let empty = ZenityOutput::<String>::Affirmed { content: None };
+        assert_eq!(empty.into_result(), Ok(None));
+    }
+
+    #[test]
+    fn only_a_confirmed_dialog_with_an_extra_button_is_ok() {
+        let results: Vec<_> = ext_button_outputs()
+            .into_iter()
+            .map(ZenityOutputExtButton::into_result)
+            .collect();
+        assert_eq!(
+            results,
+            [
+                Ok(Some("Ada".to_owned())),
+                Err(DialogDismissed::Rejected {
+                    content: Some("Quit".to_owned()),
+                }),
+                Err(DialogDismissed::ExtButton("Help".to_owned())),
+                Err(DialogDismissed::TimedOut {
+                    content: Some("Row".to_owned()),
+                }),
+                Err(DialogDismissed::Dismissed),
+                Err(DialogDismissed::Unknown {
+                    exit_code: 255,
+                    stdout: String::new(),
+                    stderr: "Gtk-WARNING".to_owned(),
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn mapping_transforms_only_the_content_of_a_confirmed_dialog() {
+        let mapped: Vec<_> = outputs()
+            .into_iter()
+            .map(|output| output.map(|content| content.len()))
+            .collect();
+        assert_eq!(mapped[0], ZenityOutput::Affirmed { content: Some(3) });
+        // The other outputs have no content of the mapped type, so they're compared as printed.
+        for (mapped, output) in mapped.iter().zip(outputs()).skip(1) {
+            assert_eq!(format!("{:?}", mapped), format!("{:?}", output));
+        }
+
+        let mapped: Vec<_> = ext_button_outputs()
+            .into_iter()
+            .map(|output| output.map(|content| content.len()))
+            .collect();
+        assert_eq!(
+            mapped[0],
+            ZenityOutputExtButton::Affirmed { content: Some(3) }
+        );
+        for (mapped, output) in mapped.iter().zip(ext_button_outputs()).skip(1) {
+            assert_eq!(format!("{:?}", mapped), format!("{:?}", output));
+        }
+    }
+
+    #[test]
+    fn a_failing_transformation_fails_only_for_content() {
+        let parse = |content: String| content.parse::<u8>();
+
+        let output = ZenityOutput::Affirmed {
+            content: Some("Ada".to_owned()),
+        };
+        assert!(output.try_map(parse).is_err());
+        let output = ZenityOutputExtButton::Affirmed {
+            content: Some("Ada".to_owned()),
+        };
+        assert!(output.try_map(parse).is_err());
+
+        // Without content, there's nothing to transform.
+        let output = ZenityOutput::<String>::Affirmed { content: None };
+        assert_eq!(
+            output.try_map(parse),
+            Ok(ZenityOutput::Affirmed { content: None })
+        );
+        let output = ZenityOutputExtButton::<String>::Affirmed { content: None };
+        assert_eq!(
+            output.try_map(parse),
+            Ok(ZenityOutputExtButton::Affirmed { content: None })
+        );
+
+        // The other outputs pass through, even with content that wouldn't parse.
+        for output in outputs().into_iter().skip(1) {
+            let debug = format!("{:?}", output);
+            assert_eq!(format!("{:?}", output.try_map(parse).unwrap()), debug);
+        }
+        for output in ext_button_outputs().into_iter().skip(1) {
+            let debug = format!("{:?}", output);
+            assert_eq!(format!("{:?}", output.try_map(parse).unwrap()), debug);
+        }
+    }
+
+    #[test]
+    fn a_successful_transformation_maps_the_content() {
+        let output = ZenityOutput::Affirmed {
+            content: Some("42".to_owned()),
+        };
+        assert_eq!(
+            output.try_map(|content| content.parse::<u8>()),
+            Ok(ZenityOutput::Affirmed { content: Some(42) })
+        );
+        let output = ZenityOutputExtButton::Affirmed {
+            content: Some("42".to_owned()),
+        };
+        assert_eq!(
+            output.try_map(|content| content.parse::<u8>()),
+            Ok(ZenityOutputExtButton::Affirmed { content: Some(42) })
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn every_output_round_trips_through_json() {
+        let mut outputs = outputs();
+        outputs.push(ZenityOutput::Unknown {
+            exit_code: -1,
+            stdout: "Größe\n".to_owned(),
+            stderr: "(zenity:1234): Gtk-WARNING **: 無法開啟顯示 ❌".to_owned(),
+        });
+        outputs.push(ZenityOutput::Affirmed { content: None });
+
+        for output in outputs {
+            let json = serde_json::to_string(&output).unwrap();
+            let parsed: ZenityOutput<String> = serde_json::from_str(&json).unwrap();
+            assert_eq!(parsed, output, "{}", json);
+        }
+
+        for output in ext_button_outputs() {
+            let json = serde_json::to_string(&output).unwrap();
+            let parsed: ZenityOutputExtButton<String> = serde_json::from_str(&json).unwrap();
+            assert_eq!(parsed, output, "{}", json);
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn outputs_are_tagged_by_their_variant_name() {
+        let json = |output: ZenityOutputExtButton<String>| serde_json::to_value(output).unwrap();
+
+        assert_eq!(
+            json(ZenityOutputExtButton::Affirmed {
+                content: Some("Ada".to_owned())
+            }),
+            serde_json::json!({ "Affirmed": { "content": "Ada" } })
+        );
+        assert_eq!(
+            json(ZenityOutputExtButton::ExtButton {
+                content: "Help".to_owned()
+            }),
+            serde_json::json!({ "ExtButton": { "content": "Help" } })
+        );
+        assert_eq!(
+            json(ZenityOutputExtButton::Dismissed),
+            serde_json::json!("Dismissed")
+        );
+    }
+
+    #[cfg(all(feature = "chrono", feature = "serde"))]
+    #[test]
+    fn dates_round_trip_through_json() {
+        let output = ZenityOutput::Affirmed {
+            content: chrono::NaiveDate::from_ymd_opt(1815, 12, 10),
+        };
+        let json = serde_json::to_string(&output).unwrap();
+        assert_eq!(json, r#"{"Affirmed":{"content":"1815-12-10"}}"#);
+        assert_eq!(
+            serde_json::from_str::<ZenityOutput<chrono::NaiveDate>>(&json).unwrap(),
+            output
+        );
+    }
+}
@@ -0,0 +1,522 @@
+use super::{
+    application::ToArgVector, DialogDismissed, DialogOutput, ZenityApplication, ZenityDialog,
+    ZenityOutput,
+};
+use crate::{Arg, CommandRunner};
+use std::{collections::BTreeMap, ffi::OsString};
+
+/// Configuration for a dialog that asks the user a question. Zenity prints nothing when it's
+/// confirmed, so its content is [None], unless the question is in switch mode, where the content
+/// is the label of the pressed button.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(::serde::Deserialize),
+    serde(default, deny_unknown_fields)
+)]
+pub struct Question {
+    /// The body text
+    pub text: Option<String>,
+    /// Custom label for the ok button
+    pub ok_label: Option<String>,
+    /// Custom label for the cancel button
+    pub cancel_label: Option<String>,
+    /// Focus the cancel button instead of the ok button
+    pub default_cancel: bool,
+    /// The labels of the buttons shown instead of ok and cancel, if the question is in switch
+    /// mode
+    pub switch: Option<Vec<String>>,
+    /// Prevent word wrap
+    pub no_wrap: bool,
+    /// Disable markup support
+    pub no_markup: bool,
+    /// Show ellipses for texts that are too long to display
+    pub ellipsize: bool,
+    /// Escape markup in the body text, so that it's shown as typed
+    pub escape_text: bool,
+}
+
+impl ZenityApplication for Question {
+    type Return = String;
+
+    fn parse(&self, output: &DialogOutput) -> Result<Self::Return, crate::Error> {
+        Ok(output.stdout().to_owned())
+    }
+
+    fn default_title() -> Option<&'static str> {
+        Some("Question")
+    }
+
+    /// In switch mode, Zenity exits with the cancel code after printing the label of the
+    /// pressed button.
+    fn cancel_prints_answer(&self) -> bool {
+        self.switch.is_some()
+    }
+
+    fn field_map(&self) -> BTreeMap<&'static str, String> {
+        let mut fields = BTreeMap::new();
+        if let Some(ref text) = self.text {
+            fields.insert("text", text.to_string());
+        }
+
+        if let Some(ref ok_label) = self.ok_label {
+            fields.insert("ok_label", ok_label.to_string());
+        }
+
+        if let Some(ref cancel_label) = self.cancel_label {
+            fields.insert("cancel_label", cancel_label.to_string());
+        }
+
+        if self.default_cancel {
+            fields.insert("default_cancel", true.to_string());
+        }
+
+        if let Some(ref switch) = self.switch {
+            fields.insert("switch", format!("{switch:?}"));
+        }
+
+        if self.no_wrap {
+            fields.insert("no_wrap", true.to_string());
+        }
+
+        if self.no_markup {
+            fields.insert("no_markup", true.to_string());
+        }
+
+        if self.ellipsize {
+            fields.insert("ellipsize", true.to_string());
+        }
+
+        if self.escape_text {
+            fields.insert("escape_text", true.to_string());
+        }
+
+        fields
+    }
+
+    fn validate(&self) -> Vec<crate::ValidationError> {
+        let mut errors = Vec::new();
+        if self.switch.as_ref().is_some_and(Vec::is_empty) {
+            errors.push(crate::ValidationError::new(
+                "switch",
+                "needs at least one button, as the ok and cancel buttons are hidden",
+            ));
+        }
+
+        if self.switch.is_some() && (self.ok_label.is_some() || self.cancel_label.is_some()) {
+            errors.push(crate::ValidationError::new(
+                "switch",
+                "hides the ok and cancel buttons, so their labels are ignored",
+            ));
+        }
+
+        errors
+    }
+}
+
+impl_has_text!(Question);
+
+impl ToArgVector for Question {
+    fn to_argv(&self) -> Vec<OsString> {
+        let mut args = vec![OsString::from("--question")];
+        if let Some(ref text) = self.text {
+            match self.escape_text && !self.no_markup {
+                true => args.push(format!("--text={}", crate::escape_markup(text)).into()),
+                false => args.push(format!("--text={text}").into()),
+            }
+        };
+
+        if let Some(ref ok_label) = self.ok_label {
+            args.push(format!("--ok-label={ok_label}").into())
+        };
+
+        if let Some(ref cancel_label) = self.cancel_label {
+            args.push(format!("--cancel-label={cancel_label}").into())
+        };
+
+        if self.default_cancel {
+            args.push("--default-cancel".into());
+        }
+
+        if self.no_wrap {
+            args.push("--no-wrap".into());
+        }
+
+        if self.no_markup {
+            args.push("--no-markup".into())
+        }
+
+        if self.ellipsize {
+            args.push("--ellipsize".into())
+        }
+
+        if let Some(ref switch) = self.switch {
+            args.push("--switch".into());
+            args.extend(
+                switch
+                    .iter()
+                    .map(|label| Arg::from(("--extra-button", label.as_str())).to_os_string()),
+            );
+        }
+
+        args
+    }
+}
+
+impl Question {
+    /// Create a default configuration.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Set the body text.
+    pub fn with_text(mut self, text: impl Into<String>) -> Self {
+        self.set_text(text);
+        self
+    }
+
+    /// Set the body text, in place.
+    pub fn set_text(&mut self, text: impl Into<String>) -> &mut Self {
+        self.text = Some(text.into());
+        self
+    }
+
+    /// Set the body text to the markup, which is passed to Zenity as is, without
+    /// `with_escape_text` escaping its tags.
+    pub fn with_markup(mut self, markup: crate::Markup) -> Self {
+        self.set_markup(markup);
+        self
+    }
+
+    /// Set the body text to the markup, in place. See [Question::with_markup].
+    pub fn set_markup(&mut self, markup: crate::Markup) -> &mut Self {
+        self.text = Some(markup.build());
+        self.escape_text = false;
+        self
+    }
+
+    /// Replace the default ok button label.
+    pub fn with_ok_label(mut self, ok_label: impl Into<String>) -> Self {
+        self.set_ok_label(ok_label);
+        self
+    }
+
+    /// Replace the default ok button label, in place.
+    pub fn set_ok_label(&mut self, ok_label: impl Into<String>) -> &mut Self {
+        self.ok_label = Some(ok_label.into());
+        self
+    }
+
+    /// Replace the default cancel button label.
+    pub fn with_cancel_label(mut self, cancel_label: impl Into<String>) -> Self {
+        self.set_cancel_label(cancel_label);
+        self
+    }
+
+    /// Replace the default cancel button label, in place.
+    pub fn set_cancel_label(&mut self, cancel_label: impl Into<String>) -> &mut Self {
+        self.cancel_label = Some(cancel_label.into());
+        self
+    }
+
+    /// Focus the cancel button, so that pressing Enter doesn't confirm a destructive action.
+    pub fn with_default_cancel(mut self) -> Self {
+        self.default_cancel = true;
+        self
+    }
+
+    /// Replace the ok and cancel buttons with buttons with the labels. The dialog is confirmed
+    /// with the label of the pressed button as its content, and [crate::ZenityDialog::validate]
+    /// rejects a switch without buttons.
+    ///
+    /// ```no_run
+    /// # use zenity_dialog::{dialog::Question, ZenityDialog};
+    /// # fn main() -> zenity_dialog::Result<()> {
+    /// let question = Question::new()
+    ///     .with_text("The file already exists.")
+    ///     .with_switch(["Overwrite", "Keep both", "Skip"]);
+    /// if let Some(choice) = ZenityDialog::new(question).show()?.content() {
+    ///     println!("{choice}");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_switch(mut self, buttons: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.set_switch(buttons);
+        self
+    }
+
+    /// Replace the ok and cancel buttons, in place. See [Question::with_switch].
+    pub fn set_switch(
+        &mut self,
+        buttons: impl IntoIterator<Item = impl Into<String>>,
+    ) -> &mut Self {
+        self.switch = Some(buttons.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Prevent text from wrapping.
+    pub fn with_no_wrap(mut self) -> Self {
+        self.no_wrap = true;
+        self
+    }
+
+    /// Disable markup support, so that tags such as `<b>` are shown as typed.
+    ///
+    /// ```
+    /// # use zenity_dialog::{dialog::Question, ZenityDialog};
+    /// let question = Question::new().with_text("Delete <b>everything</b>?").with_no_markup();
+    /// assert_eq!(
+    ///     ZenityDialog::new(question).to_command_string(),
+    ///     "zenity --question '--text=Delete <b>everything</b>?' --no-markup --title=Question"
+    /// );
+    /// ```
+    pub fn with_no_markup(mut self) -> Self {
+        self.no_markup = true;
+        self
+    }
+
+    /// Escape markup in the body text when passing it to Zenity, so that characters such as `<`
+    /// and `&` are shown as typed while markup stays enabled. See [crate::escape_markup].
+    pub fn with_escape_text(mut self) -> Self {
+        self.escape_text = true;
+        self
+    }
+
+    /// If text is too long to fit, display an ellipses. Showing the dialog with a Zenity that
+    /// doesn't list this option, as newer versions may not, fails with
+    /// [crate::Error::UnsupportedOption].
+    pub fn with_ellipsize(mut self) -> Self {
+        self.ellipsize = true;
+        self
+    }
+}
+
+/// The question with the text as its body text.
+impl From<&str> for Question {
+    fn from(text: &str) -> Self {
+        Self::new().with_text(text)
+    }
+}
+
+/// The question with the text as its body text.
+impl From<String> for Question {
+    fn from(text: String) -> Self {
+        Self::new().with_text(text)
+    }
+}
+
+impl ZenityDialog<Question> {
+    /// Show the question and report whether the user confirmed it. A cancelled question is
+    /// `false`, while a question that closed otherwise, for example because it timed out, fails
+    /// with [crate::Error::Unanswered], so that it isn't mistaken for an answer.
+    ///
+    /// ```no_run
+    /// # use zenity_dialog::{dialog::Question, ZenityDialog};
+    /// # fn main() -> zenity_dialog::Result<()> {
+    /// let delete = ZenityDialog::new(Question::new().with_text("Delete the backup?"))
+    ///     .with_title("Cleanup")
+    ///     .show_bool()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn show_bool(&self) -> crate::Result<bool> {
+        Self::answer(self.show_ref()?)
+    }
+
+    /// Like `show_bool`, but runs Zenity through the runner. See
+    /// [ZenityDialog::show_with_runner].
+    pub fn show_bool_with_runner(&self, runner: &impl CommandRunner) -> crate::Result<bool> {
+        Self::answer(self.show_with_runner(runner)?)
+    }
+
+    fn answer(output: ZenityOutput<String>) -> crate::Result<bool> {
+        match output.into_result() {
+            Ok(_) => Ok(true),
+            Err(DialogDismissed::Rejected { .. }) => Ok(false),
+            Err(dismissed) => Err(crate::Error::Unanswered(dismissed)),
+        }
+    }
+}
+
+/// Ask the user a question with the text and report whether they confirmed it, with the
+/// crate-wide settings. See [ZenityDialog::show_bool], which also allows setting a title.
+///
+/// ```no_run
+/// # fn main() -> zenity_dialog::Result<()> {
+/// if zenity_dialog::confirm("Overwrite the existing file?")? {
+///     println!("Overwriting");
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn confirm(text: impl Into<String>) -> crate::Result<bool> {
+    ZenityDialog::new(Question::new().with_text(text)).show_bool()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runner::{MockResponse, MockRunner};
+
+    fn argv(question: &Question) -> Vec<String> {
+        question
+            .to_argv()
+            .into_iter()
+            .map(|arg| arg.into_string().unwrap())
+            .collect()
+    }
+
+    fn shown(question: Question, response: MockResponse) -> ZenityOutput<String> {
+        let runner = MockRunner::new().respond_otherwise(response);
+        ZenityDialog::new(question)
+            .show_with_runner(&runner)
+            .unwrap()
+    }
+
+    #[test]
+    fn default_cancel_focuses_the_cancel_button() {
+        assert_eq!(argv(&Question::new()), ["--question"]);
+        assert_eq!(
+            argv(&Question::new().with_default_cancel()),
+            ["--question", "--default-cancel"]
+        );
+    }
+
+    #[test]
+    fn a_switch_replaces_the_buttons() {
+        let question = Question::new()
+            .with_text("The file exists.")
+            .with_switch(["Overwrite", "Keep both"]);
+        assert_eq!(
+            argv(&question),
+            [
+                "--question",
+                "--text=The file exists.",
+                "--switch",
+                "--extra-button=Overwrite",
+                "--extra-button=Keep both"
+            ]
+        );
+    }
+
+    #[test]
+    fn the_pressed_label_of_a_switch_is_the_answer() {
+        let question = Question::new().with_switch(["Overwrite", "Keep both"]);
+        assert_eq!(
+            shown(
+                question.clone(),
+                MockResponse::new(1).with_stdout("Keep both\n")
+            ),
+            ZenityOutput::Affirmed {
+                content: Some("Keep both".to_owned()),
+            }
+        );
+
+        // Closing the window prints no label.
+        assert_eq!(
+            shown(question, MockResponse::rejected()),
+            ZenityOutput::Rejected { content: None }
+        );
+    }
+
+    #[test]
+    fn printed_text_doesnt_confirm_a_question_without_a_switch() {
+        assert_eq!(
+            shown(Question::new(), MockResponse::new(1).with_stdout("Later\n")),
+            ZenityOutput::Rejected {
+                content: Some("Later".to_owned()),
+            }
+        );
+    }
+
+    #[test]
+    fn layout_options_pass_their_flags() {
+        assert_eq!(
+            argv(&Question::new().with_no_wrap()),
+            ["--question", "--no-wrap"]
+        );
+        assert_eq!(
+            argv(&Question::new().with_no_markup()),
+            ["--question", "--no-markup"]
+        );
+        assert_eq!(
+            argv(&Question::new().with_ellipsize()),
+            ["--question", "--ellipsize"]
+        );
+        assert_eq!(
+            argv(
+                &Question::new()
+                    .with_ellipsize()
+                    .with_no_markup()
+                    .with_no_wrap()
+            ),
+            ["--question", "--no-wrap", "--no-markup", "--ellipsize"]
+        );
+    }
+
+    #[test]
+    fn markup_is_shown_as_typed_with_no_markup() {
+        let text = "Delete <b>everything</b>?";
+
+        // Zenity renders the tags, unless markup is disabled or escaped.
+        assert_eq!(
+            argv(&Question::new().with_text(text))[1],
+            "--text=Delete <b>everything</b>?"
+        );
+        assert_eq!(
+            argv(&Question::new().with_text(text).with_no_markup()),
+            [
+                "--question",
+                "--text=Delete <b>everything</b>?",
+                "--no-markup"
+            ]
+        );
+        assert_eq!(
+            argv(&Question::new().with_text(text).with_escape_text())[1],
+            "--text=Delete &lt;b&gt;everything&lt;/b&gt;?"
+        );
+
+        // Text that isn't markup isn't escaped again.
+        assert_eq!(
+            argv(
+                &Question::new()
+                    .with_text(text)
+                    .with_escape_text()
+                    .with_no_markup()
+            )[1],
+            "--text=Delete <b>everything</b>?"
+        );
+    }
+
+    fn confirmed(response: MockResponse) -> crate::Result<bool> {
+        let runner = MockRunner::new().respond_otherwise(response);
+        ZenityDialog::new(Question::new().with_text("Overwrite?")).show_bool_with_runner(&runner)
+    }
+
+    #[test]
+    fn confirming_is_true_and_cancelling_is_false() {
+        assert!(confirmed(MockResponse::affirmed("")).unwrap());
+        assert!(!confirmed(MockResponse::rejected()).unwrap());
+    }
+
+    #[test]
+    fn a_question_without_an_answer_is_an_error() {
+        let err = confirmed(MockResponse::timed_out()).unwrap_err();
+        assert!(
+            matches!(
+                err,
+                crate::Error::Unanswered(DialogDismissed::TimedOut { .. })
+            ),
+            "{:?}",
+            err
+        );
+
+        let err = confirmed(MockResponse::new(255).with_stderr("no display")).unwrap_err();
+        assert!(
+            matches!(err, crate::Error::ZenityFailed { exit_code: 255, .. }),
+            "{:?}",
+            err
+        );
+    }
+}
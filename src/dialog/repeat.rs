@@ -0,0 +1,131 @@
+use super::{Entry, ZenityDialog, ZenityOutput};
+use std::time::Duration;
+
+/// Settings for collecting several answers with [ZenityDialog::show_repeating].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RepeatOptions {
+    /// Stop after this many answers have been collected
+    pub max_count: Option<usize>,
+    /// Close each dialog automatically after this duration, overriding the dialog's own timeout
+    pub timeout: Option<Duration>,
+    /// Drop an answer if it is identical to the one submitted just before it
+    pub dedup_consecutive: bool,
+    /// Body text shown on each dialog, where `{n}` is replaced with the number of the answer
+    /// being requested, starting at 1
+    pub prompt_template: Option<String>,
+}
+
+impl RepeatOptions {
+    /// The default settings.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Stop after the given number of answers.
+    pub fn with_max_count(mut self, max_count: usize) -> Self {
+        self.max_count = Some(max_count);
+        self
+    }
+
+    /// Close each dialog automatically after the duration has passed.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Drop answers that repeat the previous answer.
+    pub fn with_dedup_consecutive(mut self) -> Self {
+        self.dedup_consecutive = true;
+        self
+    }
+
+    /// Set the body text of each dialog. `{n}` is replaced with the running count, as in
+    /// `"Item {n}"`.
+    pub fn with_prompt_template(mut self, prompt_template: impl Into<String>) -> Self {
+        self.prompt_template = Some(prompt_template.into());
+        self
+    }
+}
+
+/// The answers collected by [ZenityDialog::show_repeating].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RepeatResult {
+    /// The submitted answers, in order
+    pub answers: Vec<String>,
+    /// Why the dialog stopped reopening
+    pub terminated_by: Termination,
+}
+
+/// The reason [ZenityDialog::show_repeating] stopped collecting answers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Termination {
+    /// The user rejected the dialog
+    Cancel,
+    /// The dialog timed out
+    Timeout,
+    /// The configured maximum number of answers was reached
+    Limit,
+    /// Zenity returned an unexpected response. See [ZenityOutput::Unknown].
+    Unknown {
+        /// The returned exit code.
+        exit_code: i32,
+        /// The content Zenity returned to stdout.
+        stdout: String,
+        /// The content Zenity returned to stderr.
+        stderr: String,
+    },
+}
+
+impl ZenityDialog<Entry> {
+    /// Reopen the entry dialog after every affirmed answer until the user cancels, the dialog
+    /// times out, or the configured maximum is reached, collecting every answer along the way.
+    pub fn show_repeating(&self, options: &RepeatOptions) -> crate::Result<RepeatResult> {
+        let mut dialog = self.clone();
+        if let Some(timeout) = options.timeout {
+            dialog.timeout = Some(timeout);
+        }
+
+        let mut answers: Vec<String> = Vec::new();
+
+        let terminated_by = loop {
+            if options.max_count.is_some_and(|max| answers.len() >= max) {
+                break Termination::Limit;
+            }
+
+            if let Some(ref template) = options.prompt_template {
+                let n = answers.len() + 1;
+                dialog.application.text = Some(template.replace("{n}", &n.to_string()));
+            }
+
+            let answer = match dialog.show_ref()? {
+                ZenityOutput::Affirmed { content } => content.map(String::from).unwrap_or_default(),
+                ZenityOutput::Rejected { .. } => break Termination::Cancel,
+                ZenityOutput::TimedOut { .. } => break Termination::Timeout,
+                // Dialogs shown with show_ref are never dismissed by this program.
+                ZenityOutput::Dismissed => break Termination::Cancel,
+                ZenityOutput::Unknown {
+                    exit_code,
+                    stdout,
+                    stderr,
+                } => {
+                    break Termination::Unknown {
+                        exit_code,
+                        stdout,
+                        stderr,
+                    }
+                }
+            };
+
+            if options.dedup_consecutive && answers.last() == Some(&answer) {
+                continue;
+            }
+
+            answers.push(answer);
+        };
+
+        Ok(RepeatResult {
+            answers,
+            terminated_by,
+        })
+    }
+}
@@ -0,0 +1,99 @@
+use super::{ZenityApplication, ZenityDialog, ZenityOutput};
+
+/// Shows an application directly, in a dialog with the default settings, for when no title,
+/// size, or other dialog option is needed. Implemented for every [ZenityApplication]. Extra
+/// buttons and every other option are configured on a [ZenityDialog].
+///
+/// ```no_run
+/// # use zenity_dialog::{dialog::{Entry, Info}, ShowExt};
+/// # fn main() -> zenity_dialog::Result<()> {
+/// Info::new().with_text("The backup is done.").show()?;
+/// let name = Entry::new().with_text("Your name?").show_with_title("Welcome")?;
+/// # Ok(())
+/// # }
+/// ```
+pub trait ShowExt: ZenityApplication {
+    /// Show the application in a default dialog. See [ZenityDialog::show].
+    fn show(self) -> crate::Result<ZenityOutput<Self::Return>> {
+        default_dialog(self, None).show()
+    }
+
+    /// Show the application in a default dialog with the title. See [ZenityDialog::with_title].
+    fn show_with_title(
+        self,
+        title: impl Into<String>,
+    ) -> crate::Result<ZenityOutput<Self::Return>> {
+        default_dialog(self, Some(title.into())).show()
+    }
+}
+
+impl<T: ZenityApplication> ShowExt for T {}
+
+/// The dialog [ShowExt] shows the application in.
+fn default_dialog<T: ZenityApplication>(application: T, title: Option<String>) -> ZenityDialog<T> {
+    let dialog = ZenityDialog::new(application);
+    match title {
+        Some(title) => dialog.with_title(title),
+        None => dialog,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::default_dialog;
+    #[cfg(feature = "entry")]
+    use crate::dialog::Entry;
+    use crate::{
+        dialog::Info,
+        runner::{MockResponse, MockRunner},
+        ZenityOutput,
+    };
+
+    #[test]
+    fn info_is_shown_in_a_default_dialog() {
+        let runner = MockRunner::new().respond_otherwise(MockResponse::affirmed(""));
+        let info = Info::new().with_text("Done");
+
+        assert_eq!(
+            default_dialog(info.clone(), None)
+                .show_with_runner(&runner)
+                .unwrap(),
+            ZenityOutput::Affirmed { content: None }
+        );
+        default_dialog(info, Some("Backup".to_owned()))
+            .show_with_runner(&runner)
+            .unwrap();
+
+        let invocations = runner.invocations();
+        assert!(invocations[0].has_arg("--info"));
+        assert!(invocations[0].has_arg("--text=Done"));
+        assert!(invocations[0].has_arg("--title=Information"));
+        assert!(invocations[1].has_arg("--title=Backup"));
+        assert!(!invocations[1].has_arg("--title=Information"));
+    }
+
+    #[cfg(feature = "entry")]
+    #[test]
+    fn entry_answers_are_returned_as_shown() {
+        let entry = Entry::new().with_text("Your name?");
+        let runner = MockRunner::new().respond_otherwise(MockResponse::affirmed("Ada\n"));
+        let output = default_dialog(entry.clone(), Some("Welcome".to_owned()))
+            .show_with_runner(&runner)
+            .unwrap();
+        assert_eq!(
+            output.map(String::from),
+            ZenityOutput::Affirmed {
+                content: Some("Ada".to_owned()),
+            }
+        );
+        assert!(runner.invocations()[0].has_arg("--title=Welcome"));
+
+        let runner = MockRunner::new().respond_otherwise(MockResponse::rejected());
+        assert_eq!(
+            default_dialog(entry, None)
+                .show_with_runner(&runner)
+                .unwrap(),
+            ZenityOutput::Rejected { content: None }
+        );
+    }
+}
@@ -0,0 +1,286 @@
+#[cfg(feature = "calendar")]
+use super::Calendar;
+#[cfg(feature = "entry")]
+use super::Entry;
+#[cfg(feature = "error")]
+use super::Error;
+#[cfg(feature = "file-selection")]
+use super::FileSelection;
+#[cfg(feature = "info")]
+use super::Info;
+#[cfg(feature = "list")]
+use super::List;
+#[cfg(feature = "notification")]
+use super::Notification;
+#[cfg(feature = "question")]
+use super::Question;
+#[cfg(feature = "warning")]
+use super::Warning;
+use super::{ZenityApplication, ZenityDialog};
+use serde::{de::DeserializeOwned, Deserialize, Deserializer};
+use std::{fs, path::Path, time::Duration};
+
+/// A dialog of any application type, as loaded from a definition file whose `type` field names
+/// the application, as in `type = "info"`. The remaining fields are those of [ZenityDialog].
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum DialogSpec {
+    /// A [Calendar] dialog
+    #[cfg(feature = "calendar")]
+    Calendar(ZenityDialog<Calendar>),
+    /// An [Entry] dialog
+    #[cfg(feature = "entry")]
+    Entry(ZenityDialog<Entry>),
+    /// An [Error] dialog
+    #[cfg(feature = "error")]
+    Error(ZenityDialog<Error>),
+    /// A [FileSelection] dialog
+    #[cfg(feature = "file-selection")]
+    #[serde(rename = "file-selection")]
+    FileSelection(ZenityDialog<FileSelection>),
+    /// An [Info] dialog
+    #[cfg(feature = "info")]
+    Info(ZenityDialog<Info>),
+    /// A [List] dialog
+    #[cfg(feature = "list")]
+    List(ZenityDialog<List>),
+    /// A [Notification]
+    #[cfg(feature = "notification")]
+    Notification(ZenityDialog<Notification>),
+    /// A [Question] dialog
+    #[cfg(feature = "question")]
+    Question(ZenityDialog<Question>),
+    /// A [Warning] dialog
+    #[cfg(feature = "warning")]
+    Warning(ZenityDialog<Warning>),
+}
+
+impl DialogSpec {
+    /// Load the dialog from a JSON definition.
+    pub fn from_json_str(json: &str) -> crate::Result<Self> {
+        from_json_str(json)
+    }
+
+    /// Load the dialog from a TOML definition.
+    #[cfg(feature = "toml")]
+    pub fn from_toml_str(toml: &str) -> crate::Result<Self> {
+        from_toml_str(toml)
+    }
+
+    /// Load the dialog from a definition file, whose format is inferred from its extension.
+    /// See [ZenityDialog::from_file].
+    pub fn from_file(path: impl AsRef<Path>) -> crate::Result<Self> {
+        from_file(path.as_ref())
+    }
+}
+
+impl<T> ZenityDialog<T>
+where
+    T: ZenityApplication + DeserializeOwned,
+{
+    /// Load the dialog from a JSON definition, letting texts be changed without recompiling.
+    /// The fields are those of the dialog, with the application's fields nested under
+    /// `application` and timeouts given in seconds:
+    ///
+    /// ```json
+    /// { "title": "Backup", "timeout": 30, "application": { "text": "The backup is done." } }
+    /// ```
+    pub fn from_json_str(json: &str) -> crate::Result<Self> {
+        from_json_str(json)
+    }
+
+    /// Load the dialog from a TOML definition. See [ZenityDialog::from_json_str] for the fields.
+    ///
+    /// ```toml
+    /// title = "Backup"
+    /// timeout = 30
+    ///
+    /// [application]
+    /// text = "The backup is done."
+    /// ```
+    #[cfg(feature = "toml")]
+    pub fn from_toml_str(toml: &str) -> crate::Result<Self> {
+        from_toml_str(toml)
+    }
+
+    /// Load the dialog from a definition file, whose format is inferred from its extension:
+    /// `.json`, or `.toml` with the `toml` feature.
+    pub fn from_file(path: impl AsRef<Path>) -> crate::Result<Self> {
+        from_file(path.as_ref())
+    }
+}
+
+fn from_json_str<T: DeserializeOwned>(json: &str) -> crate::Result<T> {
+    let deserializer = &mut serde_json::Deserializer::from_str(json);
+    serde_path_to_error::deserialize(deserializer)
+        .map_err(|err| crate::Error::InvalidDefinition(err.into()))
+}
+
+#[cfg(feature = "toml")]
+fn from_toml_str<T: DeserializeOwned>(toml: &str) -> crate::Result<T> {
+    toml::from_str(toml).map_err(|err| crate::Error::InvalidDefinition(err.into()))
+}
+
+fn from_file<T: DeserializeOwned>(path: &Path) -> crate::Result<T> {
+    let contents = fs::read_to_string(path).map_err(crate::Error::UnexpectedIoError)?;
+    match path.extension().and_then(|extension| extension.to_str()) {
+        Some("json") => from_json_str(&contents),
+        #[cfg(feature = "toml")]
+        Some("toml") => from_toml_str(&contents),
+        _ => Err(crate::Error::InvalidDefinition(
+            format!("unsupported file extension of {}", path.display()).into(),
+        )),
+    }
+}
+
+/// Deserialize an optional duration given in seconds.
+pub(super) fn seconds<'de, D>(deserializer: D) -> Result<Option<Duration>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Option::<f64>::deserialize(deserializer)?
+        .map(|seconds| Duration::try_from_secs_f64(seconds).map_err(serde::de::Error::custom))
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "calendar")]
+    use crate::dialog::Month;
+    use std::path::PathBuf;
+
+    fn fixture(name: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures")
+            .join(name)
+    }
+
+    #[cfg(all(feature = "toml", feature = "info"))]
+    #[test]
+    fn loads_an_info_dialog_from_toml() {
+        let toml = include_str!("../../tests/fixtures/backup.toml");
+        let dialog = ZenityDialog::<Info>::from_toml_str(toml).unwrap();
+
+        assert_eq!(
+            dialog,
+            ZenityDialog::new(Info::new().with_text("The backup is done.").with_no_wrap())
+                .with_title("Backup")
+                .with_timeout(Duration::from_secs(30))
+        );
+    }
+
+    #[cfg(feature = "entry")]
+    #[test]
+    fn loads_an_entry_dialog_from_json() {
+        let json = include_str!("../../tests/fixtures/name.json");
+        let dialog = ZenityDialog::<Entry>::from_json_str(json).unwrap();
+
+        assert_eq!(
+            dialog,
+            ZenityDialog::new(Entry::new().with_text("Your name?").with_entry_text("Ada"))
+                .with_title("Sign in")
+                .with_width(400)
+        );
+    }
+
+    #[cfg(all(feature = "toml", feature = "list"))]
+    #[test]
+    fn loads_a_list_dialog_of_the_type_named_in_the_file() {
+        let spec = DialogSpec::from_file(fixture("colors.toml")).unwrap();
+        let expected = ZenityDialog::new(
+            List::new()
+                .with_text("Pick a color")
+                .with_column("Color")
+                .with_row(["Red"])
+                .with_row(["Green"]),
+        )
+        .with_title("Colors")
+        .with_modal();
+        assert!(
+            matches!(spec, DialogSpec::List(ref dialog) if *dialog == expected),
+            "{:?}",
+            spec
+        );
+    }
+
+    #[cfg(feature = "calendar")]
+    #[test]
+    fn loads_a_calendar_dialog_of_the_type_named_in_the_file() {
+        let spec = DialogSpec::from_file(fixture("birthday.json")).unwrap();
+        let expected = ZenityDialog::new(
+            Calendar::new()
+                .with_text("Your birthday?")
+                .with_day(10usize)
+                .with_month(Month::December)
+                .with_year(1815isize),
+        );
+        assert!(
+            matches!(spec, DialogSpec::Calendar(ref dialog) if *dialog == expected),
+            "{:?}",
+            spec
+        );
+    }
+
+    #[cfg(all(feature = "toml", feature = "question"))]
+    #[test]
+    fn loads_a_question_dialog_with_a_fractional_timeout() {
+        let spec = DialogSpec::from_file(fixture("quit.toml")).unwrap();
+        let expected = ZenityDialog::new(
+            Question::new()
+                .with_text("Quit without saving?")
+                .with_ok_label("Quit")
+                .with_cancel_label("Keep editing"),
+        )
+        .with_hard_timeout(Duration::from_millis(1500));
+        assert!(
+            matches!(spec, DialogSpec::Question(ref dialog) if *dialog == expected),
+            "{:?}",
+            spec
+        );
+    }
+
+    #[cfg(feature = "info")]
+    #[test]
+    fn an_unknown_field_is_rejected_with_its_path() {
+        let err = ZenityDialog::<Info>::from_json_str(r#"{ "application": { "txt": "Done" } }"#)
+            .unwrap_err();
+        let message = err.to_string();
+        assert!(
+            matches!(err, crate::Error::InvalidDefinition(_)),
+            "{:?}",
+            err
+        );
+        assert!(message.contains("application.txt"), "{}", message);
+        assert!(message.contains("unknown field `txt`"), "{}", message);
+
+        let err = ZenityDialog::<Info>::from_json_str(r#"{ "titel": "Backup" }"#).unwrap_err();
+        assert!(err.to_string().contains("unknown field `titel`"), "{}", err);
+    }
+
+    #[cfg(all(feature = "toml", feature = "info"))]
+    #[test]
+    fn an_unknown_field_in_toml_is_rejected() {
+        let err = DialogSpec::from_toml_str("type = \"info\"\n[application]\ntxt = \"Done\"\n")
+            .unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("unknown field `txt`"), "{}", message);
+    }
+
+    #[test]
+    fn an_unknown_type_is_rejected() {
+        let err = DialogSpec::from_json_str(r#"{ "type": "slider" }"#).unwrap_err();
+        assert!(err.to_string().contains("slider"), "{}", err);
+    }
+
+    #[test]
+    fn an_unknown_extension_is_rejected() {
+        let readme = Path::new(env!("CARGO_MANIFEST_DIR")).join("README.md");
+        let err = DialogSpec::from_file(readme).unwrap_err();
+        assert!(
+            err.to_string().contains("unsupported file extension"),
+            "{}",
+            err
+        );
+    }
+}
@@ -0,0 +1,1681 @@
+//! Tests of the arguments dialogs pass to Zenity and of how its output is classified, run
+//! through a [MockRunner] or against the [TestZenity] stub instead of Zenity.
+
+#[cfg(feature = "calendar")]
+use super::Calendar;
+#[cfg(any(
+    feature = "calendar",
+    feature = "entry",
+    feature = "file-selection",
+    feature = "list"
+))]
+use super::DialogOutput;
+#[cfg(feature = "entry")]
+use super::Entry;
+#[cfg(feature = "error")]
+use super::Error as ErrorDialog;
+#[cfg(feature = "list")]
+use super::List;
+#[cfg(feature = "question")]
+use super::Question;
+#[cfg(feature = "warning")]
+use super::Warning;
+#[cfg(any(feature = "entry", feature = "question"))]
+use super::ZenityOutputExtButton;
+#[cfg(feature = "file-selection")]
+use super::{DiffEntry, FileSelection};
+use super::{Info, ZenityApplication, ZenityDialog, ZenityOutput};
+use crate::{
+    harness::{TestScripts, TestZenity},
+    runner::{Invocation, MockResponse, MockRunner},
+    settings::lock_for_test,
+};
+#[cfg(feature = "entry")]
+use std::collections::BTreeMap;
+use std::{ffi::OsString, time::Duration};
+
+/// The arguments as strings, for readable assertions.
+pub(crate) fn args(args: Vec<OsString>) -> Vec<String> {
+    args.into_iter()
+        .map(|arg| arg.into_string().expect("test arguments are UTF-8"))
+        .collect()
+}
+
+#[cfg(feature = "entry")]
+#[test]
+fn to_args_lists_the_application_then_the_dialog_options() {
+    let dialog = ZenityDialog::new(Entry::new().with_text("Name?"))
+        .with_title("Welcome")
+        .with_width(300)
+        .with_height(200)
+        .with_timeout(Duration::from_secs(30))
+        .with_modal();
+
+    assert_eq!(
+        args(dialog.to_args()),
+        [
+            "--entry",
+            "--text=Name?",
+            "--title=Welcome",
+            "--width=300",
+            "--height=200",
+            "--timeout=30",
+            "--modal",
+        ]
+    );
+}
+
+#[test]
+fn to_args_uses_the_default_title() {
+    let dialog = ZenityDialog::new(Info::new().with_text("Done"));
+    assert_eq!(
+        args(dialog.to_args()),
+        ["--info", "--text=Done", "--title=Information"]
+    );
+}
+
+#[test]
+fn setters_configure_a_dialog_conditionally_without_rebinding() {
+    for compact in [false, true] {
+        let mut dialog = ZenityDialog::new(Info::new().with_text("Done")).force_legacy_flags();
+        if compact {
+            dialog
+                .set_title("Backup")
+                .set_width(200)
+                .set_icon("dialog-information");
+        }
+
+        let expected = match compact {
+            true => vec![
+                "--info",
+                "--text=Done",
+                "--title=Backup",
+                "--icon-name=dialog-information",
+                "--width=200",
+            ],
+            false => vec!["--info", "--text=Done", "--title=Information"],
+        };
+        assert_eq!(args(dialog.to_args()), expected);
+    }
+
+    let mut ext = ZenityDialog::new(Info::new())
+        .force_legacy_flags()
+        .with_extra_button("Later");
+    ext.set_title("Backup").set_width(200);
+    assert_eq!(
+        args(ext.to_args()),
+        [
+            "--info",
+            "--title=Backup",
+            "--width=200",
+            "--extra-button=Later"
+        ]
+    );
+}
+
+#[test]
+fn every_valued_builder_has_an_in_place_counterpart() {
+    let built = ZenityDialog::new(Info::new())
+        .with_env_remove("PATH")
+        .with_display(":1")
+        .with_additional_args([("ok-label", "Go"), ("width", "100")])
+        .with_arg_override(("width", "300"));
+
+    let mut set = ZenityDialog::new(Info::new());
+    set.set_env_remove("PATH")
+        .set_display(":1")
+        .extend_additional_args([("ok-label", "Go"), ("width", "100")])
+        .set_arg_override(("width", "300"));
+
+    assert_eq!(built, set);
+}
+
+/// A stub that prints its environment as its answer.
+#[cfg(feature = "entry")]
+const ENV_DUMP: &str =
+    "#!/bin/sh\ncase \"$1\" in --version|--help-all) exit 0 ;; esac\n/usr/bin/env\n";
+
+/// The environment the dialog's Zenity process received, shown through the [ENV_DUMP] stub.
+#[cfg(feature = "entry")]
+fn child_env(scripts: &TestScripts, dialog: ZenityDialog<Entry>) -> BTreeMap<String, String> {
+    let program = scripts.add("zenity", ENV_DUMP).unwrap();
+    let answer = dialog
+        .with_program(program)
+        .allow_headless()
+        .show()
+        .unwrap()
+        .content()
+        .unwrap_or_default();
+    parse_env(&answer.text)
+}
+
+#[cfg(feature = "entry")]
+fn parse_env(dump: &str) -> BTreeMap<String, String> {
+    dump.lines()
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| (key.to_owned(), value.to_owned()))
+        .collect()
+}
+
+/// The title argument of a dialog for the application, if any.
+fn title_arg<T: ZenityApplication>(application: T) -> Option<String> {
+    args(ZenityDialog::new(application).to_args())
+        .into_iter()
+        .find(|arg| arg.starts_with("--title="))
+}
+
+#[test]
+fn every_application_has_its_own_default_title() {
+    #[cfg(feature = "calendar")]
+    assert_eq!(title_arg(Calendar::new()).unwrap(), "--title=Calendar");
+    #[cfg(feature = "entry")]
+    assert_eq!(title_arg(Entry::new()).unwrap(), "--title=Entry");
+    #[cfg(feature = "error")]
+    assert_eq!(title_arg(ErrorDialog::new()).unwrap(), "--title=Error");
+    #[cfg(feature = "file-selection")]
+    assert_eq!(
+        title_arg(FileSelection::new()).unwrap(),
+        "--title=Select a file"
+    );
+    assert_eq!(title_arg(Info::new()).unwrap(), "--title=Information");
+    #[cfg(feature = "list")]
+    assert_eq!(title_arg(List::new()).unwrap(), "--title=Select items");
+    #[cfg(feature = "question")]
+    assert_eq!(title_arg(Question::new()).unwrap(), "--title=Question");
+    #[cfg(feature = "warning")]
+    assert_eq!(title_arg(Warning::new()).unwrap(), "--title=Warning");
+}
+
+#[cfg(feature = "question")]
+#[test]
+fn explicit_titles_replace_the_default_title() {
+    let dialog = ZenityDialog::new(Question::new()).with_title("Delete?");
+    assert!(dialog.to_args().contains(&"--title=Delete?".into()));
+    assert!(!dialog.to_args().contains(&"--title=Question".into()));
+
+    // An empty title suppresses the default, leaving the title blank.
+    let dialog = ZenityDialog::new(Question::new()).with_title("");
+    assert!(dialog.to_args().contains(&"--title=".into()));
+    assert!(!dialog.to_args().contains(&"--title=Question".into()));
+}
+
+#[test]
+fn field_map_omits_fields_left_at_their_default() {
+    #[cfg(feature = "entry")]
+    assert!(ZenityDialog::new(Entry::new()).field_map().is_empty());
+    #[cfg(feature = "file-selection")]
+    assert!(ZenityDialog::new(FileSelection::new())
+        .field_map()
+        .is_empty());
+    #[cfg(feature = "list")]
+    assert!(ZenityDialog::new(List::new()).field_map().is_empty());
+    #[cfg(feature = "question")]
+    assert!(ZenityDialog::new(Question::new()).field_map().is_empty());
+    assert!(ZenityDialog::new(Info::new()).field_map().is_empty());
+}
+
+#[cfg(feature = "file-selection")]
+#[test]
+fn diff_reports_enabled_flags_as_added() {
+    let plain = ZenityDialog::new(FileSelection::new());
+    let modal = ZenityDialog::new(FileSelection::new().with_multiple()).with_modal();
+
+    assert_eq!(
+        plain.diff(&modal).entries,
+        [
+            DiffEntry::Added {
+                field: "application.multiple".to_owned(),
+                value: "true".to_owned(),
+            },
+            DiffEntry::Added {
+                field: "modal".to_owned(),
+                value: "true".to_owned(),
+            },
+        ]
+    );
+    assert!(modal.diff(&modal.clone()).is_empty());
+}
+
+#[cfg(feature = "calendar")]
+#[test]
+fn dialogs_configured_alike_are_equal() {
+    let birthday = || {
+        ZenityDialog::new(
+            Calendar::new()
+                .with_day(29usize)
+                .with_month(super::Month::February)
+                .with_year(2024isize),
+        )
+        .with_title("Birthday")
+        .with_width(300)
+    };
+
+    assert_eq!(birthday(), birthday());
+    assert_ne!(birthday(), birthday().with_title("Anniversary"));
+    assert_ne!(birthday(), birthday().with_width(301));
+    assert_ne!(
+        birthday(),
+        ZenityDialog::new(Calendar::new().with_day(1usize))
+            .with_title("Birthday")
+            .with_width(300)
+    );
+
+    let dialogs: std::collections::HashSet<_> =
+        vec![birthday(), birthday(), birthday().with_height(200)]
+            .into_iter()
+            .collect();
+    assert_eq!(dialogs.len(), 2);
+}
+
+#[cfg(feature = "entry")]
+#[test]
+fn dialogs_with_extra_buttons_are_equal_when_their_buttons_are() {
+    let dialog = ZenityDialog::new(Entry::new().with_text("Name?")).with_extra_button("Skip");
+    assert_eq!(dialog.clone(), dialog.clone());
+    assert_ne!(dialog.clone(), dialog.clone().with_extra_button("Later"));
+    assert_ne!(
+        dialog,
+        ZenityDialog::new(Entry::new().with_text("Name?")).with_extra_button("Later")
+    );
+}
+
+#[test]
+fn show_with_runner_passes_the_arguments_to_the_runner() {
+    // The program is read from the installed settings.
+    let _lock = lock_for_test();
+    let dialog = ZenityDialog::new(Info::new().with_text("Done")).with_title("Backup");
+    let runner = MockRunner::new().respond_otherwise(MockResponse::affirmed(""));
+
+    dialog.show_with_runner(&runner).unwrap();
+
+    let invocations = runner.invocations();
+    assert_eq!(
+        invocations,
+        [Invocation {
+            program: dialog.dry_run().program.into(),
+            args: dialog.to_args(),
+        }]
+    );
+    assert!(invocations[0].has_arg("--title=Backup"));
+}
+
+#[cfg(feature = "entry")]
+#[test]
+fn the_same_dialog_can_be_shown_twice_with_identical_arguments() {
+    let dialog = ZenityDialog::new(Entry::new().with_text("Name?"))
+        .with_title("Retry")
+        .with_additional_arg(crate::Arg::with_value("ok-label", "Go"));
+    let runner = MockRunner::new()
+        .respond_to_arg("--entry", MockResponse::rejected())
+        .respond_otherwise(MockResponse::affirmed(""));
+
+    assert_eq!(
+        dialog.show_with_runner(&runner).unwrap(),
+        ZenityOutput::Rejected { content: None }
+    );
+    assert_eq!(
+        dialog.show_with_runner(&runner).unwrap(),
+        ZenityOutput::Rejected { content: None }
+    );
+
+    let invocations = runner.invocations();
+    assert_eq!(invocations.len(), 2);
+    assert_eq!(invocations[0], invocations[1]);
+    assert!(invocations[1].has_arg("--ok-label=Go"));
+    // Showing the dialog leaves its additional arguments in place.
+    assert_eq!(dialog.to_args(), invocations[1].args);
+}
+
+#[test]
+fn dry_run_quotes_nasty_titles_for_the_shell() {
+    let dialog = ZenityDialog::new(Info::new().with_text("Done"))
+        .with_title(r#"it's "quoted" $HOME"#)
+        .with_program("/usr/bin/zenity");
+
+    let dry_run = dialog.dry_run();
+    assert_eq!(dry_run.args, dialog.to_args());
+    assert_eq!(
+        dry_run.to_string(),
+        r#"/usr/bin/zenity --info --text=Done '--title=it'\''s "quoted" $HOME'"#
+    );
+}
+
+#[test]
+fn show_with_runner_classifies_exit_codes() {
+    let cases = [
+        (
+            MockResponse::affirmed("Yes\n"),
+            ZenityOutput::Affirmed {
+                content: Some("Yes".to_owned()),
+            },
+        ),
+        (
+            MockResponse::affirmed(""),
+            ZenityOutput::Affirmed { content: None },
+        ),
+        (
+            MockResponse::rejected(),
+            ZenityOutput::Rejected { content: None },
+        ),
+        (
+            MockResponse::timed_out(),
+            ZenityOutput::TimedOut { content: None },
+        ),
+        (
+            MockResponse::new(42)
+                .with_stdout("partial")
+                .with_stderr("oops"),
+            ZenityOutput::Unknown {
+                exit_code: 42,
+                stdout: "partial".to_owned(),
+                stderr: "oops".to_owned(),
+            },
+        ),
+    ];
+
+    let dialog = ZenityDialog::new(Info::new());
+    for (response, expected) in cases {
+        let exit_code = response.exit_code;
+        let runner = MockRunner::new().respond_otherwise(response);
+        let output = dialog.show_with_runner(&runner).unwrap();
+        assert_eq!(output, expected, "exit code {exit_code}");
+    }
+}
+
+#[test]
+fn show_with_runner_reports_failures() {
+    let runner =
+        MockRunner::new().respond_otherwise(MockResponse::new(255).with_stderr("no display"));
+    let err = ZenityDialog::new(Info::new())
+        .show_with_runner(&runner)
+        .unwrap_err();
+    assert!(
+        matches!(
+            err,
+            crate::Error::ZenityFailed { exit_code: 255, ref stderr } if stderr == "no display"
+        ),
+        "{:?}",
+        err
+    );
+}
+
+#[test]
+fn show_with_runner_validates_before_running() {
+    let runner = MockRunner::new().respond_otherwise(MockResponse::affirmed(""));
+    let err = ZenityDialog::new(Info::new())
+        .with_width(0)
+        .show_with_runner(&runner)
+        .unwrap_err();
+
+    assert!(matches!(err, crate::Error::InvalidDialog(_)), "{:?}", err);
+    assert!(runner.invocations().is_empty());
+}
+
+#[test]
+fn show_with_runner_fails_without_a_response() {
+    let err = ZenityDialog::new(Info::new())
+        .show_with_runner(&MockRunner::new())
+        .unwrap_err();
+    assert!(!matches!(err, crate::Error::InvalidDialog(_)), "{:?}", err);
+}
+
+#[cfg(feature = "entry")]
+#[test]
+fn mock_runner_answers_each_dialog_of_a_flow() {
+    let runner = MockRunner::new()
+        .respond_when(
+            |args| args.iter().any(|arg| arg == "--text=Name?"),
+            MockResponse::affirmed("Alice\n"),
+        )
+        .respond_to_arg("--info", MockResponse::affirmed(""));
+
+    let name = ZenityDialog::new(Entry::new().with_text("Name?"))
+        .show_with_runner(&runner)
+        .unwrap()
+        .content()
+        .unwrap();
+    let greeting = ZenityDialog::new(Info::new().with_text(format!("Hello {}", name.text)))
+        .show_with_runner(&runner)
+        .unwrap();
+
+    assert_eq!(greeting, ZenityOutput::Affirmed { content: None });
+    let invocations = runner.invocations();
+    assert_eq!(invocations.len(), 2);
+    assert!(invocations[1].has_arg("--text=Hello Alice"));
+}
+
+#[test]
+fn modal_is_passed_as_a_bare_flag() {
+    let dialog = ZenityDialog::new(Info::new()).with_modal();
+    assert_eq!(
+        args(dialog.to_args()),
+        ["--info", "--title=Information", "--modal"]
+    );
+}
+
+#[test]
+#[allow(deprecated)]
+fn with_modal_hint_sets_the_flag_and_drops_the_text() {
+    let dialog = ZenityDialog::new(Info::new()).with_modal_hint("Please answer");
+    assert!(dialog.modal);
+    assert_eq!(
+        args(dialog.to_args()),
+        ["--info", "--title=Information", "--modal"]
+    );
+}
+
+#[cfg(feature = "entry")]
+#[test]
+fn show_reports_the_timeout_exit_code_as_timed_out() {
+    let _lock = lock_for_test();
+    let zenity = TestZenity::times_out().unwrap();
+    let dialog = ZenityDialog::new(Entry::new())
+        .with_program(zenity.program())
+        .allow_headless();
+
+    assert_eq!(
+        dialog.show_ref().unwrap(),
+        ZenityOutput::TimedOut { content: None }
+    );
+
+    zenity.set_stdout("half-typed\n").unwrap();
+    assert_eq!(
+        dialog.show_ref().unwrap(),
+        ZenityOutput::TimedOut {
+            content: Some("half-typed".to_owned()),
+        }
+    );
+    assert_eq!(
+        dialog.with_extra_button("Later").show().unwrap(),
+        ZenityOutputExtButton::TimedOut {
+            content: Some("half-typed".to_owned()),
+        }
+    );
+}
+
+#[cfg(feature = "entry")]
+#[test]
+fn show_classifies_the_exit_codes_of_the_stub() {
+    let _lock = lock_for_test();
+    let zenity = TestZenity::new(0).unwrap();
+    zenity.set_stdout("answer\n").unwrap();
+    zenity.set_stderr("details\n").unwrap();
+    let dialog = ZenityDialog::new(Entry::new())
+        .with_program(zenity.program())
+        .allow_headless();
+
+    let content = Some("answer".to_owned());
+    for (exit_code, expected) in [
+        (
+            0,
+            ZenityOutput::Affirmed {
+                content: content.clone(),
+            },
+        ),
+        (
+            1,
+            ZenityOutput::Rejected {
+                content: content.clone(),
+            },
+        ),
+        (
+            5,
+            ZenityOutput::TimedOut {
+                content: content.clone(),
+            },
+        ),
+        (
+            42,
+            ZenityOutput::Unknown {
+                exit_code: 42,
+                stdout: "answer".to_owned(),
+                stderr: "details\n".to_owned(),
+            },
+        ),
+    ] {
+        zenity.set_exit_code(exit_code).unwrap();
+        let output = dialog.show_ref().unwrap().map(|answer| answer.text);
+        assert_eq!(output, expected, "exit code {exit_code}");
+    }
+
+    zenity.set_exit_code(255).unwrap();
+    let err = dialog.show_ref().unwrap_err();
+    assert!(
+        matches!(
+            err,
+            crate::Error::ZenityFailed { exit_code: 255, ref stderr } if stderr == "details\n"
+        ),
+        "{:?}",
+        err
+    );
+}
+
+#[test]
+fn show_reports_the_signal_that_killed_zenity() {
+    let scripts = TestScripts::new().unwrap();
+    let program = scripts
+        .add(
+            "zenity",
+            "#!/bin/sh\necho 'about to crash' >&2\nkill -KILL $$\n",
+        )
+        .unwrap();
+
+    let err = ZenityDialog::new(Info::new())
+        .with_program(program)
+        .force_legacy_flags()
+        .allow_headless()
+        .show()
+        .unwrap_err();
+    assert!(
+        matches!(
+            err,
+            crate::Error::TerminatedBySignal { signal: 9, ref stderr } if stderr == "about to crash\n"
+        ),
+        "{:?}",
+        err
+    );
+}
+
+#[cfg(feature = "file-selection")]
+#[test]
+fn paths_that_arent_utf8_reach_zenity_unchanged() {
+    use std::{os::unix::ffi::OsStringExt, path::PathBuf};
+
+    let _lock = lock_for_test();
+    let zenity = TestZenity::affirms_with("").unwrap();
+    let filename = PathBuf::from(OsString::from_vec(b"/tmp/caf\xe9.txt".to_vec()));
+    let icon = PathBuf::from(OsString::from_vec(b"/tmp/ic\xf4ne.png".to_vec()));
+    let dialog = ZenityDialog::new(FileSelection::new().with_filename(&filename))
+        .with_icon(icon)
+        .with_program(zenity.program())
+        .allow_headless();
+
+    dialog.show_ref().unwrap();
+
+    let args = zenity.last_args_os().unwrap();
+    assert_eq!(args, dialog.to_args());
+    assert!(args.contains(&OsString::from_vec(b"--filename=/tmp/caf\xe9.txt".to_vec())));
+    assert!(args.contains(&OsString::from_vec(
+        b"--icon-name=/tmp/ic\xf4ne.png".to_vec()
+    )));
+}
+
+#[cfg(feature = "entry")]
+#[test]
+fn the_installed_program_is_used_by_default() {
+    let _lock = lock_for_test();
+    let zenity = TestZenity::affirms_with("installed\n").unwrap();
+
+    let dialog = ZenityDialog::new(Entry::new()).allow_headless();
+    assert_eq!(dialog.dry_run().program, zenity.program());
+    assert_eq!(
+        dialog.show_ref().unwrap().content().unwrap().text,
+        "installed"
+    );
+    assert_eq!(zenity.last_args().unwrap(), args(dialog.to_args()));
+}
+
+#[cfg(feature = "entry")]
+#[test]
+fn with_program_overrides_the_installed_program() {
+    let _lock = lock_for_test();
+    let installed = TestZenity::affirms_with("installed\n").unwrap();
+    let scripts = TestScripts::new().unwrap();
+    let bundled = scripts.add("zenity", "#!/bin/sh\necho bundled\n").unwrap();
+
+    let dialog = ZenityDialog::new(Entry::new())
+        .with_program(&bundled)
+        .force_legacy_flags()
+        .allow_headless();
+    assert_eq!(dialog.dry_run().program, bundled);
+    assert_eq!(
+        dialog.show_ref().unwrap().content().unwrap().text,
+        "bundled"
+    );
+    assert!(installed.last_args().unwrap().is_empty());
+}
+
+#[test]
+fn a_bogus_program_is_reported_as_not_installed_with_its_path() {
+    let bogus = "/nonexistent/bin/zenity-bogus";
+    let err = ZenityDialog::new(Info::new())
+        .with_program(bogus)
+        .allow_headless()
+        .show()
+        .unwrap_err();
+
+    assert!(err.to_string().contains(bogus), "{}", err);
+    assert!(
+        matches!(
+            err,
+            crate::Error::ZenityNotInstalled { ref program, .. } if program.as_os_str() == bogus
+        ),
+        "{:?}",
+        err
+    );
+}
+
+#[test]
+fn spawn_errors_show_the_attempted_command_line() {
+    let err = ZenityDialog::new(Info::new().with_text("Disk full"))
+        .with_program("/nonexistent/bin/zenity")
+        .force_legacy_flags()
+        .allow_headless()
+        .show()
+        .unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        "Zenity is not installed: /nonexistent/bin/zenity was not found, running \
+         `/nonexistent/bin/zenity --info '--text=Disk full' --title=Information`"
+    );
+}
+
+#[test]
+fn a_program_without_the_executable_bit_isnt_executable() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let scripts = TestScripts::new().unwrap();
+    let program = scripts.add("zenity", "#!/bin/sh\nexit 0\n").unwrap();
+    std::fs::set_permissions(&program, std::fs::Permissions::from_mode(0o644)).unwrap();
+
+    let err = ZenityDialog::new(Info::new())
+        .with_program(&program)
+        .force_legacy_flags()
+        .allow_headless()
+        .show()
+        .unwrap_err();
+    assert!(
+        matches!(err, crate::Error::ZenityNotExecutable { program: ref attempted, .. } if attempted == &program),
+        "{:?}",
+        err
+    );
+}
+
+#[test]
+fn a_program_in_an_unknown_format_isnt_a_valid_executable() {
+    let scripts = TestScripts::new().unwrap();
+    // Neither a `#!` line nor a binary format the kernel knows.
+    let program = scripts.add("zenity", "\u{7f}ELG garbage\n").unwrap();
+
+    let err = ZenityDialog::new(Info::new())
+        .with_program(&program)
+        .force_legacy_flags()
+        .allow_headless()
+        .show()
+        .unwrap_err();
+    assert!(
+        matches!(err, crate::Error::InvalidExecutable { program: ref attempted, .. } if attempted == &program),
+        "{:?}",
+        err
+    );
+}
+
+#[test]
+fn failures_show_what_zenity_printed_to_stderr() {
+    let scripts = TestScripts::new().unwrap();
+    let program = scripts
+        .add(
+            "zenity",
+            "#!/bin/sh\necho 'cannot open display: :1' >&2\nexit 255\n",
+        )
+        .unwrap();
+
+    let err = ZenityDialog::new(Info::new())
+        .with_program(program)
+        .force_legacy_flags()
+        .allow_headless()
+        .show()
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Zenity failed with exit code 255: cannot open display: :1\n"
+    );
+}
+
+#[cfg(feature = "entry")]
+#[test]
+fn with_env_sets_variables_for_zenity_only() {
+    let scripts = TestScripts::new().unwrap();
+    let env = child_env(
+        &scripts,
+        ZenityDialog::new(Entry::new())
+            .with_env("LANG", "de_DE.UTF-8")
+            .with_env("ZENITY_DIALOG_TEST", "a value with = and spaces"),
+    );
+
+    assert_eq!(env["LANG"], "de_DE.UTF-8");
+    assert_eq!(env["ZENITY_DIALOG_TEST"], "a value with = and spaces");
+    // Everything else is inherited.
+    assert_eq!(env.get("PATH"), std::env::var("PATH").ok().as_ref());
+    assert!(std::env::var_os("ZENITY_DIALOG_TEST").is_none());
+}
+
+#[cfg(feature = "entry")]
+#[test]
+fn with_env_remove_hides_inherited_variables() {
+    let scripts = TestScripts::new().unwrap();
+    assert!(std::env::var_os("PATH").is_some());
+
+    let env = child_env(
+        &scripts,
+        ZenityDialog::new(Entry::new()).with_env_remove("PATH"),
+    );
+    assert!(!env.contains_key("PATH"), "{:?}", env);
+}
+
+#[cfg(feature = "entry")]
+#[test]
+fn with_env_clear_passes_only_the_variables_set() {
+    let scripts = TestScripts::new().unwrap();
+    // Set before and after clearing, which must not matter.
+    let dialog = ZenityDialog::new(Entry::new())
+        .with_env("GDK_BACKEND", "x11")
+        .with_env_clear()
+        .with_env("DISPLAY", ":7");
+
+    let env = child_env(&scripts, dialog);
+    // The shell may export a few variables of its own, such as PWD.
+    let passed: BTreeMap<_, _> = env
+        .into_iter()
+        .filter(|(key, _)| !["PWD", "SHLVL", "_", "OLDPWD"].contains(&key.as_str()))
+        .collect();
+    assert_eq!(
+        passed,
+        [("DISPLAY", ":7"), ("GDK_BACKEND", "x11")]
+            .map(|(key, value)| (key.to_owned(), value.to_owned()))
+            .into()
+    );
+}
+
+#[cfg(feature = "entry")]
+#[test]
+fn ext_button_dialogs_pass_the_environment_through() {
+    let scripts = TestScripts::new().unwrap();
+    let program = scripts.add("zenity", ENV_DUMP).unwrap();
+
+    let output = ZenityDialog::new(Entry::new())
+        .with_extra_button("Later")
+        .with_env("LANG", "fr_FR.UTF-8")
+        .with_env_remove("PATH")
+        .with_program(program)
+        .allow_headless()
+        .show()
+        .unwrap();
+    let ZenityOutputExtButton::Affirmed {
+        content: Some(answer),
+    } = output
+    else {
+        panic!("unexpected output: {:?}", output);
+    };
+
+    let env = parse_env(&answer.text);
+    assert_eq!(env["LANG"], "fr_FR.UTF-8");
+    assert!(!env.contains_key("PATH"));
+}
+
+#[cfg(feature = "entry")]
+#[test]
+fn prefer_dark_sets_the_dark_theme_variables() {
+    let scripts = TestScripts::new().unwrap();
+    let env = child_env(&scripts, ZenityDialog::new(Entry::new()).prefer_dark());
+
+    assert_eq!(env["GTK_THEME"], "Adwaita:dark");
+    assert_eq!(env["ADW_DEBUG_COLOR_SCHEME"], "prefer-dark");
+    assert_eq!(env["GTK_APPLICATION_PREFER_DARK_THEME"], "1");
+}
+
+#[cfg(feature = "entry")]
+#[test]
+fn gtk_theme_composes_with_prefer_dark() {
+    let scripts = TestScripts::new().unwrap();
+
+    let env = child_env(
+        &scripts,
+        ZenityDialog::new(Entry::new()).with_gtk_theme("Yaru"),
+    );
+    assert_eq!(env["GTK_THEME"], "Yaru");
+    assert!(!env.contains_key("ADW_DEBUG_COLOR_SCHEME"));
+
+    let dialog = ZenityDialog::new(Entry::new())
+        .with_gtk_theme("Yaru")
+        .prefer_dark();
+    assert_eq!(child_env(&scripts, dialog)["GTK_THEME"], "Yaru:dark");
+
+    // A theme that names its variant is kept as is.
+    let dialog = ZenityDialog::new(Entry::new())
+        .with_gtk_theme("Yaru:light")
+        .prefer_dark();
+    assert_eq!(child_env(&scripts, dialog)["GTK_THEME"], "Yaru:light");
+}
+
+#[cfg(feature = "entry")]
+#[test]
+fn explicit_variables_take_precedence_over_the_theme() {
+    let scripts = TestScripts::new().unwrap();
+    let dialog = ZenityDialog::new(Entry::new())
+        .with_env("GTK_THEME", "HighContrast")
+        .with_env_remove("GTK_APPLICATION_PREFER_DARK_THEME")
+        .prefer_dark();
+
+    let env = child_env(&scripts, dialog);
+    assert_eq!(env["GTK_THEME"], "HighContrast");
+    assert_eq!(env["ADW_DEBUG_COLOR_SCHEME"], "prefer-dark");
+    assert!(!env.contains_key("GTK_APPLICATION_PREFER_DARK_THEME"));
+}
+
+#[cfg(feature = "entry")]
+#[test]
+fn theme_hints_leave_the_parent_environment_untouched() {
+    let scripts = TestScripts::new().unwrap();
+    let keys = [
+        "GTK_THEME",
+        "ADW_DEBUG_COLOR_SCHEME",
+        "GTK_APPLICATION_PREFER_DARK_THEME",
+    ];
+    let before: Vec<_> = keys.iter().map(std::env::var_os).collect();
+
+    let env = child_env(
+        &scripts,
+        ZenityDialog::new(Entry::new())
+            .with_gtk_theme("Adwaita")
+            .prefer_dark(),
+    );
+    assert_eq!(env["GTK_THEME"], "Adwaita:dark");
+
+    let after: Vec<_> = keys.iter().map(std::env::var_os).collect();
+    assert_eq!(before, after);
+}
+
+#[cfg(feature = "file-selection")]
+#[test]
+fn relative_paths_are_resolved_against_the_current_dir() {
+    let dialog = ZenityDialog::new(FileSelection::new().with_filename("reports/"))
+        .with_icon(std::path::PathBuf::from("icons/app.png"))
+        .with_current_dir("/srv/data");
+
+    let args = args(dialog.to_args());
+    assert!(
+        args.contains(&"--filename=/srv/data/reports/".to_owned()),
+        "{:?}",
+        args
+    );
+    assert!(args.contains(&"--icon-name=/srv/data/icons/app.png".to_owned()));
+    // The application itself keeps the relative path.
+    assert_eq!(
+        dialog.application.filename.as_deref(),
+        Some(std::path::Path::new("reports/"))
+    );
+}
+
+#[cfg(feature = "file-selection")]
+#[test]
+fn absolute_paths_and_paths_without_a_current_dir_are_kept() {
+    let dialog = ZenityDialog::new(FileSelection::new().with_filename("/etc/hosts"))
+        .with_current_dir("/srv/data");
+    assert!(dialog.to_args().contains(&"--filename=/etc/hosts".into()));
+
+    let dialog = ZenityDialog::new(FileSelection::new().with_filename("notes.txt"));
+    assert!(dialog.to_args().contains(&"--filename=notes.txt".into()));
+}
+
+#[cfg(feature = "file-selection")]
+#[test]
+fn a_relative_current_dir_is_resolved_against_this_process() {
+    let dialog =
+        ZenityDialog::new(FileSelection::new().with_filename("notes.txt")).with_current_dir("docs");
+
+    let mut expected = OsString::from("--filename=");
+    expected.push(std::env::current_dir().unwrap().join("docs/notes.txt"));
+    assert!(dialog.to_args().contains(&expected));
+}
+
+#[cfg(feature = "file-selection")]
+#[test]
+fn zenity_runs_in_the_current_dir() {
+    let scripts = TestScripts::new().unwrap();
+    let program = scripts
+        .add(
+            "zenity",
+            "#!/bin/sh\ncase \"$1\" in --version|--help-all) exit 0 ;; esac\npwd -P\n",
+        )
+        .unwrap();
+    let current_dir = scripts.dir().canonicalize().unwrap();
+
+    let selected = ZenityDialog::new(FileSelection::new().with_filename("draft.txt"))
+        .with_current_dir(&current_dir)
+        .with_program(program)
+        .allow_headless()
+        .show()
+        .unwrap()
+        .content()
+        .unwrap();
+    assert_eq!(selected, [current_dir]);
+}
+
+/// A stub that lists only a few options, and counts how often it's asked for them in the
+/// `probes` file next to it.
+#[cfg(feature = "entry")]
+const LIMITED_HELP: &str = "#!/bin/sh
+case \"$1\" in
+--version) echo 3.44.0; exit 0 ;;
+--help-all)
+    echo probed >> \"$(dirname \"$0\")/probes\"
+    printf '  --entry\\n  --title=TITLE\\n  --text=TEXT\\n'
+    exit 0 ;;
+esac
+echo answer
+";
+
+#[cfg(feature = "entry")]
+#[test]
+fn capabilities_are_only_checked_for_zenity() {
+    let scripts = TestScripts::new().unwrap();
+    let zenity = scripts.add("zenity", LIMITED_HELP).unwrap();
+    let qarma = scripts.add("qarma", LIMITED_HELP).unwrap();
+    let dialog = ZenityDialog::new(Entry::new())
+        .with_width(300)
+        .allow_headless();
+
+    let err = dialog.clone().with_program(zenity).show().unwrap_err();
+    assert!(
+        matches!(err, crate::Error::UnsupportedOption { ref options, .. } if options == &["--width"]),
+        "{:?}",
+        err
+    );
+
+    let answer = dialog
+        .show_with_backend(&crate::backend::ZenityBackend::compatible(qarma))
+        .unwrap();
+    assert_eq!(answer.content().unwrap().text, "answer");
+
+    // Only Zenity itself was probed.
+    let probes = std::fs::read_to_string(scripts.dir().join("probes")).unwrap();
+    assert_eq!(probes.lines().count(), 1);
+}
+
+#[cfg(feature = "entry")]
+#[test]
+fn capabilities_are_checked_for_zenity_under_any_name() {
+    let scripts = TestScripts::new().unwrap();
+    let wrapper = scripts.add("my-dialogs", LIMITED_HELP).unwrap();
+    let link = scripts.dir().join("dlg");
+    std::os::unix::fs::symlink(&wrapper, &link).unwrap();
+    let dialog = ZenityDialog::new(Entry::new())
+        .with_width(300)
+        .allow_headless();
+
+    for program in [wrapper, link] {
+        let err = dialog.clone().with_program(program).show().unwrap_err();
+        assert!(
+            matches!(err, crate::Error::UnsupportedOption { ref options, .. } if options == &["--width"]),
+            "{:?}",
+            err
+        );
+    }
+
+    // Each program is probed once, however often it's shown.
+    let probes = std::fs::read_to_string(scripts.dir().join("probes")).unwrap();
+    assert_eq!(probes.lines().count(), 2);
+    let _ = dialog
+        .clone()
+        .with_program(scripts.dir().join("my-dialogs"))
+        .show();
+    let probes = std::fs::read_to_string(scripts.dir().join("probes")).unwrap();
+    assert_eq!(probes.lines().count(), 2);
+}
+
+#[cfg(feature = "question")]
+#[test]
+fn ellipsizing_a_question_fails_with_a_zenity_without_it() {
+    let scripts = TestScripts::new().unwrap();
+    let zenity = scripts
+        .add(
+            "zenity",
+            "#!/bin/sh
+case \"$1\" in
+--version) echo 4.0.1; exit 0 ;;
+--help-all) printf '  --question\\n  --title=TITLE\\n  --text=TEXT\\n  --no-wrap\\n'; exit 0 ;;
+esac
+",
+        )
+        .unwrap();
+    let question = Question::new().with_text("Quit?").with_no_wrap();
+
+    let dialog = ZenityDialog::new(question.clone().with_ellipsize())
+        .with_program(&zenity)
+        .allow_headless();
+    let err = dialog.show().unwrap_err();
+    assert!(
+        matches!(err, crate::Error::UnsupportedOption { ref options, .. } if options == &["--ellipsize"]),
+        "{:?}",
+        err
+    );
+
+    let dialog = ZenityDialog::new(question)
+        .with_program(zenity)
+        .allow_headless();
+    assert_eq!(
+        dialog.show().unwrap(),
+        ZenityOutput::Affirmed { content: None }
+    );
+}
+
+#[cfg(feature = "entry")]
+#[test]
+fn failed_capability_probes_are_not_repeated() {
+    let scripts = TestScripts::new().unwrap();
+    let program = scripts
+        .add(
+            "zenity",
+            "#!/bin/sh
+case \"$1\" in
+--version) echo 3.44.0; exit 0 ;;
+--help-all) echo probed >> \"$(dirname \"$0\")/probes\"; printf '\\377'; exit 0 ;;
+esac
+echo answer
+",
+        )
+        .unwrap();
+    let dialog = ZenityDialog::new(Entry::new())
+        .with_program(program)
+        .allow_headless();
+
+    for _ in 0..3 {
+        assert_eq!(dialog.show_ref().unwrap().content().unwrap().text, "answer");
+    }
+    let probes = std::fs::read_to_string(scripts.dir().join("probes")).unwrap();
+    assert_eq!(probes.lines().count(), 1);
+}
+
+#[test]
+fn describing_a_dialog_doesnt_run_zenity() {
+    let scripts = TestScripts::new().unwrap();
+    let program = scripts
+        .add(
+            "zenity",
+            "#!/bin/sh
+case \"$1\" in
+--version) echo probed >> \"$(dirname \"$0\")/probes\"; echo 4.0.1; exit 0 ;;
+--help-all) exit 0 ;;
+esac
+printf '%s\\n' \"$@\" > \"$(dirname \"$0\")/args\"
+",
+        )
+        .unwrap();
+    let dialog = ZenityDialog::new(Info::new())
+        .with_icon("dialog-warning")
+        .with_program(program)
+        .allow_headless();
+
+    // Until the version is known, the legacy names are described, which Zenity 4 accepts.
+    assert!(dialog
+        .to_args()
+        .contains(&"--icon-name=dialog-warning".into()));
+    assert!(dialog
+        .dry_run()
+        .args
+        .contains(&"--icon-name=dialog-warning".into()));
+    assert!(dialog.to_string().ends_with("--icon-name=dialog-warning"));
+    assert!(dialog.conflicts().is_empty());
+    assert!(!scripts.dir().join("probes").exists());
+
+    dialog.show_ref().unwrap();
+    let executed = std::fs::read_to_string(scripts.dir().join("args")).unwrap();
+    assert!(executed.lines().any(|arg| arg == "--icon=dialog-warning"));
+    let probes = std::fs::read_to_string(scripts.dir().join("probes")).unwrap();
+    assert_eq!(probes.lines().count(), 1);
+
+    // Once it's known, the descriptions follow it.
+    assert!(dialog.to_args().contains(&"--icon=dialog-warning".into()));
+}
+
+#[test]
+fn every_description_of_the_dialog_matches_what_zenity_4_receives() {
+    let _lock = lock_for_test();
+    let zenity = TestZenity::affirms_with("").unwrap();
+    zenity.set_version("4.0.1").unwrap();
+    let dialog = ZenityDialog::new(Info::new())
+        .with_icon("dialog-warning")
+        .with_program(zenity.program())
+        .allow_headless();
+
+    dialog.show_ref().unwrap();
+
+    let executed = zenity.last_args_os().unwrap();
+    assert!(executed.contains(&"--icon=dialog-warning".into()));
+    assert_eq!(dialog.to_args(), executed);
+    assert_eq!(dialog.dry_run().args, executed);
+    assert!(dialog
+        .to_command_string()
+        .ends_with("--info --title=Information --icon=dialog-warning"));
+}
+
+#[test]
+fn every_description_of_an_ext_button_dialog_matches_what_zenity_4_receives() {
+    let _lock = lock_for_test();
+    let zenity = TestZenity::affirms_with("").unwrap();
+    zenity.set_version("4.0.1").unwrap();
+    let dialog = ZenityDialog::new(Info::new())
+        .with_icon("dialog-warning")
+        .with_program(zenity.program())
+        .allow_headless()
+        .with_extra_button("Later");
+
+    dialog.show_ref().unwrap();
+
+    let executed = zenity.last_args_os().unwrap();
+    assert!(executed.contains(&"--icon=dialog-warning".into()));
+    assert_eq!(dialog.to_args(), executed);
+    assert_eq!(dialog.dry_run().args, executed);
+    assert!(dialog
+        .to_command_string()
+        .ends_with("--icon=dialog-warning --extra-button=Later"));
+}
+
+#[cfg(feature = "entry")]
+#[test]
+fn a_hidden_entry_is_shown_with_its_secret_but_displayed_without_it() {
+    let _lock = lock_for_test();
+    let zenity = TestZenity::affirms_with("hunter2\n").unwrap();
+    let entry = Entry::new()
+        .with_text("Password?")
+        .with_entry_text("hunter2")
+        .with_hide_text();
+    let dialog = ZenityDialog::new(entry)
+        .with_program(zenity.program())
+        .allow_headless();
+
+    dialog.show_ref().unwrap();
+    assert!(zenity
+        .last_args()
+        .unwrap()
+        .contains(&"--entry-text=hunter2".to_owned()));
+
+    let displayed = dialog.to_string();
+    assert!(!displayed.contains("hunter2"), "{}", displayed);
+    assert!(displayed.contains("'--entry-text=***'"), "{}", displayed);
+
+    let dialog = dialog.with_extra_button("Forgot");
+    dialog.show_ref().unwrap();
+    let displayed = dialog.to_string();
+    assert!(!displayed.contains("hunter2"), "{}", displayed);
+    assert!(
+        displayed.ends_with("'--entry-text=***' --hide-text --title=Entry --extra-button=Forgot"),
+        "{}",
+        displayed
+    );
+}
+
+#[test]
+fn show_with_backend_uses_the_option_names_of_the_backend_program() {
+    let _lock = lock_for_test();
+    let installed = TestZenity::affirms_with("").unwrap();
+    let backend = TestZenity::affirms_with("").unwrap();
+    backend.set_version("4.0.1").unwrap();
+    let dialog = ZenityDialog::new(Info::new())
+        .with_icon("dialog-warning")
+        .with_program(installed.program())
+        .allow_headless();
+
+    dialog
+        .show_with_backend(&crate::backend::ZenityBackend::with_program(
+            backend.program(),
+        ))
+        .unwrap();
+
+    assert!(dialog
+        .to_args()
+        .contains(&"--icon-name=dialog-warning".into()));
+    assert!(backend
+        .last_args()
+        .unwrap()
+        .contains(&"--icon=dialog-warning".to_owned()));
+}
+
+#[test]
+fn overriding_an_option_replaces_it_under_its_other_name() {
+    let _lock = lock_for_test();
+    for version in ["3.44.0", "4.0.1"] {
+        let zenity = TestZenity::affirms_with("").unwrap();
+        zenity.set_version(version).unwrap();
+        let dialog = ZenityDialog::new(Info::new())
+            .with_icon("dialog-warning")
+            .with_arg_override(("icon", "dialog-error"))
+            .with_program(zenity.program())
+            .allow_headless();
+
+        let icons: Vec<_> = args(dialog.to_args())
+            .into_iter()
+            .filter(|arg| arg.starts_with("--icon"))
+            .collect();
+        assert_eq!(icons.len(), 1, "{}: {:?}", version, icons);
+        assert!(icons[0].ends_with("=dialog-error"), "{:?}", icons);
+        assert!(dialog.conflicts().is_empty(), "{}", version);
+
+        dialog.show_ref().unwrap();
+        assert_eq!(zenity.last_args_os().unwrap(), dialog.to_args());
+    }
+}
+
+#[test]
+fn overriding_an_option_replaces_earlier_additional_args_under_its_other_name() {
+    let dialog = ZenityDialog::new(Info::new())
+        .with_additional_arg(("icon-name", "dialog-warning"))
+        .with_arg_override(("icon", "dialog-error"))
+        .force_modern_flags();
+
+    assert_eq!(
+        args(dialog.to_args()),
+        ["--info", "--title=Information", "--icon=dialog-error"]
+    );
+}
+
+#[test]
+fn conflicts_detect_an_option_set_under_both_names() {
+    let dialog = ZenityDialog::new(Info::new())
+        .with_icon("dialog-warning")
+        .with_additional_arg(("icon", "dialog-error"));
+
+    assert_eq!(dialog.clone().force_modern_flags().conflicts(), ["--icon"]);
+    assert_eq!(dialog.force_legacy_flags().conflicts(), ["--icon-name"]);
+}
+
+#[cfg(all(feature = "serde", feature = "entry"))]
+#[test]
+fn recordings_replay_the_executed_arguments_and_hard_timeouts() {
+    use crate::ZenitySettings;
+
+    let _lock = lock_for_test();
+    let zenity = TestZenity::affirms_with("Ada\n").unwrap();
+    zenity.set_version("4.0.1").unwrap();
+    let scripts = TestScripts::new().unwrap();
+    let recording = scripts.dir().join("dialogs.jsonl");
+    ZenitySettings::current().record_to(&recording).install();
+
+    let entry = ZenityDialog::new(Entry::new().with_text("Name?"))
+        .with_icon("dialog-question")
+        .with_program(zenity.program())
+        .allow_headless();
+    let slow = ZenityDialog::new(Info::new())
+        .with_hard_timeout(Duration::from_millis(200))
+        .with_program(zenity.program())
+        .allow_headless();
+
+    let answer = entry.show_ref().unwrap();
+    assert_eq!(
+        answer.clone().map(String::from).content(),
+        Some("Ada".to_owned())
+    );
+    let executed = zenity.last_args().unwrap();
+    assert!(executed.contains(&"--icon=dialog-question".to_owned()));
+    zenity.set_delay(Duration::from_secs(2)).unwrap();
+    assert_eq!(
+        slow.show_ref().unwrap(),
+        ZenityOutput::TimedOut { content: None }
+    );
+
+    let lines: Vec<serde_json::Value> = std::fs::read_to_string(&recording)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0]["args"], serde_json::json!(executed));
+    assert_eq!(lines[1]["hard_timeout"], true);
+
+    // Replaying doesn't run the stub, which now answers differently.
+    zenity.set_delay(Duration::ZERO).unwrap();
+    zenity.set_stdout("Grace\n").unwrap();
+    ZenitySettings::current().replay_from(&recording).install();
+    assert_eq!(entry.show_ref().unwrap(), answer);
+    assert_eq!(
+        slow.show_ref().unwrap(),
+        ZenityOutput::TimedOut { content: None }
+    );
+    assert!(matches!(
+        entry.show_ref(),
+        Err(crate::Error::ReplayMismatch { position: 2, .. })
+    ));
+}
+
+#[cfg(any(
+    feature = "calendar",
+    feature = "entry",
+    feature = "file-selection",
+    feature = "list"
+))]
+/// Output shaped like Zenity's: short lines, with some of the characters the parsers split on.
+fn zenity_like_output() -> impl proptest::strategy::Strategy<Value = String> {
+    proptest::prop_oneof![
+        proptest::arbitrary::any::<String>(),
+        "[0-9a-z/ .|\n-]{0,40}",
+        "-?[0-9]{0,6}-[0-9]{0,3}-[0-9]{0,3}",
+    ]
+}
+
+proptest::proptest! {
+    #[cfg(feature = "calendar")]
+    #[test]
+    fn calendar_parsing_never_panics(stdout in zenity_like_output()) {
+        match Calendar::new().parse(&DialogOutput::from_stdout(stdout.clone())) {
+            Ok(_) => {}
+            Err(crate::Error::ParseResultFailure(err)) => {
+                proptest::prop_assert_eq!(err.kind(), "calendar");
+                proptest::prop_assert_eq!(err.stdout(), stdout);
+            }
+            Err(err) => proptest::prop_assert!(false, "{:?}", err),
+        }
+    }
+
+    #[cfg(feature = "calendar")]
+    #[test]
+    fn calendar_parsing_with_any_format_never_panics(
+        stdout in zenity_like_output(),
+        format in "(%[dmyYbBjaAe%]|[ ./-]|[a-z]){0,8}",
+    ) {
+        let _ = Calendar::new()
+            .with_format(format)
+            .parse(&DialogOutput::from_stdout(stdout));
+    }
+
+    #[cfg(feature = "calendar")]
+    #[test]
+    fn dates_round_trip_or_fail_cleanly(stdout in zenity_like_output()) {
+        use super::Date;
+
+        if let Ok(date) = stdout.parse::<Date>() {
+            proptest::prop_assert_eq!(date.to_string().parse::<Date>(), Ok(date));
+        }
+    }
+
+    #[cfg(feature = "list")]
+    #[test]
+    fn list_parsing_never_panics(stdout in zenity_like_output()) {
+        let rows = List::new()
+            .parse(&DialogOutput::from_stdout(stdout))
+            .unwrap();
+        proptest::prop_assert!(rows.iter().all(|row| !row.is_empty() && !row.contains('\n')));
+    }
+
+    #[cfg(feature = "file-selection")]
+    #[test]
+    fn file_selection_parsing_never_panics(stdout in zenity_like_output()) {
+        let paths = FileSelection::new()
+            .parse(&DialogOutput::from_stdout(stdout))
+            .unwrap();
+        proptest::prop_assert!(paths.iter().all(|path| !path.as_os_str().is_empty()));
+    }
+
+    #[cfg(feature = "entry")]
+    #[test]
+    fn entry_parsing_never_panics(
+        stdout in zenity_like_output(),
+        prefill in proptest::option::of(zenity_like_output()),
+    ) {
+        let mut entry = Entry::new().with_focus(super::EntryFocus::AcceptOnEnterWithPrefill);
+        entry.entry_text = prefill.clone();
+        let answer = entry.parse(&DialogOutput::from_stdout(stdout.clone())).unwrap();
+        proptest::prop_assert_eq!(answer.accepted_default, prefill == Some(stdout.clone()));
+        proptest::prop_assert_eq!(answer.text, stdout);
+    }
+}
+
+/// Show the dialog through a runner answering with the response.
+#[cfg(feature = "question")]
+fn show_ext<T: ZenityApplication>(
+    dialog: &super::ZenityDialogExtButton<T>,
+    response: MockResponse,
+) -> ZenityOutputExtButton<T::Return> {
+    dialog
+        .show_with_runner(&MockRunner::new().respond_otherwise(response))
+        .unwrap()
+}
+
+#[cfg(feature = "question")]
+#[test]
+fn an_extra_button_labelled_like_the_cancel_button_is_told_apart_by_its_output() {
+    let dialog = ZenityDialog::new(Question::new().with_cancel_label("Later"))
+        .with_extra_button("Later")
+        .allow_headless();
+
+    assert_eq!(
+        show_ext(&dialog, MockResponse::rejected().with_stdout("Later\n")),
+        ZenityOutputExtButton::ExtButton {
+            content: "Later".to_owned(),
+        }
+    );
+    assert_eq!(
+        show_ext(&dialog, MockResponse::rejected()),
+        ZenityOutputExtButton::Rejected { content: None }
+    );
+}
+
+#[cfg(feature = "question")]
+#[test]
+fn an_extra_button_with_an_empty_label_is_detected() {
+    let dialog = ZenityDialog::new(Question::new())
+        .with_extra_button("")
+        .allow_headless();
+
+    assert_eq!(
+        show_ext(&dialog, MockResponse::rejected().with_stdout("\n")),
+        ZenityOutputExtButton::ExtButton {
+            content: String::new(),
+        }
+    );
+    assert_eq!(
+        show_ext(&dialog, MockResponse::rejected()),
+        ZenityOutputExtButton::Rejected { content: None }
+    );
+}
+
+#[cfg(feature = "question")]
+#[test]
+fn cancelling_with_a_custom_label_isnt_an_extra_button_click() {
+    let dialog = ZenityDialog::new(Question::new().with_cancel_label("Not now"))
+        .with_extra_button("Skip")
+        .with_extra_button("Snooze")
+        .allow_headless();
+
+    assert_eq!(
+        show_ext(&dialog, MockResponse::rejected()),
+        ZenityOutputExtButton::Rejected { content: None }
+    );
+    assert_eq!(
+        show_ext(&dialog, MockResponse::rejected().with_stdout("Snooze\n")),
+        ZenityOutputExtButton::ExtButton {
+            content: "Snooze".to_owned(),
+        }
+    );
+    // Only a registered label printed on its own counts as a click.
+    assert!(matches!(
+        show_ext(&dialog, MockResponse::rejected().with_stdout("Not now\n")),
+        ZenityOutputExtButton::Rejected { .. }
+    ));
+    assert!(matches!(
+        show_ext(&dialog, MockResponse::new(0).with_stdout("Skip\n")),
+        ZenityOutputExtButton::Affirmed { .. }
+    ));
+}
+
+#[cfg(feature = "entry")]
+fn port(input: &str) -> Result<u16, String> {
+    input.parse().map_err(|_| format!("{input:?} isn't a port"))
+}
+
+#[cfg(feature = "entry")]
+#[test]
+fn validated_entries_succeed_on_a_later_attempt() {
+    let runner = MockRunner::new()
+        .respond_to_arg("--entry-text=http", MockResponse::affirmed("8080"))
+        .respond_otherwise(MockResponse::affirmed("http"));
+    let dialog = ZenityDialog::new(Entry::new().with_text("Port")).allow_headless();
+
+    assert_eq!(
+        dialog.show_validated_with_runner(&runner, port, 3).unwrap(),
+        super::ValidatedOutcome::Valid(8080)
+    );
+
+    let invocations = runner.invocations();
+    assert_eq!(invocations.len(), 2);
+    assert!(invocations[0].has_arg("--text=Port"));
+    assert!(!invocations[0]
+        .args
+        .iter()
+        .any(|arg| arg.to_string_lossy().starts_with("--entry-text")));
+    assert!(invocations[1].has_arg("--text=Port\n\n\"http\" isn't a port"));
+    assert!(invocations[1].has_arg("--entry-text=http"));
+}
+
+#[cfg(feature = "entry")]
+#[test]
+fn cancelling_a_validated_entry_stops_the_loop() {
+    let runner = MockRunner::new()
+        .respond_to_arg("--entry-text=http", MockResponse::rejected())
+        .respond_otherwise(MockResponse::affirmed("http"));
+    let dialog = ZenityDialog::new(Entry::new().with_text("Port")).allow_headless();
+
+    assert_eq!(
+        dialog.show_validated_with_runner(&runner, port, 5).unwrap(),
+        super::ValidatedOutcome::Dismissed(super::DialogDismissed::Rejected { content: None })
+    );
+    assert_eq!(runner.invocations().len(), 2);
+}
+
+/// A dialog that never closes, whatever its arguments, except that it lists no capabilities.
+#[cfg(feature = "entry")]
+const SLEEPER: &str = "#!/bin/sh\ncase \"$1\" in --help-all) exit 0 ;; esac\nexec sleep 30\n";
+
+#[cfg(feature = "entry")]
+#[test]
+fn the_hard_timeout_kills_a_dialog_that_never_closes() {
+    let scripts = TestScripts::new().unwrap();
+    let dialog = ZenityDialog::new(Entry::new())
+        .with_hard_timeout(Duration::from_millis(300))
+        .with_program(scripts.add("sleeper", SLEEPER).unwrap())
+        .force_legacy_flags()
+        .allow_headless();
+
+    let started = std::time::Instant::now();
+    assert_eq!(
+        dialog.show_ref().unwrap(),
+        ZenityOutput::TimedOut { content: None }
+    );
+    let elapsed = started.elapsed();
+    assert!(elapsed >= Duration::from_millis(300), "{:?}", elapsed);
+    assert!(elapsed < Duration::from_secs(10), "{:?}", elapsed);
+
+    let shown = dialog.show_detailed().unwrap();
+    assert_eq!(shown.output, ZenityOutput::TimedOut { content: None });
+    assert_eq!(shown.raw, None);
+}
+
+#[cfg(feature = "entry")]
+#[test]
+fn the_hard_timeout_leaves_room_for_the_regular_timeout() {
+    let scripts = TestScripts::new().unwrap();
+    let dialog = ZenityDialog::new(Entry::new())
+        .with_timeout(Duration::from_secs(1))
+        .with_hard_timeout(Duration::from_millis(100))
+        .with_program(scripts.add("sleeper", SLEEPER).unwrap())
+        .force_legacy_flags()
+        .allow_headless();
+
+    let started = std::time::Instant::now();
+    assert_eq!(
+        dialog.with_extra_button("Later").show().unwrap(),
+        ZenityOutputExtButton::TimedOut { content: None }
+    );
+    let elapsed = started.elapsed();
+    let grace = ZenityDialog::<Entry>::HARD_TIMEOUT_GRACE;
+    assert!(elapsed >= Duration::from_secs(1) + grace, "{:?}", elapsed);
+    assert!(elapsed < Duration::from_secs(10), "{:?}", elapsed);
+}
+
+/// Set in the helper process of `dialogs_die_with_their_parent`, to the program the helper
+/// shows a dialog with.
+#[cfg(target_os = "linux")]
+const DIE_WITH_PARENT_HELPER: &str = "ZENITY_DIALOG_DIE_WITH_PARENT_HELPER";
+
+/// Runs in a helper process: shows a dialog that dies with it, until the helper is killed.
+/// Does nothing in the regular test run.
+#[cfg(target_os = "linux")]
+#[test]
+fn die_with_parent_helper() {
+    let Some(program) = std::env::var_os(DIE_WITH_PARENT_HELPER) else {
+        return;
+    };
+
+    let _ = ZenityDialog::new(Info::new())
+        .with_program(program)
+        .force_legacy_flags()
+        .allow_headless()
+        .die_with_parent()
+        .show();
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn dialogs_die_with_their_parent() {
+    use std::{
+        fs,
+        process::{Command, Stdio},
+        thread,
+        time::Instant,
+    };
+
+    let scripts = TestScripts::new().unwrap();
+    let pid_file = scripts.dir().join("dialog.pid");
+    let program = scripts
+        .add(
+            "dialog",
+            &format!(
+                "#!/bin/sh\ncase \"$1\" in --help-all) exit 0 ;; esac\necho $$ > '{}'\nexec sleep 30\n",
+                pid_file.display()
+            ),
+        )
+        .unwrap();
+
+    let mut helper = Command::new(std::env::current_exe().unwrap())
+        .args([
+            "--exact",
+            "dialog::tests::die_with_parent_helper",
+            "--test-threads=1",
+        ])
+        .env(DIE_WITH_PARENT_HELPER, &program)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .unwrap();
+
+    let deadline = Instant::now() + Duration::from_secs(10);
+    let pid = loop {
+        match fs::read_to_string(&pid_file) {
+            Ok(pid) if pid.ends_with('\n') => break pid.trim().to_owned(),
+            _ => {
+                assert!(Instant::now() < deadline, "the dialog never started");
+                thread::sleep(Duration::from_millis(20));
+            }
+        }
+    };
+
+    helper.kill().unwrap();
+    helper.wait().unwrap();
+
+    // The orphaned dialog is reaped by init, so a zombie counts as exited.
+    let running = || {
+        fs::read_to_string(format!("/proc/{pid}/stat"))
+            .map(|stat| !stat.rsplit(')').next().unwrap_or("").starts_with(" Z"))
+            .unwrap_or(false)
+    };
+    while running() {
+        assert!(
+            Instant::now() < deadline,
+            "the dialog {} outlived its parent",
+            pid
+        );
+        thread::sleep(Duration::from_millis(20));
+    }
+}
@@ -0,0 +1,242 @@
+use super::{application::ToArgVector, DialogOutput, Entry, HasText, ZenityApplication};
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    ffi::OsString,
+    fmt::Debug,
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+    str::FromStr,
+};
+
+/// An [Entry] whose answer is parsed into `T` with [FromStr], as in a port number or an IP
+/// address. The submitted text is trimmed before parsing, and text that doesn't parse fails
+/// with [crate::Error::ParseResultFailure], whose [crate::ParseError::stdout] is the submitted
+/// text. Created with [Entry::typed].
+///
+/// ```no_run
+/// # use zenity_dialog::{dialog::Entry, ZenityDialog};
+/// # fn main() -> zenity_dialog::Result<()> {
+/// let port = ZenityDialog::new(Entry::new().with_text("Port").typed::<u16>()).show()?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct TypedEntry<T> {
+    entry: Entry,
+    // Only the parsed type is named, so that the entry is Send and Sync regardless of `T`.
+    parsed: PhantomData<fn() -> T>,
+}
+
+impl<T> ZenityApplication for TypedEntry<T>
+where
+    T: FromStr + Clone,
+    T::Err: Into<Box<dyn Error + Send + Sync>>,
+{
+    type Return = T;
+
+    fn parse(&self, output: &DialogOutput) -> Result<Self::Return, crate::Error> {
+        let stdout = output.stdout();
+        stdout
+            .trim()
+            .parse()
+            .map_err(|err: T::Err| crate::ParseError::new("entry", stdout, err).into())
+    }
+
+    fn default_title() -> Option<&'static str> {
+        Entry::default_title()
+    }
+
+    /// An empty submission is parsed, so that it's rejected unless `T` accepts empty text, or
+    /// reported as missing content with [Entry::with_empty_as_none].
+    fn parses_empty_output(&self) -> bool {
+        self.entry.parses_empty_output()
+    }
+
+    fn trim_output(&self) -> bool {
+        self.entry.trim_output()
+    }
+
+    fn field_map(&self) -> BTreeMap<&'static str, String> {
+        self.entry.field_map()
+    }
+
+    fn secret_options(&self) -> Vec<&'static str> {
+        self.entry.secret_options()
+    }
+
+    #[cfg(feature = "tty-fallback")]
+    fn tty_prompt(&self) -> Option<crate::TtyPrompt> {
+        self.entry.tty_prompt()
+    }
+}
+
+impl<T> HasText for TypedEntry<T> {
+    fn set_text(&mut self, text: String) {
+        self.entry.set_text(text);
+    }
+
+    fn text(&self) -> Option<&str> {
+        self.entry.text.as_deref()
+    }
+}
+
+impl<T> ToArgVector for TypedEntry<T> {
+    fn to_argv(&self) -> Vec<OsString> {
+        self.entry.to_argv()
+    }
+}
+
+impl<T> TypedEntry<T> {
+    /// The default settings.
+    pub fn new() -> Self {
+        Entry::new().typed()
+    }
+
+    /// Override default input label.
+    pub fn with_text(mut self, text: impl Into<String>) -> Self {
+        self.entry.set_text(text);
+        self
+    }
+
+    /// Prefill the input with the given text.
+    pub fn with_entry_text(mut self, entry_text: impl Into<String>) -> Self {
+        self.entry.set_entry_text(entry_text);
+        self
+    }
+
+    /// Hide the content of the text input, as for a password input.
+    pub fn with_hide_text(mut self) -> Self {
+        self.entry.hide_text = true;
+        self
+    }
+
+    /// The underlying entry settings.
+    pub fn entry(&self) -> &Entry {
+        &self.entry
+    }
+
+    /// The underlying entry settings, for changing them in place.
+    pub fn entry_mut(&mut self) -> &mut Entry {
+        &mut self.entry
+    }
+
+    /// The underlying entry settings, dropping the parsed type.
+    pub fn into_entry(self) -> Entry {
+        self.entry
+    }
+}
+
+impl Entry {
+    /// Parse the answer into `T` instead of returning the text. See [TypedEntry].
+    pub fn typed<T>(self) -> TypedEntry<T> {
+        TypedEntry {
+            entry: self,
+            parsed: PhantomData,
+        }
+    }
+}
+
+// The traits are implemented by hand, as deriving them would require `T` to implement them too.
+
+impl<T> Debug for TypedEntry<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TypedEntry")
+            .field("entry", &self.entry)
+            .field("parsed", &std::any::type_name::<T>())
+            .finish()
+    }
+}
+
+impl<T> Clone for TypedEntry<T> {
+    fn clone(&self) -> Self {
+        self.entry.clone().typed()
+    }
+}
+
+impl<T> Default for TypedEntry<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> PartialEq for TypedEntry<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.entry == other.entry
+    }
+}
+
+impl<T> Eq for TypedEntry<T> {}
+
+impl<T> Hash for TypedEntry<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.entry.hash(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DialogOutput, Entry, TypedEntry, ZenityApplication};
+    use std::{
+        net::{IpAddr, Ipv4Addr, Ipv6Addr},
+        str::FromStr,
+    };
+
+    fn parse<T>(stdout: &str) -> crate::Result<T>
+    where
+        TypedEntry<T>: ZenityApplication<Return = T>,
+    {
+        Entry::new()
+            .typed::<T>()
+            .parse(&DialogOutput::from_stdout(stdout))
+    }
+
+    #[test]
+    fn parses_integers() {
+        assert_eq!(parse::<u16>("8080").unwrap(), 8080);
+        assert_eq!(parse::<u16>("  443 ").unwrap(), 443);
+    }
+
+    #[test]
+    fn parses_floats() {
+        assert_eq!(parse::<f64>("2.5").unwrap(), 2.5);
+        assert_eq!(parse::<f64>("-1e3").unwrap(), -1000.0);
+    }
+
+    #[test]
+    fn parses_ip_addresses() {
+        assert_eq!(
+            parse::<IpAddr>("192.168.1.10").unwrap(),
+            IpAddr::V4(Ipv4Addr::new(192, 168, 1, 10))
+        );
+        assert_eq!(
+            parse::<IpAddr>("::1").unwrap(),
+            IpAddr::V6(Ipv6Addr::LOCALHOST)
+        );
+    }
+
+    #[test]
+    fn text_that_doesnt_parse_fails_with_the_submitted_text() {
+        for stdout in ["70000", "eighty", "-1", ""] {
+            match parse::<u16>(stdout) {
+                Err(crate::Error::ParseResultFailure(err)) => {
+                    assert_eq!(err.kind(), "entry");
+                    assert_eq!(err.stdout(), stdout);
+                }
+                other => panic!("{:?} parsed as {:?}", stdout, other),
+            }
+        }
+
+        match parse::<IpAddr>("10.0.0.256") {
+            Err(crate::Error::ParseResultFailure(err)) => {
+                assert_eq!(err.stdout(), "10.0.0.256");
+                assert_eq!(
+                    std::error::Error::source(&err).map(ToString::to_string),
+                    IpAddr::from_str("10.0.0.256")
+                        .err()
+                        .map(|err| err.to_string())
+                );
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+}
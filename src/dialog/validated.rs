@@ -0,0 +1,89 @@
+use super::{DialogDismissed, Entry, EntryAnswer, ZenityDialog, ZenityOutput};
+use crate::CommandRunner;
+
+/// The outcome of [ZenityDialog::show_validated].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidatedOutcome<V> {
+    /// The user submitted input the validator accepted, converted by the validator
+    Valid(V),
+    /// The dialog closed without an answer, for example because the user cancelled it
+    Dismissed(DialogDismissed),
+    /// The validator rejected every attempt
+    Exhausted {
+        /// The input of the last attempt
+        input: String,
+        /// The validator's message for the last attempt
+        message: String,
+    },
+}
+
+impl ZenityDialog<Entry> {
+    /// Show the entry dialog until the validator accepts the input, the dialog closes without
+    /// an answer, or the validator has rejected `max_retries` additional attempts. After a
+    /// rejected attempt, the dialog is shown again prefilled with the rejected input and with
+    /// the validator's message appended to its text.
+    ///
+    /// ```no_run
+    /// # use zenity_dialog::{dialog::{Entry, ValidatedOutcome}, ZenityDialog};
+    /// # fn main() -> zenity_dialog::Result<()> {
+    /// let dialog = ZenityDialog::new(Entry::new().with_text("Port"));
+    /// let validate = |input: &str| input.parse::<u16>().map_err(|err| err.to_string());
+    /// if let ValidatedOutcome::Valid(port) = dialog.show_validated(validate, 3)? {
+    ///     println!("Listening on {port}");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn show_validated<V>(
+        &self,
+        validator: impl Fn(&str) -> Result<V, String>,
+        max_retries: usize,
+    ) -> crate::Result<ValidatedOutcome<V>> {
+        self.validated_loop(validator, max_retries, ZenityDialog::show_ref)
+    }
+
+    /// Like `show_validated`, but renders every attempt through the runner. See
+    /// [ZenityDialog::show_with_runner].
+    pub fn show_validated_with_runner<V>(
+        &self,
+        runner: &impl CommandRunner,
+        validator: impl Fn(&str) -> Result<V, String>,
+        max_retries: usize,
+    ) -> crate::Result<ValidatedOutcome<V>> {
+        self.validated_loop(validator, max_retries, |dialog| {
+            dialog.show_with_runner(runner)
+        })
+    }
+
+    fn validated_loop<V>(
+        &self,
+        validator: impl Fn(&str) -> Result<V, String>,
+        max_retries: usize,
+        show: impl Fn(&Self) -> crate::Result<ZenityOutput<EntryAnswer>>,
+    ) -> crate::Result<ValidatedOutcome<V>> {
+        let mut dialog = self.clone();
+        let mut attempt = 0;
+
+        loop {
+            let input = match show(&dialog)?.into_result() {
+                Ok(answer) => answer.map(String::from).unwrap_or_default(),
+                Err(dismissed) => return Ok(ValidatedOutcome::Dismissed(dismissed)),
+            };
+
+            let message = match validator(&input) {
+                Ok(value) => return Ok(ValidatedOutcome::Valid(value)),
+                Err(message) if attempt >= max_retries => {
+                    return Ok(ValidatedOutcome::Exhausted { input, message })
+                }
+                Err(message) => message,
+            };
+
+            attempt += 1;
+            dialog.application.text = Some(match self.application.text {
+                Some(ref text) => format!("{text}\n\n{message}"),
+                None => message,
+            });
+            dialog.application.entry_text = Some(input);
+        }
+    }
+}
@@ -0,0 +1,5 @@
+message_application! {
+    /// Configuration for a dialog that warns the user of a potential problem. Zenity prints
+    /// nothing when it's confirmed, so its content is [None].
+    Warning, "--warning", "Warning"
+}
@@ -0,0 +1,242 @@
+use super::{DialogDismissed, ZenityApplication, ZenityDialog, ZenityOutputExtButton};
+use crate::CommandRunner;
+
+type Step<A> = Box<
+    dyn Fn(
+        &[Option<A>],
+        Option<&dyn CommandRunner>,
+        Option<&str>,
+    ) -> crate::Result<ZenityOutputExtButton<A>>,
+>;
+
+/// Shows a sequence of dialogs, each built from the answers to the previous ones, and stops as
+/// soon as one closes without an answer. With [Wizard::with_back_button], every step but the
+/// first has an extra button that returns to the previous step.
+///
+/// The answers are converted into the common type `A`, such as [String] or an enum of the
+/// answer types, and a step without content, such as a confirmed question, answers [None].
+///
+/// ```no_run
+/// # use zenity_dialog::{dialog::{Entry, Question, Wizard, WizardOutcome}, ZenityDialog};
+/// # fn main() -> zenity_dialog::Result<()> {
+/// let wizard = Wizard::<String>::new()
+///     .with_step(|_| ZenityDialog::new(Entry::new().with_text("Your name?")))
+///     .with_step(|answers| {
+///         let name = answers[0].as_deref().unwrap_or_default();
+///         ZenityDialog::new(Question::new().with_text(format!("Create an account for {name}?")))
+///     })
+///     .with_back_button("Back");
+///
+/// match wizard.run()? {
+///     WizardOutcome::Completed(answers) => println!("Created {:?}", answers[0]),
+///     WizardOutcome::Cancelled { at_step, .. } => println!("Cancelled at step {at_step}"),
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct Wizard<A> {
+    steps: Vec<Step<A>>,
+    back_label: Option<String>,
+}
+
+/// The result of running a [Wizard].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WizardOutcome<A> {
+    /// Every step was answered. The answers are in the order of the steps.
+    Completed(Vec<Option<A>>),
+    /// A step closed without an answer.
+    Cancelled {
+        /// The step, starting at 0, that closed without an answer
+        at_step: usize,
+        /// Why the step closed
+        reason: DialogDismissed,
+    },
+}
+
+impl<A: 'static> Wizard<A> {
+    /// A wizard without steps.
+    pub fn new() -> Self {
+        Self {
+            steps: Vec::new(),
+            back_label: None,
+        }
+    }
+
+    /// Add a step, which builds its dialog from the answers to the previous steps.
+    pub fn with_step<T>(mut self, step: impl Fn(&[Option<A>]) -> ZenityDialog<T> + 'static) -> Self
+    where
+        T: ZenityApplication,
+        T::Return: Into<A>,
+    {
+        self.steps
+            .push(Box::new(move |answers, runner, back_label| {
+                let dialog = step(answers);
+                let output = match (back_label, runner) {
+                    (Some(label), Some(runner)) => {
+                        dialog.with_extra_button(label).show_with_runner(&runner)?
+                    }
+                    (Some(label), None) => dialog.with_extra_button(label).show_ref()?,
+                    (None, Some(runner)) => dialog.show_with_runner(&runner)?.into(),
+                    (None, None) => dialog.show_ref()?.into(),
+                };
+                Ok(output.map(Into::into))
+            }));
+        self
+    }
+
+    /// Add an extra button with the label to every step but the first, which shows the previous
+    /// step again, discarding its answer.
+    pub fn with_back_button(mut self, label: impl Into<String>) -> Self {
+        self.back_label = Some(label.into());
+        self
+    }
+
+    /// Show the steps in order. See [ZenityDialog::show].
+    pub fn run(&self) -> crate::Result<WizardOutcome<A>> {
+        self.run_steps(None)
+    }
+
+    /// Show the steps in order through the runner. See [ZenityDialog::show_with_runner].
+    pub fn run_with_runner(&self, runner: &impl CommandRunner) -> crate::Result<WizardOutcome<A>> {
+        self.run_steps(Some(runner))
+    }
+
+    fn run_steps(&self, runner: Option<&dyn CommandRunner>) -> crate::Result<WizardOutcome<A>> {
+        let mut answers = Vec::new();
+        while let Some(step) = self.steps.get(answers.len()) {
+            let back_label = self.back_label.as_deref().filter(|_| !answers.is_empty());
+            match step(&answers, runner, back_label)?.into_result() {
+                Ok(answer) => answers.push(answer),
+                // Only the back button returns; any other extra button ends the wizard.
+                Err(DialogDismissed::ExtButton(ref label))
+                    if Some(label.as_str()) == back_label =>
+                {
+                    answers.pop();
+                }
+                Err(reason) => {
+                    return Ok(WizardOutcome::Cancelled {
+                        at_step: answers.len(),
+                        reason,
+                    })
+                }
+            }
+        }
+
+        Ok(WizardOutcome::Completed(answers))
+    }
+}
+
+impl<A: 'static> Default for Wizard<A> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<A> std::fmt::Debug for Wizard<A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Wizard")
+            .field("steps", &self.steps.len())
+            .field("back_label", &self.back_label)
+            .finish()
+    }
+}
+
+#[cfg(all(test, unix, feature = "entry", feature = "question"))]
+mod tests {
+    use super::{Wizard, WizardOutcome};
+    use crate::dialog::{DialogDismissed, Entry, Question, ZenityDialog};
+    use crate::runner::{MockResponse, MockRunner};
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    /// Asks for a name, then confirms it, with a back button.
+    fn wizard() -> Wizard<String> {
+        Wizard::new()
+            .with_step(|_| ZenityDialog::new(Entry::new().with_text("Name?")))
+            .with_step(|answers: &[Option<String>]| {
+                let name = answers[0].clone().unwrap_or_default();
+                ZenityDialog::new(Question::new().with_text(format!("Use {name}?")))
+            })
+            .with_back_button("Back")
+    }
+
+    #[test]
+    fn completes_with_every_answer() {
+        let runner = MockRunner::new()
+            .respond_to_arg("--entry", MockResponse::affirmed("alice\n"))
+            .respond_to_arg("--question", MockResponse::affirmed(""));
+
+        assert_eq!(
+            wizard().run_with_runner(&runner).unwrap(),
+            WizardOutcome::Completed(vec![Some("alice".to_owned()), None])
+        );
+        let invocations = runner.invocations();
+        assert!(!invocations[0].has_arg("--extra-button=Back"));
+        assert!(invocations[1].has_arg("--extra-button=Back"));
+    }
+
+    #[test]
+    fn back_button_shows_the_previous_step_again() {
+        let entries = Arc::new(AtomicUsize::new(0));
+        let first_entry = Arc::clone(&entries);
+        let runner = MockRunner::new()
+            .respond_when(
+                move |args| {
+                    args.iter().any(|arg| arg == "--entry")
+                        && first_entry.fetch_add(1, Ordering::SeqCst) == 0
+                },
+                MockResponse::affirmed("alice\n"),
+            )
+            .respond_to_arg("--entry", MockResponse::affirmed("bob\n"))
+            .respond_to_arg(
+                "--text=Use alice?",
+                MockResponse::rejected().with_stdout("Back\n"),
+            )
+            .respond_to_arg("--text=Use bob?", MockResponse::affirmed(""));
+
+        assert_eq!(
+            wizard().run_with_runner(&runner).unwrap(),
+            WizardOutcome::Completed(vec![Some("bob".to_owned()), None])
+        );
+        assert_eq!(runner.invocations().len(), 4);
+        assert_eq!(entries.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn cancelling_a_step_ends_the_wizard() {
+        let runner = MockRunner::new()
+            .respond_to_arg("--entry", MockResponse::rejected())
+            .respond_otherwise(MockResponse::affirmed(""));
+
+        assert_eq!(
+            wizard().run_with_runner(&runner).unwrap(),
+            WizardOutcome::Cancelled {
+                at_step: 0,
+                reason: DialogDismissed::Rejected { content: None },
+            }
+        );
+        assert_eq!(runner.invocations().len(), 1);
+    }
+
+    #[test]
+    fn other_buttons_than_back_end_the_wizard() {
+        let runner = MockRunner::new()
+            .respond_to_arg("--entry", MockResponse::affirmed("alice\n"))
+            .respond_to_arg(
+                "--question",
+                MockResponse::rejected().with_stdout("Later\n"),
+            );
+
+        assert_eq!(
+            wizard().run_with_runner(&runner).unwrap(),
+            WizardOutcome::Cancelled {
+                at_step: 1,
+                reason: DialogDismissed::Rejected {
+                    content: Some("Later".to_owned()),
+                },
+            }
+        );
+    }
+}
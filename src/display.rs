@@ -0,0 +1,151 @@
+use std::{env, ffi::OsString};
+
+/// Whether a display is configured, reading the environment through `var`: `DISPLAY` or
+/// `WAYLAND_DISPLAY` is set and not empty.
+pub(crate) fn is_display_set(var: impl Fn(&str) -> Option<OsString>) -> bool {
+    let is_set = |key: &str| var(key).is_some_and(|value| !value.is_empty());
+    is_set("DISPLAY") || is_set("WAYLAND_DISPLAY")
+}
+
+/// The windowing backend GTK uses to render the dialog.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RenderedBackend {
+    /// Rendered natively in an X11 session
+    X11,
+    /// Rendered natively in a Wayland session
+    Wayland,
+    /// Rendered through X11 in a Wayland session
+    XWayland,
+}
+
+impl RenderedBackend {
+    /// Infer the backend a dialog would be rendered with from the `GDK_BACKEND`,
+    /// `XDG_SESSION_TYPE`, `WAYLAND_DISPLAY`, and `DISPLAY` environment variables. This is
+    /// best-effort: it returns [None] when the environment is ambiguous or no display is
+    /// available.
+    pub fn infer() -> Option<Self> {
+        Self::infer_from(|key| env::var(key).ok())
+    }
+
+    fn infer_from(var: impl Fn(&str) -> Option<String>) -> Option<Self> {
+        let is_set = |key: &str| var(key).is_some_and(|value| !value.is_empty());
+        let session_type = var("XDG_SESSION_TYPE").unwrap_or_default();
+        let wayland_session = is_set("WAYLAND_DISPLAY") || session_type == "wayland";
+        let x11_available = is_set("DISPLAY");
+
+        // GDK_BACKEND may list several backends in order of preference, as in `wayland,x11`.
+        let requested = var("GDK_BACKEND")
+            .and_then(|value| value.split(',').next().map(|first| first.trim().to_owned()))
+            .filter(|first| !first.is_empty() && first != "*");
+
+        match requested.as_deref() {
+            Some("x11") if wayland_session && x11_available => Some(RenderedBackend::XWayland),
+            Some("x11") if x11_available => Some(RenderedBackend::X11),
+            Some("wayland") if is_set("WAYLAND_DISPLAY") => Some(RenderedBackend::Wayland),
+            Some(_) => None,
+            None if is_set("WAYLAND_DISPLAY") => Some(RenderedBackend::Wayland),
+            None if x11_available && !wayland_session => Some(RenderedBackend::X11),
+            None => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_display_set, RenderedBackend};
+    use std::collections::HashMap;
+
+    /// Infer the backend from the listed variables only.
+    fn infer(vars: &[(&str, &str)]) -> Option<RenderedBackend> {
+        let vars: HashMap<_, _> = vars.iter().copied().collect();
+        RenderedBackend::infer_from(|key| vars.get(key).map(|value| value.to_string()))
+    }
+
+    #[test]
+    fn infers_x11_in_an_x11_session() {
+        let x11 = [("XDG_SESSION_TYPE", "x11"), ("DISPLAY", ":0")];
+        assert_eq!(infer(&x11), Some(RenderedBackend::X11));
+        assert_eq!(infer(&[("DISPLAY", ":0")]), Some(RenderedBackend::X11));
+        assert_eq!(
+            infer(&[("DISPLAY", ":0"), ("GDK_BACKEND", "x11")]),
+            Some(RenderedBackend::X11)
+        );
+    }
+
+    #[test]
+    fn infers_wayland_in_a_wayland_session() {
+        let wayland = [
+            ("XDG_SESSION_TYPE", "wayland"),
+            ("WAYLAND_DISPLAY", "wayland-0"),
+            ("DISPLAY", ":0"),
+        ];
+        assert_eq!(infer(&wayland), Some(RenderedBackend::Wayland));
+        assert_eq!(
+            infer(&[
+                ("WAYLAND_DISPLAY", "wayland-0"),
+                ("GDK_BACKEND", "wayland,x11")
+            ]),
+            Some(RenderedBackend::Wayland)
+        );
+    }
+
+    #[test]
+    fn infers_xwayland_when_gdk_is_forced_to_x11_under_wayland() {
+        let forced = [
+            ("XDG_SESSION_TYPE", "wayland"),
+            ("WAYLAND_DISPLAY", "wayland-0"),
+            ("DISPLAY", ":0"),
+            ("GDK_BACKEND", "x11"),
+        ];
+        assert_eq!(infer(&forced), Some(RenderedBackend::XWayland));
+        assert_eq!(
+            infer(&[
+                ("XDG_SESSION_TYPE", "wayland"),
+                ("DISPLAY", ":1"),
+                ("GDK_BACKEND", " x11 ,wayland")
+            ]),
+            Some(RenderedBackend::XWayland)
+        );
+    }
+
+    #[test]
+    fn ambiguous_environments_infer_nothing() {
+        assert_eq!(infer(&[]), None);
+        assert_eq!(infer(&[("DISPLAY", "")]), None);
+        // A Wayland session without a Wayland display could render either way.
+        assert_eq!(
+            infer(&[("XDG_SESSION_TYPE", "wayland"), ("DISPLAY", ":0")]),
+            None
+        );
+        // The requested backend isn't available.
+        assert_eq!(
+            infer(&[("DISPLAY", ":0"), ("GDK_BACKEND", "wayland")]),
+            None
+        );
+        assert_eq!(
+            infer(&[
+                ("WAYLAND_DISPLAY", "wayland-0"),
+                ("GDK_BACKEND", "broadway")
+            ]),
+            None
+        );
+    }
+
+    #[test]
+    fn a_wildcard_gdk_backend_is_ignored() {
+        assert_eq!(
+            infer(&[("DISPLAY", ":0"), ("GDK_BACKEND", "*")]),
+            Some(RenderedBackend::X11)
+        );
+    }
+
+    #[test]
+    fn display_is_set_by_either_variable_when_not_empty() {
+        assert!(is_display_set(|key| (key == "DISPLAY").then(|| ":0".into())));
+        assert!(is_display_set(
+            |key| (key == "WAYLAND_DISPLAY").then(|| "wayland-0".into())
+        ));
+        assert!(!is_display_set(|key| (key == "DISPLAY").then(|| "".into())));
+        assert!(!is_display_set(|_| None));
+    }
+}
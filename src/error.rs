@@ -1,11 +1,59 @@
-use std::{io, string::FromUtf8Error};
+use std::{
+    ffi::OsString,
+    io,
+    path::{Path, PathBuf},
+    string::FromUtf8Error,
+};
 
 /// The errors that may occur when trying to launch a Zenity dialog.
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
     /// Failed to find Zenity
-    #[error("Zenity is not installed")]
-    ZenityNotInstalled(#[source] io::Error),
+    #[error("Zenity is not installed: {} was not found, running `{}`", program.display(), command_line(program, args))]
+    ZenityNotInstalled {
+        /// The program that was attempted.
+        program: PathBuf,
+        /// The arguments the program was attempted with.
+        args: Vec<OsString>,
+        /// The underlying error.
+        #[source]
+        source: io::Error,
+    },
+    /// Zenity was found, but this process isn't allowed to execute it, for example because it
+    /// lacks the executable permission bit or is on a filesystem mounted `noexec`.
+    #[error("Zenity is not executable: permission to run {} was denied, running `{}`", program.display(), command_line(program, args))]
+    ZenityNotExecutable {
+        /// The program that was attempted.
+        program: PathBuf,
+        /// The arguments the program was attempted with.
+        args: Vec<OsString>,
+        /// The underlying error.
+        #[source]
+        source: io::Error,
+    },
+    /// Zenity was found, but isn't a valid executable, for example because it was built for
+    /// another architecture or is a script without a `#!` line.
+    #[error("Zenity is not a valid executable: {} has an unknown format, running `{}`", program.display(), command_line(program, args))]
+    InvalidExecutable {
+        /// The program that was attempted.
+        program: PathBuf,
+        /// The arguments the program was attempted with.
+        args: Vec<OsString>,
+        /// The underlying error.
+        #[source]
+        source: io::Error,
+    },
+    /// Zenity was found, but couldn't be started for another reason.
+    #[error("Failed to run `{}`: {source}", command_line(program, args))]
+    SpawnFailed {
+        /// The program that was attempted.
+        program: PathBuf,
+        /// The arguments the program was attempted with.
+        args: Vec<OsString>,
+        /// The underlying error.
+        #[source]
+        source: io::Error,
+    },
     /// A currently untracked error type occured when trying to invoke Zenity.
     #[error("Unexpected io error occured: {0}")]
     UnexpectedIoError(#[source] io::Error),
@@ -14,6 +62,79 @@ pub enum Error {
     /// report it.
     #[error("Failed to decode stdout as utf-8: {0}")]
     InvalidUtf8FromStdout(#[source] FromUtf8Error),
+    /// Zenity reported that it could not display the dialog, for example because it received
+    /// invalid arguments or could not connect to a display. The details are usually in stderr.
+    #[error("Zenity failed with exit code {exit_code}: {stderr}")]
+    ZenityFailed {
+        /// The returned exit code.
+        exit_code: i32,
+        /// The content Zenity returned to stderr.
+        stderr: String,
+    },
+    /// Zenity was terminated by a signal before it could exit, for example because it was killed
+    /// by the user or by the OOM killer.
+    #[error("Zenity was terminated by signal {signal}")]
+    TerminatedBySignal {
+        /// The number of the signal that terminated the process.
+        signal: i32,
+        /// The content Zenity returned to stderr before it was terminated.
+        stderr: String,
+    },
+    /// The dialog was not shown because the user stayed idle for longer than the configured
+    /// deferral. See [crate::IdleDeferral].
+    #[cfg(feature = "idle-detect")]
+    #[error("The user was still idle after deferring the dialog for {0:?}")]
+    DeferredExpired(std::time::Duration),
+    /// The dialog was not shown because neither `DISPLAY` nor `WAYLAND_DISPLAY` is set for the
+    /// Zenity process. See [crate::ZenityDialog::allow_headless].
+    #[error("No display is available to show the dialog")]
+    NoDisplay,
+    /// The dialog was not shown because the installed Zenity doesn't support some of its
+    /// options. Additional arguments are only checked in strict mode; see
+    /// [crate::ZenityDialog::strict_capabilities].
+    #[error("Zenity{} doesn't support the options {}", installed_version.map(|version| format!(" {version}")).unwrap_or_default(), options.join(", "))]
+    UnsupportedOption {
+        /// Every unsupported option, with leading dashes and without values.
+        options: Vec<String>,
+        /// The version of the installed Zenity, if it could be determined.
+        installed_version: Option<crate::Version>,
+    },
+    /// The dialog was not shown because the backend can't render it, for example because the
+    /// desktop portal only offers file selection dialogs. See
+    /// [crate::backend::DialogBackend::run].
+    #[error("{backend} can't show the dialog: {reason}")]
+    UnsupportedByBackend {
+        /// The backend, as in `xdg-desktop-portal`.
+        backend: String,
+        /// What the backend doesn't support.
+        reason: String,
+    },
+    /// A dialog didn't match the next dialog of the replayed recording. See
+    /// [crate::ZenitySettings::replay_from].
+    #[cfg(all(feature = "serde", unix))]
+    #[error("Dialog {position} of the recording was shown with {actual:?}, but {}", expected.as_ref().map(|expected| format!("{expected:?} was recorded")).unwrap_or_else(|| "the recording has ended".into()))]
+    ReplayMismatch {
+        /// The position of the dialog in the recording, starting from 0.
+        position: usize,
+        /// The recorded arguments, or [None] if the recording has no more dialogs.
+        expected: Option<Vec<String>>,
+        /// The arguments the dialog was shown with.
+        actual: Vec<String>,
+    },
+    /// A dialog definition couldn't be loaded, because it's malformed or has an unknown field or
+    /// file extension. The message points to the offending field. See
+    /// [crate::ZenityDialog::from_file].
+    #[cfg(feature = "serde")]
+    #[error("Invalid dialog definition: {0}")]
+    InvalidDefinition(#[source] Box<dyn std::error::Error + Send + Sync>),
+    /// The dialog was not shown because its configuration is invalid. See
+    /// [crate::ZenityDialog::validate].
+    #[error("The dialog is invalid: {}", .0.iter().map(ToString::to_string).collect::<Vec<_>>().join("; "))]
+    InvalidDialog(Vec<crate::ValidationError>),
+    /// A dialog that must be answered, such as the question of [crate::confirm], closed without
+    /// the user confirming or cancelling it, for example because it timed out.
+    #[error("The dialog wasn't answered: {0}")]
+    Unanswered(#[source] crate::DialogDismissed),
     /// An error that should never occur. Yet, the [std::process::Command] does not guarantee that
     /// a code is produced, so we may potentially this error in the off chance that one is not returned.
     #[error("Zenity failed to return an exit code.")]
@@ -21,5 +142,227 @@ pub enum Error {
     /// Occurs if the output from Zenity could not be converted to [crate::ZenityOutput]. This would
     /// typically indicate a bug in this crate, so if it occurs, please open an issue!
     #[error("Failed to parse the output: {0}")]
-    ParseResultFailure(#[from] anyhow::Error),
+    ParseResultFailure(#[from] ParseError),
+}
+
+/// Why the output of Zenity couldn't be parsed. See [Error::ParseResultFailure].
+///
+/// ```
+/// # use zenity_dialog::ParseError;
+/// let err = ParseError::new("calendar", "17.10.2026", "input contains invalid characters")
+///     .with_format("%d/%m/%y");
+/// assert_eq!(
+///     err.to_string(),
+///     r#"calendar output "17.10.2026" doesn't match the format "%d/%m/%y": input contains invalid characters"#
+/// );
+/// ```
+#[derive(Debug, thiserror::Error)]
+#[error("{kind} output {stdout:?}{}: {source}", format.as_ref().map(|format| format!(" doesn't match the format {format:?}")).unwrap_or_default())]
+pub struct ParseError {
+    kind: &'static str,
+    stdout: String,
+    format: Option<String>,
+    #[source]
+    source: Box<dyn std::error::Error + Send + Sync>,
+}
+
+impl ParseError {
+    /// A failure to parse `stdout`, as printed by the kind of dialog or probe, such as
+    /// `calendar` or `version`.
+    pub fn new(
+        kind: &'static str,
+        stdout: impl Into<String>,
+        source: impl Into<Box<dyn std::error::Error + Send + Sync>>,
+    ) -> Self {
+        Self {
+            kind,
+            stdout: stdout.into(),
+            format: None,
+            source: source.into(),
+        }
+    }
+
+    /// Record the format the text was expected in, such as the date format of a calendar.
+    pub fn with_format(mut self, format: impl Into<String>) -> Self {
+        self.format = Some(format.into());
+        self
+    }
+
+    /// The kind of dialog or probe whose output couldn't be parsed, as in `calendar`.
+    pub fn kind(&self) -> &'static str {
+        self.kind
+    }
+
+    /// The exact text that was being parsed. Included in the error message.
+    pub fn stdout(&self) -> &str {
+        &self.stdout
+    }
+
+    /// The format the text was expected in, if the parser has one. Included in the error
+    /// message.
+    pub fn format(&self) -> Option<&str> {
+        self.format.as_deref()
+    }
+}
+
+/// Wraps errors of parsers written against the former `anyhow`-based
+/// [Error::ParseResultFailure], without the parsed text. Deprecated along with the `anyhow`
+/// feature; construct a [ParseError] instead.
+#[cfg(feature = "anyhow")]
+impl From<anyhow::Error> for Error {
+    fn from(err: anyhow::Error) -> Self {
+        Error::ParseResultFailure(ParseError::new("unknown", String::new(), err))
+    }
+}
+
+impl Error {
+    /// Map an error starting the program with the arguments. Every failure to start Zenity goes
+    /// through here, so that they're reported consistently.
+    pub(crate) fn spawn(
+        program: impl Into<PathBuf>,
+        args: Vec<OsString>,
+        source: io::Error,
+    ) -> Self {
+        let program = program.into();
+
+        #[cfg(unix)]
+        let invalid_executable = source.raw_os_error() == Some(libc::ENOEXEC);
+        #[cfg(not(unix))]
+        let invalid_executable = false;
+
+        match source.kind() {
+            io::ErrorKind::NotFound => Error::ZenityNotInstalled {
+                program,
+                args,
+                source,
+            },
+            io::ErrorKind::PermissionDenied => Error::ZenityNotExecutable {
+                program,
+                args,
+                source,
+            },
+            _ if invalid_executable => Error::InvalidExecutable {
+                program,
+                args,
+                source,
+            },
+            _ => Error::SpawnFailed {
+                program,
+                args,
+                source,
+            },
+        }
+    }
+
+    /// A copy of the error, so that a failed probe can be cached and reported again without
+    /// running the program. Sources that can't be copied are replaced with errors of the same
+    /// kind and message, and errors a probe doesn't return are reduced to their message.
+    pub(crate) fn replicate(&self) -> Self {
+        let io = |source: &io::Error| match source.raw_os_error() {
+            Some(code) => io::Error::from_raw_os_error(code),
+            None => io::Error::new(source.kind(), source.to_string()),
+        };
+
+        match self {
+            Error::ZenityNotInstalled {
+                program,
+                args,
+                source,
+            } => Error::ZenityNotInstalled {
+                program: program.clone(),
+                args: args.clone(),
+                source: io(source),
+            },
+            Error::ZenityNotExecutable {
+                program,
+                args,
+                source,
+            } => Error::ZenityNotExecutable {
+                program: program.clone(),
+                args: args.clone(),
+                source: io(source),
+            },
+            Error::InvalidExecutable {
+                program,
+                args,
+                source,
+            } => Error::InvalidExecutable {
+                program: program.clone(),
+                args: args.clone(),
+                source: io(source),
+            },
+            Error::SpawnFailed {
+                program,
+                args,
+                source,
+            } => Error::SpawnFailed {
+                program: program.clone(),
+                args: args.clone(),
+                source: io(source),
+            },
+            Error::UnexpectedIoError(source) => Error::UnexpectedIoError(io(source)),
+            Error::InvalidUtf8FromStdout(source) => Error::InvalidUtf8FromStdout(source.clone()),
+            Error::ParseResultFailure(err) => Error::ParseResultFailure(ParseError {
+                kind: err.kind,
+                stdout: err.stdout.clone(),
+                format: err.format.clone(),
+                source: err.source.to_string().into(),
+            }),
+            other => Error::UnexpectedIoError(io::Error::other(other.to_string())),
+        }
+    }
+
+    /// Map an error starting the command.
+    pub(crate) fn spawn_command(command: &std::process::Command, source: io::Error) -> Self {
+        let args = command.get_args().map(ToOwned::to_owned).collect();
+        Error::spawn(command.get_program(), args, source)
+    }
+}
+
+/// Render the program and its arguments as a shell-quoted command line, lossily.
+fn command_line(program: &Path, args: &[OsString]) -> String {
+    let args: Vec<_> = args.iter().map(|arg| arg.to_string_lossy()).collect();
+    crate::shell::command_line(&program.to_string_lossy(), args.iter().map(AsRef::as_ref))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Error;
+    use std::io;
+
+    fn spawn(source: io::Error) -> Error {
+        Error::spawn("/usr/bin/zenity", vec!["--info".into()], source)
+    }
+
+    #[test]
+    fn spawn_errors_are_mapped_by_their_cause() {
+        assert!(matches!(
+            spawn(io::ErrorKind::NotFound.into()),
+            Error::ZenityNotInstalled { .. }
+        ));
+        assert!(matches!(
+            spawn(io::ErrorKind::PermissionDenied.into()),
+            Error::ZenityNotExecutable { .. }
+        ));
+        #[cfg(unix)]
+        assert!(matches!(
+            spawn(io::Error::from_raw_os_error(libc::ENOEXEC)),
+            Error::InvalidExecutable { .. }
+        ));
+        assert!(matches!(
+            spawn(io::ErrorKind::OutOfMemory.into()),
+            Error::SpawnFailed { .. }
+        ));
+    }
+
+    #[test]
+    fn spawn_errors_keep_the_attempted_command() {
+        match spawn(io::ErrorKind::PermissionDenied.into()) {
+            Error::ZenityNotExecutable { program, args, .. } => {
+                assert_eq!(program, std::path::Path::new("/usr/bin/zenity"));
+                assert_eq!(args, ["--info"]);
+            }
+            err => panic!("{:?}", err),
+        }
+    }
 }
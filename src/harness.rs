@@ -0,0 +1,213 @@
+use crate::ZenitySettings;
+use std::{
+    ffi::{OsStr, OsString},
+    fs, io,
+    os::unix::{ffi::OsStrExt, fs::PermissionsExt},
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
+
+/// A stand-in for Zenity that prints and exits as scripted, for end-to-end tests of dialog
+/// flows. While the guard is alive it's installed as the crate-wide program, and the previous
+/// settings are restored when it's dropped.
+///
+/// The stub reads its scenario from files in its own temporary directory, so it can be changed
+/// between dialogs. As the crate-wide settings are shared, tests that run in parallel should
+/// pass [TestZenity::program] to [crate::ZenityDialog::with_program] instead of relying on the
+/// installed settings. The stub doesn't need a display, but dialogs still check for one unless
+/// [crate::ZenityDialog::allow_headless] is set.
+// The crate's own tests hold `lock_for_test` for as long as a stub is installed, as other tests
+// read the crate-wide settings while holding it.
+#[derive(Debug)]
+pub struct TestZenity {
+    dir: PathBuf,
+    previous: ZenitySettings,
+}
+
+impl TestZenity {
+    const SCRIPT: &'static str = r#"#!/bin/sh
+dir=$(dirname "$0")
+case "$1" in
+--version) cat "$dir/version"; exit 0 ;;
+--help-all) exit 0 ;;
+esac
+for arg in "$@"; do printf '%s\000' "$arg"; done > "$dir/args"
+if [ -s "$dir/delay" ]; then sleep "$(cat "$dir/delay")"; fi
+cat "$dir/stderr" >&2
+cat "$dir/stdout"
+exit "$(cat "$dir/exit_code")"
+"#;
+
+    /// A stub that exits with the code without printing anything.
+    pub fn new(exit_code: i32) -> io::Result<Self> {
+        let dir = temp_dir()?;
+        let script = write_script(&dir, "zenity", Self::SCRIPT)?;
+
+        let harness = Self {
+            previous: ZenitySettings::current(),
+            dir,
+        };
+        harness.write("version", "3.44.0")?;
+        harness.write("delay", "")?;
+        harness.write("stdout", "")?;
+        harness.write("stderr", "")?;
+        harness.set_exit_code(exit_code)?;
+
+        ZenitySettings::current().with_program(script).install();
+        Ok(harness)
+    }
+
+    /// A stub that confirms the dialog, printing `stdout`.
+    pub fn affirms_with(stdout: impl AsRef<str>) -> io::Result<Self> {
+        let harness = Self::new(0)?;
+        harness.set_stdout(stdout)?;
+        Ok(harness)
+    }
+
+    /// A stub that cancels the dialog.
+    pub fn rejects() -> io::Result<Self> {
+        Self::new(1)
+    }
+
+    /// A stub that reports the dialog as timed out.
+    pub fn times_out() -> io::Result<Self> {
+        Self::new(5)
+    }
+
+    /// A stub that fails to show the dialog, printing `stderr`.
+    pub fn fails_with(stderr: impl AsRef<str>) -> io::Result<Self> {
+        let harness = Self::new(255)?;
+        harness.set_stderr(stderr)?;
+        Ok(harness)
+    }
+
+    /// The path of the stub executable.
+    pub fn program(&self) -> PathBuf {
+        self.dir.join("zenity")
+    }
+
+    /// Change the exit code of the following dialogs.
+    pub fn set_exit_code(&self, exit_code: i32) -> io::Result<()> {
+        self.write("exit_code", &exit_code.to_string())
+    }
+
+    /// Change what the following dialogs print to stdout.
+    pub fn set_stdout(&self, stdout: impl AsRef<str>) -> io::Result<()> {
+        self.write("stdout", stdout.as_ref())
+    }
+
+    /// Change what the following dialogs print to stderr.
+    pub fn set_stderr(&self, stderr: impl AsRef<str>) -> io::Result<()> {
+        self.write("stderr", stderr.as_ref())
+    }
+
+    /// Make the following dialogs wait before exiting. Delays are rounded to milliseconds.
+    pub fn set_delay(&self, delay: Duration) -> io::Result<()> {
+        self.write("delay", &format!("{:.3}", delay.as_secs_f64()))
+    }
+
+    /// Change the version the stub reports for `--version`. Defaults to `3.44.0`. Versions are
+    /// cached per program, so this only affects stubs whose version wasn't probed yet.
+    pub fn set_version(&self, version: impl AsRef<str>) -> io::Result<()> {
+        self.write("version", version.as_ref())
+    }
+
+    /// The arguments of the last dialog the stub showed, or an empty list if it hasn't shown
+    /// any. Arguments that aren't valid UTF-8 are converted lossily.
+    pub fn last_args(&self) -> io::Result<Vec<String>> {
+        let args = self.last_args_os()?;
+        Ok(args
+            .iter()
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect())
+    }
+
+    /// Like `last_args`, but keeps arguments that aren't valid UTF-8, such as paths, as they were
+    /// passed.
+    pub fn last_args_os(&self) -> io::Result<Vec<OsString>> {
+        // The stub terminates each argument with a NUL byte, which can't occur in arguments.
+        match fs::read(self.dir.join("args")) {
+            Ok(args) => Ok(match args.strip_suffix(b"\0") {
+                Some(args) => args
+                    .split(|&byte| byte == 0)
+                    .map(|arg| OsStr::from_bytes(arg).to_owned())
+                    .collect(),
+                None => Vec::new(),
+            }),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// The directory holding the stub and its scenario.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn write(&self, name: &str, contents: &str) -> io::Result<()> {
+        fs::write(self.dir.join(name), contents)
+    }
+}
+
+impl Drop for TestZenity {
+    fn drop(&mut self) {
+        self.previous.clone().install();
+        // A leftover directory in the temporary directory is harmless.
+        let _ = fs::remove_dir_all(&self.dir);
+    }
+}
+
+/// Create a directory of its own in the temporary directory.
+fn temp_dir() -> io::Result<PathBuf> {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    let dir = std::env::temp_dir().join(format!(
+        "zenity-dialog-test-{}-{}",
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::SeqCst)
+    ));
+    fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+/// Write an executable script into the directory, returning its path.
+fn write_script(dir: &Path, name: &str, script: &str) -> io::Result<PathBuf> {
+    let path = dir.join(name);
+    fs::write(&path, script)?;
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o755))?;
+    Ok(path)
+}
+
+/// Executable scripts in a temporary directory of their own, for the crate's tests that need a
+/// program behaving differently from [TestZenity]. Unlike [TestZenity], nothing is installed.
+/// The directory is removed when this is dropped.
+#[cfg(test)]
+#[derive(Debug)]
+pub(crate) struct TestScripts {
+    dir: PathBuf,
+}
+
+#[cfg(test)]
+impl TestScripts {
+    pub(crate) fn new() -> io::Result<Self> {
+        Ok(Self { dir: temp_dir()? })
+    }
+
+    /// Write the script under the name, returning its path.
+    pub(crate) fn add(&self, name: &str, script: &str) -> io::Result<PathBuf> {
+        write_script(&self.dir, name, script)
+    }
+
+    /// The directory holding the scripts.
+    pub(crate) fn dir(&self) -> &Path {
+        &self.dir
+    }
+}
+
+#[cfg(test)]
+impl Drop for TestScripts {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.dir);
+    }
+}
@@ -0,0 +1,51 @@
+use crate::{display, ZenitySettings};
+use std::{env, path::PathBuf};
+
+/// Whether dialogs can be shown at all: Zenity is installed, reports its version, and a display
+/// is available. No dialog is shown to find out.
+pub fn is_available() -> bool {
+    health_check().is_ok_and(|health| health.is_ok())
+}
+
+/// Check whether the Zenity executable configured in the installed [ZenitySettings] is usable,
+/// without showing any dialog. Only `--version` is run, and it's killed if it hangs. The
+/// version is cached per program path, so repeated checks are cheap; see
+/// [crate::ZenitySession::invalidate] to notice an executable installed meanwhile.
+pub fn health_check() -> crate::Result<Health> {
+    let program = ZenitySettings::current().resolved_program();
+
+    let (found, version) = match crate::program_version(&program) {
+        Ok(version) => (true, Some(version)),
+        Err(crate::Error::ZenityNotInstalled { .. }) => (false, None),
+        Err(crate::Error::ParseResultFailure(_)) => (true, None),
+        Err(err) => Err(err)?,
+    };
+
+    Ok(Health {
+        program,
+        found,
+        version,
+        display: display::is_display_set(|key| env::var_os(key)),
+    })
+}
+
+/// The result of [health_check].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Health {
+    /// The program that was checked
+    pub program: PathBuf,
+    /// Whether the program was found
+    pub found: bool,
+    /// The version the program reported, if it could be parsed
+    pub version: Option<crate::Version>,
+    /// Whether `DISPLAY` or `WAYLAND_DISPLAY` is set
+    pub display: bool,
+}
+
+impl Health {
+    /// Whether dialogs can be shown: the program was found, reported a version, and a display
+    /// is available.
+    pub fn is_ok(&self) -> bool {
+        self.found && self.version.is_some() && self.display
+    }
+}
@@ -0,0 +1,61 @@
+use std::{
+    env,
+    ffi::OsStr,
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// The extensions icon themes store their images with.
+const EXTENSIONS: [&str; 3] = ["png", "svg", "xpm"];
+
+/// The directories searched for icons, in the order of the freedesktop icon theme
+/// specification: `~/.icons`, then `icons` in each XDG data directory, then `/usr/share/pixmaps`.
+fn base_dirs() -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    if let Some(home) = env::var_os("HOME").filter(|home| !home.is_empty()) {
+        dirs.push(Path::new(&home).join(".icons"));
+    }
+
+    match env::var_os("XDG_DATA_HOME").filter(|data_home| !data_home.is_empty()) {
+        Some(data_home) => dirs.push(Path::new(&data_home).join("icons")),
+        None => {
+            if let Some(home) = env::var_os("HOME").filter(|home| !home.is_empty()) {
+                dirs.push(Path::new(&home).join(".local/share/icons"));
+            }
+        }
+    }
+
+    let data_dirs = env::var_os("XDG_DATA_DIRS")
+        .filter(|data_dirs| !data_dirs.is_empty())
+        .unwrap_or_else(|| "/usr/local/share:/usr/share".into());
+    dirs.extend(env::split_paths(&data_dirs).map(|dir| dir.join("icons")));
+
+    dirs.push(PathBuf::from("/usr/share/pixmaps"));
+    dirs
+}
+
+/// Whether an icon with the name exists in any installed icon theme, in any size.
+pub(crate) fn contains(name: &str) -> bool {
+    base_dirs().iter().any(|dir| find_in(dir, name))
+}
+
+fn find_in(dir: &Path, name: &str) -> bool {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return false;
+    };
+
+    entries.flatten().any(|entry| {
+        let path = entry.path();
+        match entry.file_type() {
+            Ok(file_type) if file_type.is_dir() => find_in(&path, name),
+            Ok(_) => {
+                path.file_stem() == Some(OsStr::new(name))
+                    && path
+                        .extension()
+                        .and_then(OsStr::to_str)
+                        .is_some_and(|extension| EXTENSIONS.contains(&extension))
+            }
+            Err(_) => false,
+        }
+    })
+}
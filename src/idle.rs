@@ -0,0 +1,234 @@
+use std::{
+    convert::TryFrom,
+    thread,
+    time::{Duration, Instant},
+};
+use x11rb::{connection::Connection, protocol::screensaver::ConnectionExt as _};
+
+/// Reports whether the user is currently idle. See [SystemIdleSource] for the implementation
+/// used by default.
+pub trait IdleSource {
+    /// Whether the user has been idle for at least `threshold`. Returns [None] if the idle state
+    /// can't be determined.
+    fn is_idle(&self, threshold: Duration) -> Option<bool>;
+}
+
+/// Queries the idle state of the desktop session, trying the X11 screensaver extension, the
+/// `org.freedesktop.ScreenSaver` D-Bus service, and logind's `IdleHint`, in that order.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemIdleSource;
+
+impl IdleSource for SystemIdleSource {
+    fn is_idle(&self, threshold: Duration) -> Option<bool> {
+        x11_idle_time()
+            .or_else(screensaver_idle_time)
+            .map(|idle_time| idle_time >= threshold)
+            .or_else(logind_idle_hint)
+    }
+}
+
+fn x11_idle_time() -> Option<Duration> {
+    let (conn, screen_num) = x11rb::connect(None).ok()?;
+    let root = conn.setup().roots.get(screen_num)?.root;
+    let info = conn.screensaver_query_info(root).ok()?.reply().ok()?;
+    Some(Duration::from_millis(info.ms_since_user_input.into()))
+}
+
+fn screensaver_idle_time() -> Option<Duration> {
+    let conn = zbus::blocking::Connection::session().ok()?;
+    let reply = conn
+        .call_method(
+            Some("org.freedesktop.ScreenSaver"),
+            "/org/freedesktop/ScreenSaver",
+            Some("org.freedesktop.ScreenSaver"),
+            "GetSessionIdleTime",
+            &(),
+        )
+        .ok()?;
+    let idle_ms: u32 = reply.body().deserialize().ok()?;
+    Some(Duration::from_millis(idle_ms.into()))
+}
+
+fn logind_idle_hint() -> Option<bool> {
+    let conn = zbus::blocking::Connection::system().ok()?;
+    let reply = conn
+        .call_method(
+            Some("org.freedesktop.login1"),
+            "/org/freedesktop/login1/session/auto",
+            Some("org.freedesktop.DBus.Properties"),
+            "Get",
+            &("org.freedesktop.login1.Session", "IdleHint"),
+        )
+        .ok()?;
+    let value: zbus::zvariant::OwnedValue = reply.body().deserialize().ok()?;
+    bool::try_from(value).ok()
+}
+
+/// What to do when the user is still idle once the maximum deferral has elapsed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum DeferralExpiry {
+    /// Show the dialog anyway.
+    #[default]
+    ShowAnyway,
+    /// Don't show the dialog and fail with [crate::Error::DeferredExpired].
+    Fail,
+}
+
+/// Settings for deferring a dialog while the user is idle. See
+/// [crate::ZenityDialog::with_defer_while_idle].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IdleDeferral {
+    /// The longest time to wait for the user to become active
+    pub max_defer: Duration,
+    /// How often to check the idle state
+    pub poll: Duration,
+    /// How long the user must have been inactive to be considered idle
+    pub idle_threshold: Duration,
+    /// What to do when the user is still idle after `max_defer`
+    pub on_expiry: DeferralExpiry,
+}
+
+impl IdleDeferral {
+    const DEFAULT_IDLE_THRESHOLD: Duration = Duration::from_secs(60);
+
+    /// Wait at most `max_defer`, checking the idle state every `poll`.
+    pub fn new(max_defer: Duration, poll: Duration) -> Self {
+        Self {
+            max_defer,
+            poll,
+            idle_threshold: Self::DEFAULT_IDLE_THRESHOLD,
+            on_expiry: Default::default(),
+        }
+    }
+
+    /// Consider the user idle after this long without input. Defaults to one minute.
+    pub fn with_idle_threshold(mut self, idle_threshold: Duration) -> Self {
+        self.idle_threshold = idle_threshold;
+        self
+    }
+
+    /// Choose what happens when the user is still idle after the maximum deferral.
+    pub fn with_on_expiry(mut self, on_expiry: DeferralExpiry) -> Self {
+        self.on_expiry = on_expiry;
+        self
+    }
+
+    /// Block until the user is active according to the [SystemIdleSource].
+    pub(crate) fn wait(&self) -> crate::Result<()> {
+        self.wait_with(&SystemIdleSource)
+    }
+
+    /// Block until `source` reports the user as active, or until the maximum deferral has
+    /// elapsed. If the idle state can't be determined, the user is assumed to be active.
+    pub fn wait_with(&self, source: &impl IdleSource) -> crate::Result<()> {
+        let deadline = Instant::now() + self.max_defer;
+
+        loop {
+            if source.is_idle(self.idle_threshold) != Some(true) {
+                return Ok(());
+            }
+
+            let now = Instant::now();
+            if now >= deadline {
+                return match self.on_expiry {
+                    DeferralExpiry::ShowAnyway => Ok(()),
+                    DeferralExpiry::Fail => Err(crate::Error::DeferredExpired(self.max_defer)),
+                };
+            }
+
+            thread::sleep(self.poll.min(deadline - now));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DeferralExpiry, IdleDeferral, IdleSource};
+    use std::{
+        cell::RefCell,
+        collections::VecDeque,
+        time::{Duration, Instant},
+    };
+
+    /// Reports the scripted idle states in order, then the last one forever, recording the
+    /// thresholds it was asked about.
+    struct ScriptedIdle {
+        states: RefCell<VecDeque<Option<bool>>>,
+        thresholds: RefCell<Vec<Duration>>,
+    }
+
+    impl ScriptedIdle {
+        fn new(states: &[Option<bool>]) -> Self {
+            Self {
+                states: RefCell::new(states.iter().copied().collect()),
+                thresholds: RefCell::new(Vec::new()),
+            }
+        }
+
+        fn polls(&self) -> usize {
+            self.thresholds.borrow().len()
+        }
+    }
+
+    impl IdleSource for ScriptedIdle {
+        fn is_idle(&self, threshold: Duration) -> Option<bool> {
+            self.thresholds.borrow_mut().push(threshold);
+            let mut states = self.states.borrow_mut();
+            match states.len() {
+                1 => states[0],
+                _ => states.pop_front().flatten(),
+            }
+        }
+    }
+
+    fn deferral() -> IdleDeferral {
+        IdleDeferral::new(Duration::from_millis(200), Duration::from_millis(5))
+    }
+
+    #[test]
+    fn an_active_user_isnt_waited_for() {
+        let source = ScriptedIdle::new(&[Some(false)]);
+        deferral().wait_with(&source).unwrap();
+        assert_eq!(source.polls(), 1);
+    }
+
+    #[test]
+    fn waits_until_the_user_is_back() {
+        let source = ScriptedIdle::new(&[Some(true), Some(true), Some(false)]);
+        deferral()
+            .with_idle_threshold(Duration::from_secs(5))
+            .wait_with(&source)
+            .unwrap();
+        assert_eq!(*source.thresholds.borrow(), [Duration::from_secs(5); 3]);
+    }
+
+    #[test]
+    fn an_unknown_idle_state_counts_as_active() {
+        let source = ScriptedIdle::new(&[Some(true), None]);
+        deferral().wait_with(&source).unwrap();
+        assert_eq!(source.polls(), 2);
+    }
+
+    #[test]
+    fn shows_anyway_once_the_deferral_expires() {
+        let source = ScriptedIdle::new(&[Some(true)]);
+        let start = Instant::now();
+        deferral().wait_with(&source).unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(200));
+        assert!(source.polls() > 1);
+    }
+
+    #[test]
+    fn fails_once_the_deferral_expires_when_asked_to() {
+        let source = ScriptedIdle::new(&[Some(true)]);
+        let err = deferral()
+            .with_on_expiry(DeferralExpiry::Fail)
+            .wait_with(&source)
+            .unwrap_err();
+        assert!(
+            matches!(err, crate::Error::DeferredExpired(max) if max == Duration::from_millis(200)),
+            "{:?}",
+            err
+        );
+    }
+}
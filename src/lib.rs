@@ -1,20 +1,151 @@
 #![doc = include_str!("./../README.md")]
 #![deny(missing_docs)]
 
+#[cfg(feature = "anyhow")]
+extern crate anyhow;
+#[cfg(feature = "async-io")]
+extern crate async_process;
 #[cfg(feature = "chrono")]
 extern crate chrono;
+#[cfg(unix)]
+extern crate libc;
+#[cfg(feature = "notify-fallback")]
+extern crate notify_rust;
+#[cfg(test)]
+extern crate proptest;
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(feature = "serde")]
+extern crate serde_json;
+#[cfg(feature = "serde")]
+extern crate serde_path_to_error;
+#[cfg(feature = "async-tokio")]
+extern crate tokio;
+#[cfg(feature = "toml")]
+extern crate toml;
+#[cfg(feature = "idle-detect")]
+extern crate x11rb;
+#[cfg(any(feature = "idle-detect", feature = "portal"))]
+extern crate zbus;
+#[cfg(feature = "derive")]
+extern crate zenity_dialog_derive;
 
 mod arg;
+/// Abstracts over the program that renders dialogs.
+pub mod backend;
+/// Delivers dialog outcomes as messages for GUI event loops.
+pub mod bridge;
+mod cancel;
+mod capabilities;
 /// Contains configuration structs for the various types of Zenity dialogs.
 pub mod dialog;
+mod display;
 mod error;
+// Also compiled for the crate's own tests, which run dialogs against the stub.
+#[cfg(all(any(test, feature = "test-harness"), unix))]
+#[cfg_attr(not(feature = "test-harness"), allow(dead_code))]
+mod harness;
+mod health;
+mod icon_theme;
+#[cfg(feature = "idle-detect")]
+mod idle;
+mod markup;
+#[cfg(feature = "notify-fallback")]
+mod notify;
+mod probe;
+pub mod quick;
+#[cfg(all(feature = "serde", unix))]
+mod record;
+mod runner;
+mod session;
+mod settings;
+mod shell;
+mod signals;
+#[cfg(feature = "sound")]
+mod sound;
+mod stderr;
+#[cfg(feature = "tty-fallback")]
+mod tty;
+mod validation;
+mod version;
 
 /// Alias for the common [Result] produced by operations in this crate.
 pub type Result<T> = std::result::Result<T, crate::error::Error>;
 
 pub use crate::arg::Arg;
+pub use crate::cancel::CancelFlag;
+pub use crate::capabilities::{capabilities, program_capabilities, Capabilities};
+#[cfg(feature = "question")]
+pub use crate::dialog::confirm;
+pub use crate::dialog::DialogDiff;
+pub use crate::dialog::DialogDismissed;
+#[cfg(feature = "serde")]
+pub use crate::dialog::DialogSpec;
+pub use crate::dialog::DryRun;
+pub use crate::dialog::ShowExt;
 pub use crate::dialog::ZenityDialog;
 pub use crate::dialog::ZenityDialogExtButton;
 pub use crate::dialog::ZenityOutput;
 pub use crate::dialog::ZenityOutputExtButton;
-pub use crate::error::Error;
+pub use crate::dialog::{DialogOutput, RawOutput, ShownDialog};
+pub use crate::dialog::{DynDialog, DynOutput};
+pub use crate::display::RenderedBackend;
+pub use crate::error::{Error, ParseError};
+#[cfg(all(feature = "test-harness", unix))]
+pub use crate::harness::TestZenity;
+pub use crate::health::{health_check, is_available, Health};
+#[cfg(feature = "idle-detect")]
+pub use crate::idle::{DeferralExpiry, IdleDeferral, IdleSource, SystemIdleSource};
+pub use crate::markup::{escape_markup, markup_to_plain, Markup, PlainMarkupStyle};
+pub use crate::runner::{CommandRunner, SystemRunner};
+#[cfg(feature = "test-util")]
+pub use crate::runner::{Invocation, MockResponse, MockRunner};
+pub use crate::session::ZenitySession;
+pub use crate::settings::ZenitySettings;
+#[cfg(feature = "sound")]
+pub use crate::sound::SoundCue;
+pub use crate::stderr::StderrFilter;
+#[cfg(feature = "tty-fallback")]
+pub use crate::tty::TtyPrompt;
+pub use crate::validation::ValidationError;
+pub use crate::version::{program_version, zenity_version, Version};
+/// Derives the arguments of a custom application from `#[zenity(...)]` attributes, so that
+/// options the crate doesn't cover can be used without writing `to_argv` by hand. Fields are
+/// passed in order, and fields without an attribute are skipped.
+///
+/// ```
+/// # use zenity_dialog::{dialog::{DialogOutput, ZenityApplication}, ToArgVector, ZenityDialog};
+/// #[derive(Debug, Clone, Default, ToArgVector)]
+/// #[zenity(subcommand = "--progress")]
+/// struct Progress {
+///     #[zenity(arg = "--text")]
+///     text: Option<String>,
+///     #[zenity(arg = "--percentage")]
+///     percentage: Option<u8>,
+///     #[zenity(flag = "--pulsate")]
+///     pulsate: bool,
+/// }
+///
+/// impl ZenityApplication for Progress {
+///     type Return = String;
+///
+///     fn parse(&self, output: &DialogOutput) -> zenity_dialog::Result<String> {
+///         Ok(output.stdout().to_owned())
+///     }
+/// }
+///
+/// let progress = Progress { percentage: Some(40), pulsate: true, ..Default::default() };
+/// assert_eq!(
+///     ZenityDialog::new(progress).to_command_string(),
+///     "zenity --progress --percentage=40 --pulsate"
+/// );
+/// ```
+#[cfg(feature = "derive")]
+pub use zenity_dialog_derive::ToArgVector;
+
+/// Items used by the code the derive macros generate, which isn't part of the public API.
+#[cfg(feature = "derive")]
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::dialog::ToArgVector;
+}
@@ -0,0 +1,399 @@
+/// Determines how bold text is represented when Pango markup is flattened into plain text by
+/// [markup_to_plain].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum PlainMarkupStyle {
+    /// Drop the tags and keep only their textual content.
+    #[default]
+    Strip,
+    /// Render bold text in uppercase.
+    Uppercase,
+    /// Surround bold text with asterisks, as in `*bold*`.
+    Asterisks,
+}
+
+/// Convert Pango markup into plain text for dialog programs that can't render it.
+///
+/// Tags are removed while their textual content is preserved, `<br>`/`<br/>` and `\r\n` are
+/// normalized to `\n`, and character entities are decoded. Bold text (`<b>`, `<strong>`) is
+/// rendered according to the provided [PlainMarkupStyle]. Malformed markup, such as an unclosed
+/// `<` or an unknown entity, is passed through verbatim rather than dropped.
+pub fn markup_to_plain(markup: &str, style: PlainMarkupStyle) -> String {
+    let mut plain = String::with_capacity(markup.len());
+    let mut bold_depth = 0usize;
+    let mut rest = markup;
+
+    while let Some(ch) = rest.chars().next() {
+        match ch {
+            '<' if starts_tag(rest) => {
+                if let Some(end) = rest.find('>') {
+                    let tag = Tag::parse(&rest[1..end]);
+                    match tag.name.as_str() {
+                        "br" => plain.push('\n'),
+                        "b" | "strong" => {
+                            if tag.closing {
+                                bold_depth = bold_depth.saturating_sub(1);
+                            } else if !tag.self_closing {
+                                bold_depth += 1;
+                            }
+                            if style == PlainMarkupStyle::Asterisks && !tag.self_closing {
+                                plain.push('*');
+                            }
+                        }
+                        _ => {}
+                    }
+                    rest = &rest[end + 1..];
+                    continue;
+                }
+                plain.push(ch);
+            }
+            '&' => {
+                if let Some((decoded, len)) = decode_entity(rest) {
+                    push_text(&mut plain, decoded, bold_depth > 0, style);
+                    rest = &rest[len..];
+                    continue;
+                }
+                plain.push(ch);
+            }
+            '\r' if rest.starts_with("\r\n") => {}
+            _ => push_text(&mut plain, ch, bold_depth > 0, style),
+        }
+        rest = &rest[ch.len_utf8()..];
+    }
+
+    plain
+}
+
+/// Escape the characters Pango interprets as markup, `& < > ' "`, so that the text is shown as
+/// typed, as for a file name in a dialog's text. Entities that are already escaped are kept, so
+/// escaping twice gives the same result as escaping once.
+///
+/// ```
+/// # use zenity_dialog::escape_markup;
+/// assert_eq!(escape_markup("a<b&c.txt"), "a&lt;b&amp;c.txt");
+/// assert_eq!(escape_markup(&escape_markup("a<b&c.txt")), "a&lt;b&amp;c.txt");
+/// ```
+pub fn escape_markup(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    push_escaped(&mut escaped, text, true);
+    escaped
+}
+
+/// Escape the text onto `escaped`, keeping existing entities if `keep_entities` is set.
+fn push_escaped(escaped: &mut String, text: &str, keep_entities: bool) {
+    for (index, ch) in text.char_indices() {
+        match ch {
+            '&' if keep_entities && decode_entity(&text[index..]).is_some() => escaped.push('&'),
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '\'' => escaped.push_str("&apos;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(ch),
+        }
+    }
+}
+
+/// Builds Pango markup for a dialog's text, escaping the text it's given so that only its own
+/// tags are interpreted. Every method takes plain text, or another [Markup] for nested tags.
+/// Set on a dialog with `with_markup`, as in [crate::dialog::Info::with_markup].
+///
+/// ```
+/// # use zenity_dialog::Markup;
+/// let path = "/tmp/a<b&c.txt";
+/// let markup = Markup::new()
+///     .bold("Failed:")
+///     .text(" ")
+///     .italic(Markup::new().text("copying ").monospace(path))
+///     .build();
+/// assert_eq!(
+///     markup,
+///     "<b>Failed:</b> <i>copying <tt>/tmp/a&lt;b&amp;c.txt</tt></i>"
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Markup {
+    markup: String,
+}
+
+impl Markup {
+    /// Empty markup.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append plain text or nested markup.
+    pub fn text(mut self, text: impl Into<Markup>) -> Self {
+        self.markup.push_str(&text.into().markup);
+        self
+    }
+
+    /// Append bold text.
+    pub fn bold(self, text: impl Into<Markup>) -> Self {
+        self.tag("b", text)
+    }
+
+    /// Append italic text.
+    pub fn italic(self, text: impl Into<Markup>) -> Self {
+        self.tag("i", text)
+    }
+
+    /// Append underlined text.
+    pub fn underline(self, text: impl Into<Markup>) -> Self {
+        self.tag("u", text)
+    }
+
+    /// Append struck-through text.
+    pub fn strikethrough(self, text: impl Into<Markup>) -> Self {
+        self.tag("s", text)
+    }
+
+    /// Append text in a monospace font, as for a path or a command.
+    pub fn monospace(self, text: impl Into<Markup>) -> Self {
+        self.tag("tt", text)
+    }
+
+    /// Append text with the Pango span attributes, as in `[("foreground", "red")]`. The values
+    /// are escaped.
+    ///
+    /// ```
+    /// # use zenity_dialog::Markup;
+    /// let markup = Markup::new().span([("foreground", "red"), ("size", "large")], "Stop");
+    /// assert_eq!(markup.build(), r#"<span foreground="red" size="large">Stop</span>"#);
+    /// ```
+    pub fn span<'a>(
+        mut self,
+        attributes: impl IntoIterator<Item = (&'a str, &'a str)>,
+        text: impl Into<Markup>,
+    ) -> Self {
+        self.markup.push_str("<span");
+        for (name, value) in attributes {
+            self.markup.push(' ');
+            self.markup.push_str(name);
+            self.markup.push_str("=\"");
+            push_escaped(&mut self.markup, value, false);
+            self.markup.push('"');
+        }
+        self.markup.push('>');
+        self.markup.push_str(&text.into().markup);
+        self.markup.push_str("</span>");
+        self
+    }
+
+    /// Append a line break.
+    pub fn line_break(mut self) -> Self {
+        self.markup.push('\n');
+        self
+    }
+
+    /// The markup, as passed to Zenity.
+    pub fn as_str(&self) -> &str {
+        &self.markup
+    }
+
+    /// The markup, as passed to Zenity.
+    pub fn build(self) -> String {
+        self.markup
+    }
+
+    fn tag(mut self, tag: &str, text: impl Into<Markup>) -> Self {
+        self.markup.push('<');
+        self.markup.push_str(tag);
+        self.markup.push('>');
+        self.markup.push_str(&text.into().markup);
+        self.markup.push_str("</");
+        self.markup.push_str(tag);
+        self.markup.push('>');
+        self
+    }
+}
+
+/// Plain text, escaped so that it's shown as typed, including any entities.
+impl From<&str> for Markup {
+    fn from(text: &str) -> Self {
+        let mut markup = String::with_capacity(text.len());
+        push_escaped(&mut markup, text, false);
+        Self { markup }
+    }
+}
+
+/// Plain text. See `From<&str>`.
+impl From<String> for Markup {
+    fn from(text: String) -> Self {
+        Self::from(text.as_str())
+    }
+}
+
+/// Plain text. See `From<&str>`.
+impl From<&String> for Markup {
+    fn from(text: &String) -> Self {
+        Self::from(text.as_str())
+    }
+}
+
+impl std::fmt::Display for Markup {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.markup)
+    }
+}
+
+/// Whether the `<` at the start of `text` plausibly opens a tag rather than being a stray
+/// comparison operator.
+fn starts_tag(text: &str) -> bool {
+    text[1..]
+        .chars()
+        .next()
+        .is_some_and(|c| c == '/' || c.is_ascii_alphabetic())
+}
+
+fn push_text(plain: &mut String, ch: char, bold: bool, style: PlainMarkupStyle) {
+    if bold && style == PlainMarkupStyle::Uppercase {
+        plain.extend(ch.to_uppercase());
+    } else {
+        plain.push(ch);
+    }
+}
+
+/// Decode the entity at the start of `text`, returning the character and the number of bytes
+/// consumed.
+fn decode_entity(text: &str) -> Option<(char, usize)> {
+    // The longest entity we accept is a hex reference for the largest code point: `&#x10FFFF;`
+    let end = text.char_indices().take(11).find(|(_, c)| *c == ';')?.0;
+    let name = &text[1..end];
+    let decoded = match name {
+        "amp" => '&',
+        "lt" => '<',
+        "gt" => '>',
+        "quot" => '"',
+        "apos" => '\'',
+        _ => {
+            let code = match name.strip_prefix("#x").or_else(|| name.strip_prefix("#X")) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                None => name.strip_prefix('#')?.parse().ok()?,
+            };
+            char::from_u32(code)?
+        }
+    };
+
+    Some((decoded, end + 1))
+}
+
+struct Tag {
+    name: String,
+    closing: bool,
+    self_closing: bool,
+}
+
+impl Tag {
+    fn parse(inner: &str) -> Self {
+        let inner = inner.trim();
+        let (closing, inner) = match inner.strip_prefix('/') {
+            Some(inner) => (true, inner),
+            None => (false, inner),
+        };
+        let (self_closing, inner) = match inner.strip_suffix('/') {
+            Some(inner) => (true, inner),
+            None => (false, inner),
+        };
+        let name = inner
+            .split(|c: char| c.is_whitespace())
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+
+        Self {
+            name,
+            closing,
+            self_closing,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_the_text_of_nested_tags() {
+        assert_eq!(
+            markup_to_plain(
+                "<span foreground=\"red\">Disk <i>almost <u>full</u></i></span>!",
+                PlainMarkupStyle::Strip
+            ),
+            "Disk almost full!"
+        );
+    }
+
+    #[test]
+    fn renders_nested_bold_text_in_the_style() {
+        let markup = "Delete <b>all <i>backups</i></b> now?";
+        assert_eq!(
+            markup_to_plain(markup, PlainMarkupStyle::Strip),
+            "Delete all backups now?"
+        );
+        assert_eq!(
+            markup_to_plain(markup, PlainMarkupStyle::Uppercase),
+            "Delete ALL BACKUPS now?"
+        );
+        assert_eq!(
+            markup_to_plain(markup, PlainMarkupStyle::Asterisks),
+            "Delete *all backups* now?"
+        );
+    }
+
+    #[test]
+    fn normalizes_line_breaks() {
+        assert_eq!(
+            markup_to_plain(
+                "one<br>two<br/>three<BR />four\r\nfive\nsix",
+                PlainMarkupStyle::Strip
+            ),
+            "one\ntwo\nthree\nfour\nfive\nsix"
+        );
+    }
+
+    #[test]
+    fn decodes_entities() {
+        assert_eq!(
+            markup_to_plain(
+                "a &lt; b &amp;&amp; c &gt; d &quot;e&quot; &apos;f&apos; &#233; &#x1F600;",
+                PlainMarkupStyle::Strip
+            ),
+            "a < b && c > d \"e\" 'f' é 😀"
+        );
+    }
+
+    #[test]
+    fn uppercases_decoded_entities_in_bold_text() {
+        assert_eq!(
+            markup_to_plain("<b>&#x61;nd</b>", PlainMarkupStyle::Uppercase),
+            "AND"
+        );
+    }
+
+    #[test]
+    fn passes_malformed_markup_through() {
+        for (markup, plain) in [
+            ("1 < 2", "1 < 2"),
+            ("a <b unclosed", "a <b unclosed"),
+            ("fish & chips", "fish & chips"),
+            ("&unknown; &#xZZ; &#1114112;", "&unknown; &#xZZ; &#1114112;"),
+            ("trailing &", "trailing &"),
+            ("</b>stray close", "stray close"),
+            ("<", "<"),
+        ] {
+            assert_eq!(
+                markup_to_plain(markup, PlainMarkupStyle::Strip),
+                plain,
+                "{markup}"
+            );
+        }
+    }
+
+    #[test]
+    fn survives_multibyte_text_around_tags() {
+        assert_eq!(
+            markup_to_plain("<b>größe</b> ≤ 10 &lt;ß&gt;", PlainMarkupStyle::Uppercase),
+            "GRÖSSE ≤ 10 <ß>"
+        );
+    }
+}
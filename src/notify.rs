@@ -0,0 +1,254 @@
+use crate::CommandRunner;
+use std::{
+    ffi::{OsStr, OsString},
+    io,
+    process::Output,
+    time::Duration,
+};
+
+/// Which program shows a notification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Notifier {
+    Zenity,
+    /// The desktop's notification service, through `notify-rust`.
+    Native,
+}
+
+/// Pick the program showing a notification: the notification service if the caller prefers
+/// it, otherwise Zenity if it's installed, otherwise the notification service. Whether Zenity
+/// is installed is checked by running `--version` through the runner.
+pub(crate) fn choose(
+    prefer_native: bool,
+    runner: &impl CommandRunner,
+    program: &OsStr,
+) -> Notifier {
+    if prefer_native {
+        return Notifier::Native;
+    }
+
+    match runner.run(program, &["--version".into()]) {
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Notifier::Native,
+        _ => Notifier::Zenity,
+    }
+}
+
+/// A notification, translated from Zenity's arguments to those of the notification service.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct NativeNotification {
+    pub(crate) summary: String,
+    pub(crate) body: Option<String>,
+    pub(crate) icon: Option<String>,
+    pub(crate) timeout: Option<Duration>,
+}
+
+impl NativeNotification {
+    /// Translate the arguments of a notification. The title, if any, becomes the summary and
+    /// the text the body; otherwise the text is the summary. Listen mode fails with
+    /// [crate::Error::UnsupportedByBackend], as do options that aren't understood.
+    pub(crate) fn from_args(args: &[OsString]) -> crate::Result<Self> {
+        let unsupported = |reason: String| crate::Error::UnsupportedByBackend {
+            backend: "the notification service".to_owned(),
+            reason,
+        };
+
+        let mut title = None;
+        let mut text = None;
+        let mut notification = Self::default();
+        for arg in args.iter().skip(1) {
+            let arg = arg.to_string_lossy();
+            let (name, value) = match arg.split_once('=') {
+                Some((name, value)) => (name, Some(value)),
+                None => (&*arg, None),
+            };
+
+            match (name, value) {
+                ("--text", Some(value)) => text = Some(value.to_owned()),
+                ("--title", Some(value)) => title = Some(value.to_owned()),
+                ("--icon-name" | "--icon" | "--window-icon", Some(value)) => {
+                    notification.icon = Some(value.to_owned())
+                }
+                ("--timeout", Some(value)) => {
+                    let seconds = value
+                        .parse()
+                        .map_err(|_| unsupported(format!("the timeout {value} isn't a number")))?;
+                    notification.timeout = Some(Duration::from_secs(seconds));
+                }
+                ("--listen", None) => {
+                    return Err(unsupported(
+                        "listen mode needs Zenity's notification icon".to_owned(),
+                    ))
+                }
+                // Sizes and modality mean nothing for a notification.
+                ("--width" | "--height", Some(_)) | ("--modal", None) => {}
+                _ => return Err(unsupported(format!("the option {arg} isn't supported"))),
+            }
+        }
+
+        match title {
+            Some(title) => {
+                notification.summary = title;
+                notification.body = text;
+            }
+            None => notification.summary = text.unwrap_or_default(),
+        }
+        Ok(notification)
+    }
+
+    /// Send the notification. Succeeds with the output of a Zenity notification that was
+    /// shown.
+    pub(crate) fn show(&self) -> crate::Result<Output> {
+        let mut notification = notify_rust::Notification::new();
+        notification.summary(&self.summary);
+        if let Some(ref body) = self.body {
+            notification.body(body);
+        }
+
+        if let Some(ref icon) = self.icon {
+            notification.icon(icon);
+        }
+
+        if let Some(timeout) = self.timeout {
+            notification.timeout(timeout);
+        }
+
+        notification
+            .show()
+            .map_err(|err| crate::Error::UnexpectedIoError(io::Error::other(err)))?;
+        Ok(Output {
+            status: success(),
+            stdout: Vec::new(),
+            stderr: Vec::new(),
+        })
+    }
+}
+
+#[cfg(unix)]
+fn success() -> std::process::ExitStatus {
+    use std::os::unix::process::ExitStatusExt;
+    std::process::ExitStatus::from_raw(0)
+}
+
+#[cfg(windows)]
+fn success() -> std::process::ExitStatus {
+    use std::os::windows::process::ExitStatusExt;
+    std::process::ExitStatus::from_raw(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{choose, NativeNotification, Notifier};
+    use crate::{
+        dialog::Notification,
+        runner::{MockResponse, MockRunner},
+        ZenityDialog,
+    };
+    use std::{ffi::OsStr, time::Duration};
+
+    fn installed() -> MockRunner {
+        MockRunner::new().respond_to_arg("--version", MockResponse::affirmed("4.0.1"))
+    }
+
+    /// Responds to nothing, so that every program is missing.
+    fn missing() -> MockRunner {
+        MockRunner::new()
+    }
+
+    #[test]
+    fn prefers_zenity_when_its_installed() {
+        let runner = installed();
+        assert_eq!(
+            choose(false, &runner, OsStr::new("zenity")),
+            Notifier::Zenity
+        );
+
+        let invocations = runner.invocations();
+        assert_eq!(invocations.len(), 1);
+        assert!(invocations[0].has_arg("--version"));
+    }
+
+    #[test]
+    fn falls_back_when_zenity_is_missing() {
+        assert_eq!(
+            choose(false, &missing(), OsStr::new("zenity")),
+            Notifier::Native
+        );
+    }
+
+    #[test]
+    fn an_explicit_preference_wins_without_probing() {
+        let runner = installed();
+        assert_eq!(
+            choose(true, &runner, OsStr::new("zenity")),
+            Notifier::Native
+        );
+        assert!(runner.invocations().is_empty());
+        assert_eq!(
+            choose(true, &missing(), OsStr::new("zenity")),
+            Notifier::Native
+        );
+    }
+
+    #[test]
+    fn zenity_failing_to_report_its_version_isnt_a_missing_zenity() {
+        let runner = MockRunner::new().respond_otherwise(MockResponse::new(255));
+        assert_eq!(
+            choose(false, &runner, OsStr::new("zenity")),
+            Notifier::Zenity
+        );
+    }
+
+    fn translated(dialog: ZenityDialog<Notification>) -> crate::Result<NativeNotification> {
+        NativeNotification::from_args(&dialog.force_legacy_flags().to_args())
+    }
+
+    #[test]
+    fn translates_the_text_icon_and_timeout() {
+        let notification = translated(
+            ZenityDialog::new(Notification::new().with_text("Backup done"))
+                .with_icon("drive-harddisk")
+                .with_timeout(Duration::from_secs(5)),
+        )
+        .unwrap();
+
+        assert_eq!(
+            notification,
+            NativeNotification {
+                summary: "Backup done".to_owned(),
+                body: None,
+                icon: Some("drive-harddisk".to_owned()),
+                timeout: Some(Duration::from_secs(5)),
+            }
+        );
+    }
+
+    #[test]
+    fn a_title_becomes_the_summary() {
+        let notification = translated(
+            ZenityDialog::new(Notification::new().with_text("3 files copied")).with_title("Backup"),
+        )
+        .unwrap();
+
+        assert_eq!(notification.summary, "Backup");
+        assert_eq!(notification.body.as_deref(), Some("3 files copied"));
+    }
+
+    #[test]
+    fn the_modern_icon_option_translates_too() {
+        let args = ZenityDialog::new(Notification::new())
+            .with_icon("drive-harddisk")
+            .force_modern_flags()
+            .to_args();
+        let notification = NativeNotification::from_args(&args).unwrap();
+        assert_eq!(notification.icon.as_deref(), Some("drive-harddisk"));
+    }
+
+    #[test]
+    fn listen_mode_is_unsupported() {
+        let err = translated(ZenityDialog::new(Notification::new().with_listen())).unwrap_err();
+        assert!(
+            matches!(err, crate::Error::UnsupportedByBackend { ref reason, .. } if reason.contains("listen")),
+            "{:?}",
+            err
+        );
+    }
+}
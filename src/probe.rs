@@ -0,0 +1,193 @@
+use std::{
+    collections::HashMap,
+    io::{self, Read},
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    sync::{Mutex, OnceLock},
+    thread,
+    time::{Duration, Instant},
+};
+
+/// How long a probe may run before it's killed, so that a hung program can't block the caller.
+const TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Run the program with a single informational argument, such as `--version`, and return its
+/// stdout. The program is never expected to show any UI, and is killed if it doesn't exit
+/// within a couple of seconds.
+pub(crate) fn run(program: &Path, arg: &str) -> crate::Result<String> {
+    let mut child = Command::new(program)
+        .arg(arg)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|err| crate::Error::spawn(program, vec![arg.into()], err))?;
+
+    // Read on another thread so that a chatty program can't block on a full pipe while it's
+    // being waited on.
+    let mut stdout = child.stdout.take().expect("stdout is piped");
+    let reader = thread::spawn(move || {
+        let mut buf = Vec::new();
+        stdout.read_to_end(&mut buf).map(|_| buf)
+    });
+
+    let deadline = Instant::now() + TIMEOUT;
+    while child
+        .try_wait()
+        .map_err(crate::Error::UnexpectedIoError)?
+        .is_none()
+    {
+        if Instant::now() >= deadline {
+            // The program may already have exited in the meantime, so failures are ignored.
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(crate::Error::UnexpectedIoError(io::Error::new(
+                io::ErrorKind::TimedOut,
+                format!(
+                    "{} {arg} did not exit within {TIMEOUT:?}",
+                    program.display()
+                ),
+            )));
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+
+    let stdout = reader
+        .join()
+        .expect("the reader thread doesn't panic")
+        .map_err(crate::Error::UnexpectedIoError)?;
+    String::from_utf8(stdout).map_err(crate::Error::InvalidUtf8FromStdout)
+}
+
+/// A process-wide cache of probe results, keyed by program path. Failed probes are cached as
+/// well, so that a program that can't be probed, such as a Zenity clone without `--help-all`,
+/// isn't run again for every dialog; the cached error is reported again instead.
+pub(crate) struct ProbeCache<T> {
+    entries: OnceLock<Mutex<HashMap<PathBuf, crate::Result<T>>>>,
+}
+
+impl<T> ProbeCache<T>
+where
+    T: Clone,
+{
+    pub(crate) const fn new() -> Self {
+        Self {
+            entries: OnceLock::new(),
+        }
+    }
+
+    /// The cached result for the program, probing it with `probe` if there is none.
+    pub(crate) fn get_or_probe(
+        &self,
+        program: &Path,
+        probe: impl FnOnce(&Path) -> crate::Result<T>,
+    ) -> crate::Result<T> {
+        if let Some(cached) = self.lock().get(program) {
+            return copy(cached);
+        }
+
+        // The lock isn't held while probing, so concurrent callers may probe the same program
+        // twice; both get the same answer.
+        let result = probe(program);
+        self.lock().insert(program.to_owned(), copy(&result));
+        result
+    }
+
+    /// The cached result for the program, without probing it if there is none.
+    pub(crate) fn cached(&self, program: &Path) -> Option<crate::Result<T>> {
+        self.lock().get(program).map(copy)
+    }
+
+    /// Drop the cached result for the program, so that it's probed again on next use.
+    pub(crate) fn forget(&self, program: &Path) {
+        self.lock().remove(program);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<PathBuf, crate::Result<T>>> {
+        self.entries
+            .get_or_init(Default::default)
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+fn copy<T: Clone>(result: &crate::Result<T>) -> crate::Result<T> {
+    match result {
+        Ok(value) => Ok(value.clone()),
+        Err(err) => Err(err.replicate()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ProbeCache;
+    use std::{cell::Cell, io, path::Path};
+
+    #[test]
+    fn probes_each_program_once() {
+        let cache = ProbeCache::new();
+        let probes = Cell::new(0);
+        let probe = |program: &Path| {
+            probes.set(probes.get() + 1);
+            Ok(program.display().to_string())
+        };
+
+        assert_eq!(cache.get_or_probe(Path::new("a"), probe).unwrap(), "a");
+        assert_eq!(cache.get_or_probe(Path::new("a"), probe).unwrap(), "a");
+        assert_eq!(cache.get_or_probe(Path::new("b"), probe).unwrap(), "b");
+        assert_eq!(probes.get(), 2);
+    }
+
+    #[test]
+    fn failures_are_cached_and_reported_again() {
+        let cache = ProbeCache::<String>::new();
+        let probes = Cell::new(0);
+        let probe = |program: &Path| {
+            probes.set(probes.get() + 1);
+            Err(crate::Error::spawn(
+                program,
+                vec!["--help-all".into()],
+                io::ErrorKind::NotFound.into(),
+            ))
+        };
+
+        let first = cache
+            .get_or_probe(Path::new("/no/zenity"), probe)
+            .unwrap_err();
+        let second = cache
+            .get_or_probe(Path::new("/no/zenity"), probe)
+            .unwrap_err();
+        assert_eq!(probes.get(), 1);
+        assert_eq!(first.to_string(), second.to_string());
+        assert!(
+            matches!(
+                second,
+                crate::Error::ZenityNotInstalled { ref program, ref source, .. }
+                    if program == Path::new("/no/zenity") && source.kind() == io::ErrorKind::NotFound
+            ),
+            "{:?}",
+            second
+        );
+    }
+
+    #[test]
+    fn cached_parse_failures_keep_their_text() {
+        let cache = ProbeCache::<u32>::new();
+        let probe = |_: &Path| {
+            Err(
+                crate::ParseError::new("version", "garbage", "not a version")
+                    .with_format("x.y.z")
+                    .into(),
+            )
+        };
+
+        cache.get_or_probe(Path::new("zenity"), probe).unwrap_err();
+        let err = cache.get_or_probe(Path::new("zenity"), probe).unwrap_err();
+        let crate::Error::ParseResultFailure(ref parse) = err else {
+            panic!("unexpected error: {:?}", err);
+        };
+        assert_eq!(parse.stdout(), "garbage");
+        assert_eq!(parse.format(), Some("x.y.z"));
+        assert!(err.to_string().contains("not a version"));
+    }
+}
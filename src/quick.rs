@@ -0,0 +1,718 @@
+//! One-line helpers for scripts, which show a dialog with sensible defaults and collapse its
+//! output into a simple value. Failures to show the dialog, such as
+//! [crate::Error::ZenityNotInstalled], are still returned as errors. For anything more, build
+//! a [crate::ZenityDialog].
+//!
+//! ```no_run
+//! # fn main() -> zenity_dialog::Result<()> {
+//! use zenity_dialog::quick;
+//!
+//! if let Some(name) = quick::prompt("What's your name?")? {
+//!     quick::alert(format!("Hello, {name}!"))?;
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+#[cfg(feature = "entry")]
+use crate::dialog::Entry;
+#[cfg(feature = "error")]
+use crate::dialog::Error;
+#[cfg(feature = "info")]
+use crate::dialog::Info;
+#[cfg(feature = "list")]
+use crate::dialog::List;
+#[cfg(feature = "warning")]
+use crate::dialog::Warning;
+#[cfg(feature = "file-selection")]
+use crate::dialog::{FileFilter, FileSelection};
+#[cfg(any(
+    feature = "entry",
+    feature = "error",
+    feature = "file-selection",
+    feature = "info",
+    feature = "list",
+    feature = "warning"
+))]
+use crate::{
+    dialog::{Icon, ZenityApplication},
+    ZenityDialog, ZenityOutput,
+};
+#[cfg(feature = "file-selection")]
+use std::path::{Path, PathBuf};
+
+/// Shows the dialogs the helpers build. The helpers show them with the crate-wide settings, and
+/// the tests through a runner or without a display.
+#[cfg(any(
+    feature = "entry",
+    feature = "error",
+    feature = "file-selection",
+    feature = "info",
+    feature = "list",
+    feature = "warning"
+))]
+trait Show {
+    fn show<T: ZenityApplication>(
+        &self,
+        dialog: ZenityDialog<T>,
+    ) -> crate::Result<ZenityOutput<T::Return>>;
+}
+
+/// Shows dialogs with [ZenityDialog::show].
+#[cfg(any(
+    feature = "entry",
+    feature = "error",
+    feature = "file-selection",
+    feature = "info",
+    feature = "list",
+    feature = "warning"
+))]
+struct Installed;
+
+#[cfg(any(
+    feature = "entry",
+    feature = "error",
+    feature = "file-selection",
+    feature = "info",
+    feature = "list",
+    feature = "warning"
+))]
+impl Show for Installed {
+    fn show<T: ZenityApplication>(
+        &self,
+        dialog: ZenityDialog<T>,
+    ) -> crate::Result<ZenityOutput<T::Return>> {
+        dialog.show()
+    }
+}
+
+/// Show an informational message and wait until it's closed.
+#[cfg(feature = "info")]
+pub fn alert(text: impl Into<String>) -> crate::Result<()> {
+    alert_with(&Installed, text)
+}
+
+#[cfg(feature = "info")]
+fn alert_with(shower: &impl Show, text: impl Into<String>) -> crate::Result<()> {
+    let dialog = ZenityDialog::new(Info::new().with_text(text)).with_icon(Icon::Info);
+    shower.show(dialog).map(drop)
+}
+
+/// Show an error message and wait until it's closed.
+#[cfg(feature = "error")]
+pub fn error(text: impl Into<String>) -> crate::Result<()> {
+    error_with(&Installed, text)
+}
+
+#[cfg(feature = "error")]
+fn error_with(shower: &impl Show, text: impl Into<String>) -> crate::Result<()> {
+    let dialog = ZenityDialog::new(Error::new().with_text(text)).with_icon(Icon::Error);
+    shower.show(dialog).map(drop)
+}
+
+/// Show a warning and wait until it's closed.
+#[cfg(feature = "warning")]
+pub fn warning(text: impl Into<String>) -> crate::Result<()> {
+    warning_with(&Installed, text)
+}
+
+#[cfg(feature = "warning")]
+fn warning_with(shower: &impl Show, text: impl Into<String>) -> crate::Result<()> {
+    let dialog = ZenityDialog::new(Warning::new().with_text(text)).with_icon(Icon::Warning);
+    shower.show(dialog).map(drop)
+}
+
+/// Ask the user for a line of text. Returns the submitted text, which may be empty, or [None]
+/// if the dialog closed without an answer, for example because the user cancelled it.
+#[cfg(feature = "entry")]
+pub fn prompt(text: impl Into<String>) -> crate::Result<Option<String>> {
+    prompt_with(&Installed, text)
+}
+
+#[cfg(feature = "entry")]
+fn prompt_with(shower: &impl Show, text: impl Into<String>) -> crate::Result<Option<String>> {
+    let dialog = ZenityDialog::new(Entry::new().with_text(text)).with_icon(Icon::Question);
+    Ok(shower.show(dialog)?.content().map(String::from))
+}
+
+/// Ask the user for a file to open, showing the title if given and offering the filters.
+/// Returns [None] if the dialog closed without a selection.
+#[cfg(feature = "file-selection")]
+pub fn pick_file(title: Option<&str>, filters: &[FileFilter]) -> crate::Result<Option<PathBuf>> {
+    pick_file_with(&Installed, title, filters)
+}
+
+#[cfg(feature = "file-selection")]
+fn pick_file_with(
+    shower: &impl Show,
+    title: Option<&str>,
+    filters: &[FileFilter],
+) -> crate::Result<Option<PathBuf>> {
+    let selection = show_file_selection(shower, FileSelection::new(), title, filters)?;
+    Ok(selection.and_then(|paths| paths.into_iter().next()))
+}
+
+/// Ask the user for any number of files to open. Returns [None] if the dialog closed without a
+/// selection.
+#[cfg(feature = "file-selection")]
+pub fn pick_files(
+    title: Option<&str>,
+    filters: &[FileFilter],
+) -> crate::Result<Option<Vec<PathBuf>>> {
+    pick_files_with(&Installed, title, filters)
+}
+
+#[cfg(feature = "file-selection")]
+fn pick_files_with(
+    shower: &impl Show,
+    title: Option<&str>,
+    filters: &[FileFilter],
+) -> crate::Result<Option<Vec<PathBuf>>> {
+    let application = FileSelection::new().with_multiple();
+    show_file_selection(shower, application, title, filters)
+}
+
+/// Ask the user for a directory. Returns [None] if the dialog closed without a selection.
+#[cfg(feature = "file-selection")]
+pub fn pick_folder(title: Option<&str>) -> crate::Result<Option<PathBuf>> {
+    pick_folder_with(&Installed, title)
+}
+
+#[cfg(feature = "file-selection")]
+fn pick_folder_with(shower: &impl Show, title: Option<&str>) -> crate::Result<Option<PathBuf>> {
+    let title = title.or(Some("Select a folder"));
+    let application = FileSelection::new().with_directory();
+    let selection = show_file_selection(shower, application, title, &[])?;
+    Ok(selection.and_then(|paths| paths.into_iter().next()))
+}
+
+/// Ask the user for a file to save to, suggesting the name, which may include a directory.
+/// Returns [None] if the dialog closed without a selection.
+///
+/// ```no_run
+/// # use zenity_dialog::{dialog::FileFilter, quick};
+/// # fn main() -> zenity_dialog::Result<()> {
+/// let filters = [FileFilter::new("Text", ["*.txt"])];
+/// if let Some(path) = quick::save_file("notes.txt", Some("Save the notes"), &filters)? {
+///     std::fs::write(path, "...").ok();
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "file-selection")]
+pub fn save_file(
+    suggested_name: impl AsRef<Path>,
+    title: Option<&str>,
+    filters: &[FileFilter],
+) -> crate::Result<Option<PathBuf>> {
+    save_file_with(&Installed, suggested_name, title, filters)
+}
+
+#[cfg(feature = "file-selection")]
+fn save_file_with(
+    shower: &impl Show,
+    suggested_name: impl AsRef<Path>,
+    title: Option<&str>,
+    filters: &[FileFilter],
+) -> crate::Result<Option<PathBuf>> {
+    let application = FileSelection::new()
+        .with_save()
+        .with_filename(suggested_name);
+    let title = title.or(Some("Save as"));
+    let selection = show_file_selection(shower, application, title, filters)?;
+    Ok(selection.and_then(|paths| paths.into_iter().next()))
+}
+
+#[cfg(feature = "file-selection")]
+fn show_file_selection(
+    shower: &impl Show,
+    mut application: FileSelection,
+    title: Option<&str>,
+    filters: &[FileFilter],
+) -> crate::Result<Option<Vec<PathBuf>>> {
+    application.file_filters.extend_from_slice(filters);
+    let mut dialog = ZenityDialog::new(application);
+    if let Some(title) = title {
+        dialog.set_title(title);
+    }
+
+    Ok(shower
+        .show(dialog)?
+        .content()
+        .filter(|paths| !paths.is_empty()))
+}
+
+/// Ask the user to choose one of the options. Returns [None] if the dialog closed without a
+/// choice, and fails with [crate::Error::InvalidDialog] if there are no options. See
+/// [choose_one_index] to tell apart options with the same text.
+///
+/// ```no_run
+/// # fn main() -> zenity_dialog::Result<()> {
+/// let color = zenity_dialog::quick::choose_one("Pick a color", &["Red", "Green", "Blue"])?;
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "list")]
+pub fn choose_one(prompt: &str, options: &[impl AsRef<str>]) -> crate::Result<Option<String>> {
+    choose_one_with(&Installed, prompt, options)
+}
+
+#[cfg(feature = "list")]
+fn choose_one_with(
+    shower: &impl Show,
+    prompt: &str,
+    options: &[impl AsRef<str>],
+) -> crate::Result<Option<String>> {
+    let index = choose_one_index_with(shower, prompt, options)?;
+    Ok(index.map(|index| options[index].as_ref().to_owned()))
+}
+
+/// Ask the user to choose one of the options, returning the index of the chosen option. See
+/// [choose_one].
+#[cfg(feature = "list")]
+pub fn choose_one_index(prompt: &str, options: &[impl AsRef<str>]) -> crate::Result<Option<usize>> {
+    choose_one_index_with(&Installed, prompt, options)
+}
+
+#[cfg(feature = "list")]
+fn choose_one_index_with(
+    shower: &impl Show,
+    prompt: &str,
+    options: &[impl AsRef<str>],
+) -> crate::Result<Option<usize>> {
+    let indices = show_chooser(shower, List::new(), prompt, options)?;
+    Ok(indices.first().copied())
+}
+
+/// Ask the user to check any of the options. Returns the checked options, which are empty if
+/// the dialog closed without a choice, and fails with [crate::Error::InvalidDialog] if there
+/// are no options. See [choose_many_indices] to tell apart options with the same text.
+#[cfg(feature = "list")]
+pub fn choose_many(prompt: &str, options: &[impl AsRef<str>]) -> crate::Result<Vec<String>> {
+    choose_many_with(&Installed, prompt, options)
+}
+
+#[cfg(feature = "list")]
+fn choose_many_with(
+    shower: &impl Show,
+    prompt: &str,
+    options: &[impl AsRef<str>],
+) -> crate::Result<Vec<String>> {
+    let indices = choose_many_indices_with(shower, prompt, options)?;
+    Ok(indices
+        .into_iter()
+        .map(|index| options[index].as_ref().to_owned())
+        .collect())
+}
+
+/// Ask the user to check any of the options, returning the indices of the checked options. See
+/// [choose_many].
+#[cfg(feature = "list")]
+pub fn choose_many_indices(prompt: &str, options: &[impl AsRef<str>]) -> crate::Result<Vec<usize>> {
+    choose_many_indices_with(&Installed, prompt, options)
+}
+
+#[cfg(feature = "list")]
+fn choose_many_indices_with(
+    shower: &impl Show,
+    prompt: &str,
+    options: &[impl AsRef<str>],
+) -> crate::Result<Vec<usize>> {
+    let list = List::new().with_checklist().with_column("");
+    show_chooser(shower, list, prompt, options)
+}
+
+/// Show the options in a list after the columns already in it, with each row identified by its
+/// index in a hidden column, so that options with the same text are told apart.
+#[cfg(feature = "list")]
+fn show_chooser(
+    shower: &impl Show,
+    list: List,
+    prompt: &str,
+    options: &[impl AsRef<str>],
+) -> crate::Result<Vec<usize>> {
+    if options.is_empty() {
+        return Err(crate::Error::InvalidDialog(vec![
+            crate::ValidationError::new("options", "must contain at least one option"),
+        ]));
+    }
+
+    let checklist = list.checklist;
+    let mut list = list.with_text(prompt).with_hide_header();
+    list.push_column("Index").push_column("Option");
+    let index_column = list.columns.len() - 1;
+    list.set_print_column(index_column)
+        .set_hide_column(index_column);
+    for (index, option) in options.iter().enumerate() {
+        let mut row = Vec::new();
+        if checklist {
+            row.push("FALSE".to_owned());
+        }
+        row.extend([index.to_string(), option.as_ref().to_owned()]);
+        list.push_row(row);
+    }
+
+    let selected = shower
+        .show(ZenityDialog::new(list))?
+        .content()
+        .unwrap_or_default();
+    selected
+        .iter()
+        .map(|index| {
+            index
+                .parse()
+                .ok()
+                .filter(|index| *index < options.len())
+                .ok_or_else(|| crate::ParseError::new("list", index, "not an option index").into())
+        })
+        .collect()
+}
+
+#[cfg(all(
+    test,
+    any(
+        feature = "entry",
+        feature = "error",
+        feature = "file-selection",
+        feature = "info",
+        feature = "list",
+        feature = "warning"
+    )
+))]
+mod tests {
+    use super::*;
+    use crate::{
+        runner::{MockResponse, MockRunner},
+        settings::lock_for_test,
+    };
+
+    /// Shows dialogs through the runner.
+    struct Runner<'a>(&'a MockRunner);
+
+    impl Show for Runner<'_> {
+        fn show<T: ZenityApplication>(
+            &self,
+            dialog: ZenityDialog<T>,
+        ) -> crate::Result<ZenityOutput<T::Return>> {
+            dialog.force_legacy_flags().show_with_runner(self.0)
+        }
+    }
+
+    /// Shows dialogs with the installed program, without a display.
+    #[cfg(all(unix, feature = "file-selection"))]
+    struct Headless;
+
+    #[cfg(all(unix, feature = "file-selection"))]
+    impl Show for Headless {
+        fn show<T: ZenityApplication>(
+            &self,
+            dialog: ZenityDialog<T>,
+        ) -> crate::Result<ZenityOutput<T::Return>> {
+            dialog.allow_headless().show()
+        }
+    }
+
+    #[cfg(feature = "info")]
+    #[test]
+    fn an_alert_is_done_however_its_closed() {
+        let _lock = lock_for_test();
+        for response in [MockResponse::affirmed(""), MockResponse::rejected()] {
+            let runner = MockRunner::new().respond_to_arg("--info", response);
+            alert_with(&Runner(&runner), "Saved").unwrap();
+
+            let invocations = runner.invocations();
+            assert_eq!(invocations.len(), 1);
+            assert!(invocations[0].has_arg("--text=Saved"));
+        }
+    }
+
+    #[cfg(feature = "error")]
+    #[test]
+    fn an_error_is_done_however_its_closed() {
+        let _lock = lock_for_test();
+        for response in [MockResponse::affirmed(""), MockResponse::rejected()] {
+            let runner = MockRunner::new().respond_to_arg("--error", response);
+            error_with(&Runner(&runner), "Disk full").unwrap();
+            assert!(runner.invocations()[0].has_arg("--text=Disk full"));
+        }
+    }
+
+    #[cfg(feature = "warning")]
+    #[test]
+    fn a_warning_is_done_however_its_closed() {
+        let _lock = lock_for_test();
+        for response in [MockResponse::affirmed(""), MockResponse::rejected()] {
+            let runner = MockRunner::new().respond_to_arg("--warning", response);
+            warning_with(&Runner(&runner), "Low battery").unwrap();
+            assert!(runner.invocations()[0].has_arg("--text=Low battery"));
+        }
+    }
+
+    #[cfg(feature = "entry")]
+    #[test]
+    fn a_prompt_returns_the_submitted_text() {
+        let _lock = lock_for_test();
+        let runner = MockRunner::new().respond_to_arg("--entry", MockResponse::affirmed("Ada\n"));
+        assert_eq!(
+            prompt_with(&Runner(&runner), "Name?").unwrap().as_deref(),
+            Some("Ada")
+        );
+        assert!(runner.invocations()[0].has_arg("--text=Name?"));
+
+        let runner = MockRunner::new().respond_to_arg("--entry", MockResponse::affirmed("\n"));
+        assert_eq!(
+            prompt_with(&Runner(&runner), "Name?").unwrap().as_deref(),
+            Some("")
+        );
+    }
+
+    #[cfg(feature = "entry")]
+    #[test]
+    fn a_cancelled_prompt_has_no_answer() {
+        let _lock = lock_for_test();
+        let runner = MockRunner::new().respond_to_arg("--entry", MockResponse::rejected());
+        assert_eq!(prompt_with(&Runner(&runner), "Name?").unwrap(), None);
+    }
+
+    #[cfg(any(
+        feature = "entry",
+        feature = "error",
+        feature = "info",
+        feature = "warning"
+    ))]
+    #[test]
+    fn a_missing_zenity_is_reported() {
+        let _lock = lock_for_test();
+        // A runner that responds to nothing finds no program.
+        let runner = MockRunner::new();
+        let shower = Runner(&runner);
+        let results = [
+            #[cfg(feature = "info")]
+            alert_with(&shower, "Saved"),
+            #[cfg(feature = "error")]
+            error_with(&shower, "Disk full"),
+            #[cfg(feature = "warning")]
+            warning_with(&shower, "Low battery"),
+            #[cfg(feature = "entry")]
+            prompt_with(&shower, "Name?").map(drop),
+        ];
+
+        for result in results {
+            assert!(
+                matches!(result, Err(crate::Error::ZenityNotInstalled { .. })),
+                "{:?}",
+                result
+            );
+        }
+    }
+
+    #[cfg(all(unix, feature = "file-selection"))]
+    #[test]
+    fn a_file_is_picked_with_the_filters_offered() {
+        let _lock = lock_for_test();
+        let zenity = crate::harness::TestZenity::affirms_with("/home/ada/notes.txt\n").unwrap();
+        let filters = [
+            FileFilter::new("Text", ["*.txt", "*.md"]),
+            FileFilter::new("Images", ["*.png"]),
+        ];
+
+        assert_eq!(
+            pick_file_with(&Headless, Some("Open notes"), &filters).unwrap(),
+            Some(PathBuf::from("/home/ada/notes.txt"))
+        );
+
+        let args = zenity.last_args().unwrap();
+        assert!(args.contains(&"--file-selection".to_owned()), "{:?}", args);
+        assert!(
+            args.contains(&"--title=Open notes".to_owned()),
+            "{:?}",
+            args
+        );
+        assert!(args.contains(&"--file-filter=Text | *.txt *.md".to_owned()));
+        assert!(args.contains(&"--file-filter=Images | *.png".to_owned()));
+        assert!(!args.contains(&"--multiple".to_owned()));
+    }
+
+    #[cfg(all(unix, feature = "file-selection"))]
+    #[test]
+    fn several_files_are_picked() {
+        let _lock = lock_for_test();
+        let zenity =
+            crate::harness::TestZenity::affirms_with("/tmp/a b.png\n/tmp/c|d.png\n").unwrap();
+
+        assert_eq!(
+            pick_files_with(&Headless, None, &[]).unwrap(),
+            Some(vec![
+                PathBuf::from("/tmp/a b.png"),
+                PathBuf::from("/tmp/c|d.png")
+            ])
+        );
+        assert!(zenity
+            .last_args()
+            .unwrap()
+            .contains(&"--multiple".to_owned()));
+    }
+
+    #[cfg(all(unix, feature = "file-selection"))]
+    #[test]
+    fn a_folder_is_picked_with_a_default_title() {
+        let _lock = lock_for_test();
+        let zenity = crate::harness::TestZenity::affirms_with("/home/ada/Music\n").unwrap();
+
+        assert_eq!(
+            pick_folder_with(&Headless, None).unwrap(),
+            Some(PathBuf::from("/home/ada/Music"))
+        );
+
+        let args = zenity.last_args().unwrap();
+        assert!(args.contains(&"--directory".to_owned()), "{:?}", args);
+        assert!(args.contains(&"--title=Select a folder".to_owned()));
+    }
+
+    #[cfg(all(unix, feature = "file-selection"))]
+    #[test]
+    fn a_file_to_save_to_is_picked_from_the_suggested_name() {
+        let _lock = lock_for_test();
+        let zenity = crate::harness::TestZenity::affirms_with("/home/ada/report.csv\n").unwrap();
+        let filters = [FileFilter::new("CSV", ["*.csv"])];
+
+        assert_eq!(
+            save_file_with(&Headless, "report.csv", None, &filters).unwrap(),
+            Some(PathBuf::from("/home/ada/report.csv"))
+        );
+
+        let args = zenity.last_args().unwrap();
+        assert!(args.contains(&"--save".to_owned()), "{:?}", args);
+        assert!(args.contains(&"--filename=report.csv".to_owned()));
+        assert!(args.contains(&"--title=Save as".to_owned()));
+        assert!(args.contains(&"--file-filter=CSV | *.csv".to_owned()));
+    }
+
+    #[cfg(all(unix, feature = "file-selection"))]
+    #[test]
+    fn a_cancelled_file_selection_picks_nothing() {
+        let _lock = lock_for_test();
+        let _zenity = crate::harness::TestZenity::rejects().unwrap();
+
+        assert_eq!(pick_file_with(&Headless, None, &[]).unwrap(), None);
+        assert_eq!(pick_files_with(&Headless, None, &[]).unwrap(), None);
+        assert_eq!(pick_folder_with(&Headless, None).unwrap(), None);
+        assert_eq!(
+            save_file_with(&Headless, "report.csv", None, &[]).unwrap(),
+            None
+        );
+    }
+
+    #[cfg(feature = "list")]
+    #[test]
+    fn options_with_the_same_text_are_told_apart_by_their_index() {
+        let _lock = lock_for_test();
+        let options = ["Red", "Green", "Red"];
+        let runner = MockRunner::new().respond_to_arg("--list", MockResponse::affirmed("2\n"));
+
+        assert_eq!(
+            choose_one_index_with(&Runner(&runner), "Pick a color", &options).unwrap(),
+            Some(2)
+        );
+        assert_eq!(
+            choose_one_with(&Runner(&runner), "Pick a color", &options)
+                .unwrap()
+                .as_deref(),
+            Some("Red")
+        );
+
+        // Each row is its index in the hidden column, followed by the option.
+        let invocation = &runner.invocations()[0];
+        assert!(invocation.has_arg("--text=Pick a color"));
+        assert!(invocation.has_arg("--print-column=1"));
+        assert!(invocation.has_arg("--hide-column=1"));
+        let rows: Vec<_> = invocation
+            .args
+            .iter()
+            .skip_while(|arg| !arg.to_string_lossy().starts_with("--separator="))
+            .skip(1)
+            .take(6)
+            .collect();
+        assert_eq!(rows, ["0", "Red", "1", "Green", "2", "Red"]);
+    }
+
+    #[cfg(feature = "list")]
+    #[test]
+    fn many_options_are_chosen_by_their_index() {
+        let _lock = lock_for_test();
+        let options = ["Red", "Green", "Red"];
+        let runner = MockRunner::new().respond_to_arg("--list", MockResponse::affirmed("0\n2\n"));
+
+        assert_eq!(
+            choose_many_indices_with(&Runner(&runner), "Pick colors", &options).unwrap(),
+            [0, 2]
+        );
+        assert_eq!(
+            choose_many_with(&Runner(&runner), "Pick colors", &options).unwrap(),
+            ["Red", "Red"]
+        );
+
+        // The checkbox column comes first, so the index is in the second.
+        let invocation = &runner.invocations()[0];
+        assert!(invocation.has_arg("--checklist"));
+        assert!(invocation.has_arg("--print-column=2"));
+        assert!(invocation.has_arg("--hide-column=2"));
+    }
+
+    #[cfg(feature = "list")]
+    #[test]
+    fn a_cancelled_chooser_chooses_nothing() {
+        let _lock = lock_for_test();
+        let runner = MockRunner::new().respond_to_arg("--list", MockResponse::rejected());
+        let shower = Runner(&runner);
+
+        assert_eq!(choose_one_with(&shower, "Pick", &["a", "b"]).unwrap(), None);
+        assert_eq!(
+            choose_one_index_with(&shower, "Pick", &["a", "b"]).unwrap(),
+            None
+        );
+        assert!(choose_many_with(&shower, "Pick", &["a", "b"])
+            .unwrap()
+            .is_empty());
+        assert!(choose_many_indices_with(&shower, "Pick", &["a", "b"])
+            .unwrap()
+            .is_empty());
+    }
+
+    #[cfg(feature = "list")]
+    #[test]
+    fn choosing_from_no_options_is_invalid() {
+        let runner = MockRunner::new().respond_otherwise(MockResponse::affirmed("0\n"));
+        let shower = Runner(&runner);
+        let options: [&str; 0] = [];
+
+        let results = [
+            choose_one_with(&shower, "Pick", &options).map(drop),
+            choose_one_index_with(&shower, "Pick", &options).map(drop),
+            choose_many_with(&shower, "Pick", &options).map(drop),
+            choose_many_indices_with(&shower, "Pick", &options).map(drop),
+        ];
+        for result in results {
+            assert!(
+                matches!(result, Err(crate::Error::InvalidDialog(_))),
+                "{:?}",
+                result
+            );
+        }
+        // No dialog is shown.
+        assert!(runner.invocations().is_empty());
+    }
+
+    #[cfg(feature = "list")]
+    #[test]
+    fn an_index_that_isnt_an_option_fails_to_parse() {
+        let _lock = lock_for_test();
+        let runner = MockRunner::new().respond_to_arg("--list", MockResponse::affirmed("3\n"));
+        let err = choose_one_with(&Runner(&runner), "Pick", &["a", "b"]).unwrap_err();
+        assert!(
+            matches!(err, crate::Error::ParseResultFailure(_)),
+            "{:?}",
+            err
+        );
+    }
+}
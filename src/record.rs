@@ -0,0 +1,151 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::VecDeque,
+    ffi::OsString,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    os::unix::process::ExitStatusExt,
+    path::{Path, PathBuf},
+    process::{ExitStatus, Output},
+    sync::Mutex,
+};
+
+/// The recordings left to replay, loaded on first use. Reset whenever settings are installed.
+static REPLAY: Mutex<Option<Replay>> = Mutex::new(None);
+
+#[derive(Debug)]
+struct Replay {
+    path: PathBuf,
+    position: usize,
+    recordings: VecDeque<Recording>,
+}
+
+/// One dialog as stored in a recording file, one JSON object per line. The arguments are those
+/// the program was run with, after the option names were resolved for its version.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct Recording {
+    args: Vec<String>,
+    /// Whether the program was killed by the hard timeout, in which case it printed nothing.
+    #[serde(default, skip_serializing_if = "is_false")]
+    hard_timeout: bool,
+    exit_code: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    signal: Option<i32>,
+    stdout: String,
+    stderr: String,
+}
+
+fn is_false(value: &bool) -> bool {
+    !value
+}
+
+impl Recording {
+    /// The output of the dialog, or [None] if it was killed by the hard timeout.
+    fn to_output(&self) -> Option<Output> {
+        if self.hard_timeout {
+            return None;
+        }
+
+        let status = match (self.exit_code, self.signal) {
+            // Wait statuses store the exit code in the second byte.
+            (Some(code), _) => ExitStatus::from_raw((code & 0xff) << 8),
+            (None, Some(signal)) => ExitStatus::from_raw(signal & 0x7f),
+            (None, None) => ExitStatus::from_raw(0x7f),
+        };
+
+        Some(Output {
+            status,
+            stdout: self.stdout.clone().into_bytes(),
+            stderr: self.stderr.clone().into_bytes(),
+        })
+    }
+}
+
+/// The arguments as stored in recordings. Arguments that aren't valid UTF-8 are stored lossily.
+fn to_strings(args: &[OsString]) -> Vec<String> {
+    args.iter()
+        .map(|arg| arg.to_string_lossy().into_owned())
+        .collect()
+}
+
+/// Append the dialog to the recording file, creating it if needed. An output of [None] records
+/// a dialog killed by the hard timeout.
+pub(crate) fn record(path: &Path, args: &[OsString], output: Option<&Output>) -> io::Result<()> {
+    let recording = match output {
+        Some(output) => Recording {
+            args: to_strings(args),
+            hard_timeout: false,
+            exit_code: output.status.code(),
+            signal: output.status.signal(),
+            stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
+            stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+        },
+        None => Recording {
+            args: to_strings(args),
+            hard_timeout: true,
+            exit_code: None,
+            signal: None,
+            stdout: String::new(),
+            stderr: String::new(),
+        },
+    };
+
+    let mut line = serde_json::to_string(&recording)?;
+    line.push('\n');
+
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?
+        .write_all(line.as_bytes())
+}
+
+/// The output of the next recorded dialog, which must have been shown with the same arguments,
+/// or [None] if it was killed by the hard timeout.
+pub(crate) fn replay(path: &Path, args: &[OsString]) -> crate::Result<Option<Output>> {
+    let mut replay = REPLAY
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+    let replay = match *replay {
+        Some(ref mut replay) if replay.path == path => replay,
+        _ => replay.insert(Replay {
+            path: path.to_owned(),
+            position: 0,
+            recordings: load(path).map_err(crate::Error::UnexpectedIoError)?,
+        }),
+    };
+
+    let args = to_strings(args);
+    let position = replay.position;
+    match replay.recordings.front() {
+        Some(recording) if recording.args == args => {
+            replay.position += 1;
+            Ok(replay
+                .recordings
+                .pop_front()
+                .expect("the recording was just matched")
+                .to_output())
+        }
+        recording => Err(crate::Error::ReplayMismatch {
+            position,
+            expected: recording.map(|recording| recording.args.clone()),
+            actual: args,
+        }),
+    }
+}
+
+/// Forget the replay progress, so that the next replay starts from the beginning of its file.
+pub(crate) fn reset() {
+    *REPLAY
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = None;
+}
+
+fn load(path: &Path) -> io::Result<VecDeque<Recording>> {
+    fs::read_to_string(path)?
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| serde_json::from_str(line).map_err(io::Error::from))
+        .collect()
+}
@@ -0,0 +1,209 @@
+use std::{
+    ffi::{OsStr, OsString},
+    io,
+    process::{Command, Output, Stdio},
+};
+
+/// Runs the dialog program. [SystemRunner] spawns it for real; substitute another runner, such
+/// as the `MockRunner` of the `test-util` feature, to test dialog flows without Zenity. See
+/// [crate::ZenityDialog::show_with_runner].
+pub trait CommandRunner {
+    /// Run the program with the arguments to completion and return its output.
+    fn run(&self, program: &OsStr, args: &[OsString]) -> io::Result<Output>;
+}
+
+impl<R: CommandRunner + ?Sized> CommandRunner for &R {
+    fn run(&self, program: &OsStr, args: &[OsString]) -> io::Result<Output> {
+        (**self).run(program, args)
+    }
+}
+
+/// Spawns the program as a child process and waits for it.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemRunner;
+
+impl CommandRunner for SystemRunner {
+    fn run(&self, program: &OsStr, args: &[OsString]) -> io::Result<Output> {
+        Command::new(program)
+            .args(args)
+            .stdin(Stdio::null())
+            .output()
+    }
+}
+
+// Also compiled for the crate's own tests, which run dialogs through the mock.
+#[cfg(any(test, feature = "test-util"))]
+pub use self::mock::{Invocation, MockResponse, MockRunner};
+
+#[cfg(any(test, feature = "test-util"))]
+mod mock {
+    use super::CommandRunner;
+    use std::{
+        ffi::{OsStr, OsString},
+        io,
+        process::Output,
+        sync::Mutex,
+    };
+
+    type Matcher = Box<dyn Fn(&[OsString]) -> bool + Send + Sync>;
+
+    /// A [CommandRunner] that records every invocation and answers with scripted responses
+    /// instead of running anything.
+    #[derive(Default)]
+    pub struct MockRunner {
+        responses: Vec<(Matcher, MockResponse)>,
+        fallback: Option<MockResponse>,
+        invocations: Mutex<Vec<Invocation>>,
+    }
+
+    /// A program invocation recorded by a [MockRunner].
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct Invocation {
+        /// The program that would have run
+        pub program: OsString,
+        /// The arguments it would have received
+        pub args: Vec<OsString>,
+    }
+
+    impl Invocation {
+        /// Whether the invocation has the exact argument, as in `--entry` or `--title=Hello`.
+        pub fn has_arg(&self, arg: impl AsRef<OsStr>) -> bool {
+            self.args.iter().any(|candidate| candidate == arg.as_ref())
+        }
+    }
+
+    /// The output a [MockRunner] pretends the program produced.
+    #[derive(Debug, Clone, Default, PartialEq, Eq)]
+    pub struct MockResponse {
+        /// The exit code
+        pub exit_code: i32,
+        /// What the program printed to stdout
+        pub stdout: String,
+        /// What the program printed to stderr
+        pub stderr: String,
+    }
+
+    impl MockResponse {
+        /// A response with the exit code and no output.
+        pub fn new(exit_code: i32) -> Self {
+            Self {
+                exit_code,
+                ..Default::default()
+            }
+        }
+
+        /// The user confirmed the dialog, which printed `stdout`.
+        pub fn affirmed(stdout: impl Into<String>) -> Self {
+            Self::new(0).with_stdout(stdout)
+        }
+
+        /// The user cancelled the dialog.
+        pub fn rejected() -> Self {
+            Self::new(1)
+        }
+
+        /// The dialog timed out.
+        pub fn timed_out() -> Self {
+            Self::new(5)
+        }
+
+        /// Set the output printed to stdout.
+        pub fn with_stdout(mut self, stdout: impl Into<String>) -> Self {
+            self.stdout = stdout.into();
+            self
+        }
+
+        /// Set the output printed to stderr.
+        pub fn with_stderr(mut self, stderr: impl Into<String>) -> Self {
+            self.stderr = stderr.into();
+            self
+        }
+
+        #[cfg(unix)]
+        fn to_output(&self) -> Output {
+            use std::os::unix::process::ExitStatusExt;
+
+            Output {
+                // Wait statuses store the exit code in the second byte.
+                status: std::process::ExitStatus::from_raw((self.exit_code & 0xff) << 8),
+                stdout: self.stdout.clone().into_bytes(),
+                stderr: self.stderr.clone().into_bytes(),
+            }
+        }
+    }
+
+    impl MockRunner {
+        /// A runner without any scripted responses.
+        pub fn new() -> Self {
+            Default::default()
+        }
+
+        /// Answer invocations whose arguments satisfy `matcher` with the response. Matchers are
+        /// tried in the order they were added.
+        pub fn respond_when(
+            mut self,
+            matcher: impl Fn(&[OsString]) -> bool + Send + Sync + 'static,
+            response: MockResponse,
+        ) -> Self {
+            self.responses.push((Box::new(matcher), response));
+            self
+        }
+
+        /// Answer invocations that have the exact argument, as in `--entry`, with the response.
+        pub fn respond_to_arg(self, arg: impl Into<OsString>, response: MockResponse) -> Self {
+            let arg = arg.into();
+            self.respond_when(move |args| args.contains(&arg), response)
+        }
+
+        /// Answer invocations that no matcher accepts with the response. Without a fallback,
+        /// such invocations fail as if the program were missing.
+        pub fn respond_otherwise(mut self, response: MockResponse) -> Self {
+            self.fallback = Some(response);
+            self
+        }
+
+        /// Every invocation so far, in order.
+        pub fn invocations(&self) -> Vec<Invocation> {
+            self.lock().clone()
+        }
+
+        fn lock(&self) -> std::sync::MutexGuard<'_, Vec<Invocation>> {
+            self.invocations
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+        }
+    }
+
+    #[cfg(unix)]
+    impl CommandRunner for MockRunner {
+        fn run(&self, program: &OsStr, args: &[OsString]) -> io::Result<Output> {
+            self.lock().push(Invocation {
+                program: program.to_owned(),
+                args: args.to_vec(),
+            });
+
+            self.responses
+                .iter()
+                .find(|(matcher, _)| matcher(args))
+                .map(|(_, response)| response)
+                .or(self.fallback.as_ref())
+                .map(MockResponse::to_output)
+                .ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::NotFound,
+                        "the MockRunner has no response for the invocation",
+                    )
+                })
+        }
+    }
+
+    impl std::fmt::Debug for MockRunner {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("MockRunner")
+                .field("responses", &self.responses.len())
+                .field("fallback", &self.fallback)
+                .field("invocations", &*self.lock())
+                .finish()
+        }
+    }
+}
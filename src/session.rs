@@ -0,0 +1,377 @@
+use crate::{backend::ZenityBackend, Capabilities, Version, ZenitySettings};
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, RwLock,
+    },
+    time::{Duration, Instant, SystemTime},
+};
+
+/// A Zenity executable shared by many dialogs, possibly across threads, with its path, version
+/// and supported options resolved once. Show dialogs with it through
+/// [crate::ZenityDialog::show_with].
+///
+/// A system update may replace Zenity while the process runs. The session notices: it checks
+/// the executable's modification time, inode and size every [ZenitySession::with_revalidate_every]
+/// interval, and right away after a dialog fails in a way a replaced executable would explain,
+/// such as the executable going missing or rejecting its arguments. When the executable has
+/// changed, it's probed again and the new profile replaces the old one in a single swap.
+/// Dialogs shown meanwhile keep using the old profile instead of waiting for the probes.
+#[derive(Debug)]
+pub struct ZenitySession {
+    program: PathBuf,
+    revalidate_every: Duration,
+    profile: RwLock<Arc<Profile>>,
+    last_checked: Mutex<Instant>,
+    stale: AtomicBool,
+    refreshing: AtomicBool,
+}
+
+/// What was known about the executable when it was last probed.
+#[derive(Debug)]
+struct Profile {
+    path: PathBuf,
+    stamp: Option<Stamp>,
+    version: Option<Version>,
+    capabilities: Option<Capabilities>,
+}
+
+/// Identifies one version of a file on disk, so that replacing it can be detected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Stamp {
+    modified: Option<SystemTime>,
+    len: u64,
+    #[cfg(unix)]
+    inode: (u64, u64),
+}
+
+impl ZenitySession {
+    /// How often the executable is checked for changes unless configured otherwise.
+    pub const DEFAULT_REVALIDATE_EVERY: Duration = Duration::from_secs(60);
+
+    /// A session for the Zenity executable configured in the installed [ZenitySettings]. The
+    /// executable is probed right away.
+    pub fn new() -> Self {
+        Self::with_program(ZenitySettings::current().resolved_program())
+    }
+
+    /// A session for the provided Zenity executable. A bare program name is looked up on
+    /// `PATH`. The executable is probed right away.
+    pub fn with_program(program: impl Into<PathBuf>) -> Self {
+        let program = program.into();
+        let profile = Profile::probe(resolve(&program));
+
+        Self {
+            program,
+            revalidate_every: Self::DEFAULT_REVALIDATE_EVERY,
+            profile: RwLock::new(Arc::new(profile)),
+            last_checked: Mutex::new(Instant::now()),
+            stale: AtomicBool::new(false),
+            refreshing: AtomicBool::new(false),
+        }
+    }
+
+    /// Check the executable for changes at most this often. A zero interval checks it before
+    /// every dialog, which costs a `stat` call each time.
+    pub fn with_revalidate_every(mut self, interval: Duration) -> Self {
+        self.revalidate_every = interval;
+        self
+    }
+
+    /// The resolved path of the executable.
+    pub fn program(&self) -> PathBuf {
+        self.current().path.clone()
+    }
+
+    /// The version of the executable, if it could be determined.
+    pub fn version(&self) -> Option<Version> {
+        self.current().version
+    }
+
+    /// The options the executable supports, if it lists them.
+    pub fn capabilities(&self) -> Option<Capabilities> {
+        self.current().capabilities.clone()
+    }
+
+    /// Forget everything known about the executable, including the crate-wide version and
+    /// capability caches for it, so that it's resolved and probed again on next use.
+    pub fn invalidate(&self) {
+        forget(&self.read().path);
+        self.stale.store(true, Ordering::Release);
+    }
+
+    /// The backend running the session's executable, revalidating it first if it's due.
+    pub(crate) fn backend(&self) -> ZenityBackend {
+        ZenityBackend::with_program(self.program())
+    }
+
+    /// Note the result of a dialog shown with the session. Failures that a replaced or removed
+    /// executable would explain make the next dialog revalidate it.
+    pub(crate) fn observe<T>(&self, result: &crate::Result<T>) {
+        let suspicious = matches!(
+            result,
+            Err(crate::Error::ZenityNotInstalled { .. }
+                | crate::Error::ZenityNotExecutable { .. }
+                | crate::Error::InvalidExecutable { .. }
+                | crate::Error::UnsupportedOption { .. }
+                | crate::Error::ZenityFailed { .. })
+        );
+        if suspicious {
+            self.stale.store(true, Ordering::Release);
+        }
+    }
+
+    /// The current profile, refreshed first if it's due for revalidation. If another thread is
+    /// already refreshing it, the profile it's replacing is returned instead of waiting.
+    fn current(&self) -> Arc<Profile> {
+        let forced = self.stale.load(Ordering::Acquire);
+        if forced || self.revalidation_due() {
+            self.revalidate(forced);
+        }
+        self.read()
+    }
+
+    fn revalidation_due(&self) -> bool {
+        self.last_checked
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .elapsed()
+            >= self.revalidate_every
+    }
+
+    /// Probe the executable again if it changed, or unconditionally if `forced`. Only one thread
+    /// revalidates at a time; the others return right away.
+    fn revalidate(&self, forced: bool) {
+        if self
+            .refreshing
+            .compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
+            .is_err()
+        {
+            return;
+        }
+        let _refreshing = ClearOnDrop(&self.refreshing);
+
+        *self
+            .last_checked
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = Instant::now();
+        let forced = self.stale.swap(false, Ordering::AcqRel) || forced;
+
+        let current = self.read();
+        let path = resolve(&self.program);
+        if !forced && path == current.path && Stamp::of(&path) == current.stamp {
+            return;
+        }
+
+        forget(&current.path);
+        forget(&path);
+        let profile = Arc::new(Profile::probe(path));
+        *self
+            .profile
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = profile;
+    }
+
+    fn read(&self) -> Arc<Profile> {
+        Arc::clone(
+            &self
+                .profile
+                .read()
+                .unwrap_or_else(|poisoned| poisoned.into_inner()),
+        )
+    }
+}
+
+impl Default for ZenitySession {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Profile {
+    /// Stamp and probe the executable. The stamp is taken first, so that a change during the
+    /// probes is caught by the next revalidation rather than missed.
+    fn probe(path: PathBuf) -> Self {
+        let stamp = Stamp::of(&path);
+        Self {
+            version: crate::program_version(&path).ok(),
+            capabilities: crate::program_capabilities(&path).ok(),
+            path,
+            stamp,
+        }
+    }
+}
+
+impl Stamp {
+    /// The stamp of the file, or `None` if it doesn't exist.
+    fn of(path: &Path) -> Option<Self> {
+        let metadata = fs::metadata(path).ok()?;
+        Some(Self {
+            modified: metadata.modified().ok(),
+            len: metadata.len(),
+            #[cfg(unix)]
+            inode: {
+                use std::os::unix::fs::MetadataExt;
+                (metadata.dev(), metadata.ino())
+            },
+        })
+    }
+}
+
+/// Resets the flag when dropped, so that a panicking probe doesn't stop future refreshes.
+struct ClearOnDrop<'a>(&'a AtomicBool);
+
+impl Drop for ClearOnDrop<'_> {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::Release);
+    }
+}
+
+/// The path of the program: a bare name is looked up on `PATH`, anything else is used as is.
+fn resolve(program: &Path) -> PathBuf {
+    let bare = program.components().count() == 1 && !program.is_absolute();
+    bare.then(|| env::var_os("PATH"))
+        .flatten()
+        .and_then(|path| crate::backend::find_program_in(&program.to_string_lossy(), &path))
+        .unwrap_or_else(|| program.to_owned())
+}
+
+/// Drop the crate-wide cached probes of the program.
+fn forget(program: &Path) {
+    crate::version::forget_version(program);
+    crate::capabilities::forget_capabilities(program);
+}
+
+#[cfg(all(test, unix, feature = "entry"))]
+mod tests {
+    use super::ZenitySession;
+    use crate::{
+        dialog::{Entry, ZenityDialog},
+        harness::TestScripts,
+        Version,
+    };
+    use std::{fs, path::PathBuf, sync::Arc, thread, time::Duration};
+
+    /// A stub reporting the version and answering every dialog with it.
+    fn stub(version: &str) -> String {
+        format!(
+            "#!/bin/sh\ncase \"$1\" in --help-all) exit 0 ;; esac\necho {}\n",
+            version
+        )
+    }
+
+    /// Replace the stub the way a package manager would: write a new file and rename it over
+    /// the old one, so that the path gets a new inode.
+    fn swap(scripts: &TestScripts, program: &PathBuf, version: &str) {
+        let replacement = scripts.add("zenity.new", &stub(version)).unwrap();
+        fs::rename(replacement, program).unwrap();
+    }
+
+    fn dialog() -> ZenityDialog<Entry> {
+        ZenityDialog::new(Entry::new()).allow_headless()
+    }
+
+    fn answer(session: &ZenitySession) -> String {
+        dialog().show_with(session).unwrap().content().unwrap().text
+    }
+
+    #[test]
+    fn probes_the_program_when_created() {
+        let scripts = TestScripts::new().unwrap();
+        let program = scripts.add("zenity", &stub("3.44.0")).unwrap();
+
+        let session = ZenitySession::with_program(&program);
+        assert_eq!(session.program(), program);
+        assert_eq!(session.version(), Some(Version::new(3, 44, 0)));
+        assert_eq!(answer(&session), "3.44.0");
+    }
+
+    #[test]
+    fn picks_up_a_replaced_program_when_revalidation_is_due() {
+        let scripts = TestScripts::new().unwrap();
+        let program = scripts.add("zenity", &stub("3.44.0")).unwrap();
+        let session = ZenitySession::with_program(&program).with_revalidate_every(Duration::ZERO);
+
+        swap(&scripts, &program, "4.0.1");
+        assert_eq!(session.version(), Some(Version::new(4, 0, 1)));
+        assert_eq!(
+            crate::program_version(&program).unwrap(),
+            Version::new(4, 0, 1)
+        );
+        assert_eq!(answer(&session), "4.0.1");
+    }
+
+    #[test]
+    fn keeps_the_profile_until_revalidation_is_due() {
+        let scripts = TestScripts::new().unwrap();
+        let program = scripts.add("zenity", &stub("3.44.0")).unwrap();
+        let session = ZenitySession::with_program(&program);
+
+        swap(&scripts, &program, "4.0.1");
+        assert_eq!(session.version(), Some(Version::new(3, 44, 0)));
+    }
+
+    #[test]
+    fn invalidate_forces_a_new_probe() {
+        let scripts = TestScripts::new().unwrap();
+        let program = scripts.add("zenity", &stub("3.44.0")).unwrap();
+        let session = ZenitySession::with_program(&program);
+
+        swap(&scripts, &program, "4.0.1");
+        session.invalidate();
+        assert_eq!(session.version(), Some(Version::new(4, 0, 1)));
+    }
+
+    #[test]
+    fn a_missing_program_triggers_revalidation() {
+        let scripts = TestScripts::new().unwrap();
+        let program = scripts.add("zenity", &stub("3.44.0")).unwrap();
+        let session = ZenitySession::with_program(&program);
+
+        fs::remove_file(&program).unwrap();
+        let err = dialog().show_with(&session).unwrap_err();
+        assert!(
+            matches!(err, crate::Error::ZenityNotInstalled { .. }),
+            "{:?}",
+            err
+        );
+
+        swap(&scripts, &program, "4.0.1");
+        assert_eq!(session.version(), Some(Version::new(4, 0, 1)));
+    }
+
+    #[test]
+    fn concurrent_dialogs_survive_a_program_swap() {
+        let scripts = TestScripts::new().unwrap();
+        let program = scripts.add("zenity", &stub("3.44.0")).unwrap();
+        let session =
+            Arc::new(ZenitySession::with_program(&program).with_revalidate_every(Duration::ZERO));
+
+        let workers: Vec<_> = (0..4)
+            .map(|_| {
+                let session = Arc::clone(&session);
+                thread::spawn(move || (0..10).map(|_| answer(&session)).collect::<Vec<_>>())
+            })
+            .collect();
+        swap(&scripts, &program, "4.0.1");
+
+        for worker in workers {
+            let answers = worker.join().unwrap();
+            assert!(
+                answers
+                    .iter()
+                    .all(|answer| answer == "3.44.0" || answer == "4.0.1"),
+                "{:?}",
+                answers
+            );
+            // Once a thread has seen the new program, it never sees the old one again.
+            let first_new = answers.iter().position(|answer| answer == "4.0.1");
+            assert!(first_new.map_or(true, |first| answers[first..]
+                .iter()
+                .all(|answer| answer == "4.0.1")));
+        }
+        assert_eq!(session.version(), Some(Version::new(4, 0, 1)));
+    }
+}
@@ -0,0 +1,112 @@
+use crate::StderrFilter;
+use std::{path::PathBuf, sync::RwLock};
+
+static INSTALLED: RwLock<Option<ZenitySettings>> = RwLock::new(None);
+
+/// Serializes the crate's tests that change process-wide state, such as the installed settings
+/// or `PATH`, so that they don't observe each other's changes.
+#[cfg(test)]
+pub(crate) fn lock_for_test() -> std::sync::MutexGuard<'static, ()> {
+    static LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+    LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Crate-wide defaults applied to every dialog. Build the settings and make them active with
+/// [ZenitySettings::install]. Settings on an individual dialog always take precedence.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ZenitySettings {
+    /// The Zenity executable to run when a dialog doesn't specify one. Defaults to `zenity`,
+    /// looked up on `PATH`.
+    pub program: Option<PathBuf>,
+    /// The noise removed from Zenity's stderr before it's used to classify dialogs and reported
+    /// in errors. Defaults to removing harmless GTK and GLib warnings.
+    pub stderr_filter: StderrFilter,
+    /// Append every dialog shown with the blocking `show` methods to this file, as JSON lines.
+    /// See [ZenitySettings::record_to].
+    #[cfg(all(feature = "serde", unix))]
+    pub record_to: Option<PathBuf>,
+    /// Answer dialogs shown with the blocking `show` methods from this recording instead of
+    /// running Zenity. See [ZenitySettings::replay_from].
+    #[cfg(all(feature = "serde", unix))]
+    pub replay_from: Option<PathBuf>,
+}
+
+impl ZenitySettings {
+    /// The program run when neither the dialog nor the settings specify one.
+    pub(crate) const DEFAULT_PROGRAM: &'static str = "zenity";
+
+    /// The default settings.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Run the provided executable instead of `zenity`, for example a bundled copy.
+    pub fn with_program(mut self, program: impl Into<PathBuf>) -> Self {
+        self.program = Some(program.into());
+        self
+    }
+
+    /// Remove the noise matched by the filter from Zenity's stderr, instead of the harmless GTK
+    /// and GLib warnings removed by default. Pass [StderrFilter::none] to keep stderr
+    /// unchanged.
+    pub fn with_stderr_filter(mut self, stderr_filter: StderrFilter) -> Self {
+        self.stderr_filter = stderr_filter;
+        self
+    }
+
+    /// Record every dialog shown with the blocking `show` methods: the arguments it was run
+    /// with, after option names were resolved for the program's version, and its exit code,
+    /// stdout, and stderr, or whether the hard timeout killed it, are appended to the file as
+    /// one JSON object per line. Useful for capturing the interaction behind a bug report, to
+    /// be replayed with [ZenitySettings::replay_from].
+    #[cfg(all(feature = "serde", unix))]
+    pub fn record_to(mut self, path: impl Into<PathBuf>) -> Self {
+        self.record_to = Some(path.into());
+        self
+    }
+
+    /// Answer dialogs shown with the blocking `show` methods from a file written by
+    /// [ZenitySettings::record_to], in order, without running Zenity or checking for a display.
+    /// Each dialog must be shown with the same arguments as the recorded one, or it fails with
+    /// [crate::Error::ReplayMismatch]. Replay starts over whenever settings are installed.
+    /// Replayed dialogs aren't recorded again.
+    #[cfg(all(feature = "serde", unix))]
+    pub fn replay_from(mut self, path: impl Into<PathBuf>) -> Self {
+        self.replay_from = Some(path.into());
+        self
+    }
+
+    /// Make these settings the crate-wide defaults, replacing any previously installed settings.
+    pub fn install(self) {
+        #[cfg(all(feature = "serde", unix))]
+        crate::record::reset();
+        *INSTALLED
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(self);
+    }
+
+    /// Close every open dialog when this process receives SIGINT or SIGTERM, as when the user
+    /// hits Ctrl-C in the terminal, instead of leaving the windows open. The signal is then
+    /// handled as it would have been without forwarding, by the previously installed handler or
+    /// the default action. Installing forwarding again does nothing. Only has an effect on
+    /// Unix.
+    pub fn install_signal_forwarding() -> crate::Result<()> {
+        crate::signals::install().map_err(crate::Error::UnexpectedIoError)
+    }
+
+    /// The currently installed settings.
+    pub fn current() -> Self {
+        INSTALLED
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+            .unwrap_or_default()
+    }
+
+    /// The program to run, falling back to [ZenitySettings::DEFAULT_PROGRAM].
+    pub(crate) fn resolved_program(&self) -> PathBuf {
+        self.program
+            .clone()
+            .unwrap_or_else(|| Self::DEFAULT_PROGRAM.into())
+    }
+}
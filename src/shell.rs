@@ -0,0 +1,73 @@
+use std::borrow::Cow;
+
+/// Quote an argument so that a POSIX shell passes it through as a single word, unchanged.
+/// Arguments made up only of characters without special meaning are returned as-is.
+pub(crate) fn quote(arg: &str) -> Cow<'_, str> {
+    let is_safe = |c: char| c.is_ascii_alphanumeric() || "-_./=:,+@%".contains(c);
+
+    if !arg.is_empty() && arg.chars().all(is_safe) {
+        return Cow::Borrowed(arg);
+    }
+
+    // Inside single quotes nothing is special, so only single quotes themselves need escaping
+    // by closing the quote, emitting an escaped quote, and reopening it.
+    Cow::Owned(format!("'{}'", arg.replace('\'', r"'\''")))
+}
+
+/// Render a program and its arguments as a command line that can be pasted into a terminal.
+pub(crate) fn command_line<'a>(program: &str, args: impl IntoIterator<Item = &'a str>) -> String {
+    let mut line = quote(program).into_owned();
+    for arg in args {
+        line.push(' ');
+        line.push_str(&quote(arg));
+    }
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{command_line, quote};
+
+    const NASTY: &str = r#"--title=it's "quoted" $HOME"#;
+
+    #[test]
+    fn leaves_safe_arguments_unquoted() {
+        assert_eq!(quote("--width=300"), "--width=300");
+        assert_eq!(quote("/usr/bin/zenity"), "/usr/bin/zenity");
+    }
+
+    #[test]
+    fn quotes_spaces_quotes_and_dollars() {
+        assert_eq!(quote(NASTY), r#"'--title=it'\''s "quoted" $HOME'"#);
+        assert_eq!(quote("a b"), "'a b'");
+        assert_eq!(quote("$(reboot)"), "'$(reboot)'");
+        assert_eq!(quote("`reboot`"), "'`reboot`'");
+        assert_eq!(quote(""), "''");
+    }
+
+    #[test]
+    fn renders_the_program_before_its_arguments() {
+        assert_eq!(
+            command_line("zenity", ["--info", NASTY]),
+            r#"zenity --info '--title=it'\''s "quoted" $HOME'"#
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn the_shell_passes_quoted_arguments_through_unchanged() {
+        let args = [NASTY, "two  spaces", "new\nline", "back\\slash", "", "*"];
+        let line = command_line("printf", std::iter::once("%s|").chain(args.iter().copied()));
+
+        let output = std::process::Command::new("sh")
+            .arg("-c")
+            .arg(&line)
+            .env("HOME", "/home/expanded")
+            .output()
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(output.stdout).unwrap(),
+            args.join("|") + "|"
+        );
+    }
+}
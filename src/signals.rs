@@ -0,0 +1,397 @@
+use std::{
+    convert::TryFrom,
+    ptr,
+    sync::atomic::{AtomicI32, AtomicPtr, Ordering},
+};
+
+/// The process ids of the open dialogs.
+static DIALOGS: Registry = Registry::new();
+
+/// The number of slots in a block of the registry.
+const BLOCK_LEN: usize = 64;
+
+/// Process ids in a chain of fixed-size blocks of slots, which hold 0 when free. Atomics are used
+/// instead of a lock because the registry is read from a signal handler. Blocks are appended when
+/// every slot is taken and are only freed with the registry, so the handler can walk the chain
+/// without synchronizing with threads tracking dialogs.
+#[derive(Debug)]
+struct Registry {
+    head: Block,
+}
+
+#[derive(Debug)]
+struct Block {
+    slots: [AtomicI32; BLOCK_LEN],
+    next: AtomicPtr<Block>,
+}
+
+impl Block {
+    const fn new() -> Self {
+        Self {
+            slots: [const { AtomicI32::new(0) }; BLOCK_LEN],
+            next: AtomicPtr::new(ptr::null_mut()),
+        }
+    }
+
+    fn next(&self) -> Option<&Block> {
+        // SAFETY: the pointer is either null or points to a leaked block that lives as long as
+        // the registry.
+        unsafe { self.next.load(Ordering::SeqCst).as_ref() }
+    }
+}
+
+impl Registry {
+    const fn new() -> Self {
+        Self { head: Block::new() }
+    }
+
+    /// Store the process id in a free slot, appending a block if every slot is taken.
+    fn insert(&self, pid: i32) -> &AtomicI32 {
+        let mut block = &self.head;
+        loop {
+            if let Some(slot) = block.slots.iter().find(|slot| {
+                slot.compare_exchange(0, pid, Ordering::SeqCst, Ordering::SeqCst)
+                    .is_ok()
+            }) {
+                return slot;
+            }
+
+            block = match block.next() {
+                Some(next) => next,
+                None => {
+                    let appended = Box::into_raw(Box::new(Block::new()));
+                    match block.next.compare_exchange(
+                        ptr::null_mut(),
+                        appended,
+                        Ordering::SeqCst,
+                        Ordering::SeqCst,
+                    ) {
+                        // SAFETY: the block was just allocated and is now owned by the chain.
+                        Ok(_) => unsafe { &*appended },
+                        Err(_) => {
+                            // Another thread appended a block first, which the next iteration
+                            // moves on to.
+                            // SAFETY: the block was never shared.
+                            drop(unsafe { Box::from_raw(appended) });
+                            block
+                        }
+                    }
+                }
+            };
+        }
+    }
+
+    fn blocks(&self) -> impl Iterator<Item = &Block> {
+        std::iter::successors(Some(&self.head), |block| block.next())
+    }
+
+    /// The process ids currently stored. Doesn't allocate, so it can be called from a signal
+    /// handler.
+    fn pids(&self) -> impl Iterator<Item = i32> + '_ {
+        self.blocks()
+            .flat_map(|block| block.slots.iter())
+            .map(|slot| slot.load(Ordering::SeqCst))
+            .filter(|&pid| pid != 0)
+    }
+}
+
+impl Drop for Registry {
+    fn drop(&mut self) {
+        let mut next = *self.head.next.get_mut();
+        while !next.is_null() {
+            // SAFETY: appended blocks are owned by the chain, and nothing else can reach them
+            // once the registry is dropped.
+            let mut block = unsafe { Box::from_raw(next) };
+            next = *block.next.get_mut();
+        }
+    }
+}
+
+/// Registers an open dialog for signal forwarding as long as it's alive. The dialog must be
+/// unregistered as soon as it has been reaped, so that a reused process id isn't killed.
+#[derive(Debug)]
+pub(crate) struct Tracked {
+    slot: Option<&'static AtomicI32>,
+}
+
+impl Tracked {
+    /// Track the process. Process ids that don't fit a `pid_t` aren't tracked.
+    pub(crate) fn new(pid: u32) -> Self {
+        let slot = i32::try_from(pid)
+            .ok()
+            .filter(|&pid| pid > 0)
+            .map(|pid| DIALOGS.insert(pid));
+
+        Self { slot }
+    }
+}
+
+impl Drop for Tracked {
+    fn drop(&mut self) {
+        if let Some(slot) = self.slot {
+            slot.store(0, Ordering::SeqCst);
+        }
+    }
+}
+
+/// The process ids of the dialogs that are currently tracked.
+#[cfg(unix)]
+fn tracked() -> impl Iterator<Item = i32> {
+    DIALOGS.pids()
+}
+
+#[cfg(unix)]
+mod handler {
+    use std::{
+        io, mem, ptr,
+        sync::{Mutex, OnceLock},
+    };
+
+    const SIGNALS: [libc::c_int; 2] = [libc::SIGINT, libc::SIGTERM];
+
+    /// The dispositions the signals had before forwarding was installed. Only set once the
+    /// handler is installed for every signal.
+    static PREVIOUS: OnceLock<[libc::sigaction; 2]> = OnceLock::new();
+
+    /// Serializes installation, so that the dispositions saved are never the handler itself.
+    static INSTALLING: Mutex<()> = Mutex::new(());
+
+    /// Install the handler for SIGINT and SIGTERM. Installing it again does nothing. If it
+    /// can't be installed for every signal, the previous dispositions are restored and it can
+    /// be installed again later.
+    pub(crate) fn install() -> io::Result<()> {
+        let _installing = INSTALLING
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if PREVIOUS.get().is_none() {
+            let previous = replace_handlers(&SIGNALS)?;
+            let _ = PREVIOUS.set(previous);
+        }
+
+        Ok(())
+    }
+
+    /// Install [forward] for the signals, returning their previous dispositions. If that fails
+    /// for any signal, the signals already handled are restored.
+    pub(super) fn replace_handlers<const N: usize>(
+        signals: &[libc::c_int; N],
+    ) -> io::Result<[libc::sigaction; N]> {
+        // SAFETY: sigaction is plain data, and the handler only calls async-signal-safe
+        // functions.
+        unsafe {
+            let mut previous: [libc::sigaction; N] = mem::zeroed();
+            let mut action: libc::sigaction = mem::zeroed();
+            action.sa_sigaction = forward as *const () as libc::sighandler_t;
+            libc::sigemptyset(&mut action.sa_mask);
+
+            for (index, signal) in signals.iter().enumerate() {
+                if libc::sigaction(*signal, &action, &mut previous[index]) == -1 {
+                    let err = io::Error::last_os_error();
+                    for (signal, previous) in signals.iter().zip(&previous).take(index) {
+                        libc::sigaction(*signal, previous, ptr::null_mut());
+                    }
+                    return Err(err);
+                }
+            }
+
+            Ok(previous)
+        }
+    }
+
+    /// Kill every tracked dialog, then restore the previous disposition of the signal and
+    /// raise it again. Forwarding stays uninstalled afterwards, as the process is expected to
+    /// exit.
+    extern "C" fn forward(signal: libc::c_int) {
+        for pid in super::tracked() {
+            // SAFETY: kill, sigaction, and raise are async-signal-safe.
+            unsafe { libc::kill(pid, libc::SIGTERM) };
+        }
+
+        let previous = PREVIOUS.get().and_then(|previous| {
+            SIGNALS
+                .iter()
+                .position(|&s| s == signal)
+                .map(|i| previous[i])
+        });
+
+        unsafe {
+            match previous {
+                Some(previous) => libc::sigaction(signal, &previous, ptr::null_mut()),
+                // The signal arrived while forwarding was being installed, or isn't one it
+                // forwards.
+                None => {
+                    libc::signal(signal, libc::SIG_DFL);
+                    0
+                }
+            };
+            libc::raise(signal);
+        }
+    }
+}
+
+/// Install the signal forwarding handler. Does nothing on platforms without Unix signals.
+pub(crate) fn install() -> std::io::Result<()> {
+    #[cfg(unix)]
+    return handler::install();
+
+    #[cfg(not(unix))]
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Registry, BLOCK_LEN};
+    use std::{collections::BTreeSet, sync::atomic::Ordering, thread};
+
+    #[test]
+    fn tracks_more_dialogs_than_a_block_holds() {
+        let registry = Registry::new();
+        let pids: Vec<i32> = (1..=3 * BLOCK_LEN as i32 + 1).collect();
+        let slots: Vec<_> = pids.iter().map(|&pid| registry.insert(pid)).collect();
+
+        assert_eq!(registry.blocks().count(), 4);
+        assert_eq!(registry.pids().collect::<Vec<_>>(), pids);
+
+        for slot in slots {
+            slot.store(0, Ordering::SeqCst);
+        }
+        assert_eq!(registry.pids().count(), 0);
+    }
+
+    #[test]
+    fn freed_slots_are_reused_before_blocks_are_appended() {
+        let registry = Registry::new();
+        let slots: Vec<_> = (1..=2 * BLOCK_LEN as i32)
+            .map(|pid| registry.insert(pid))
+            .collect();
+        slots[3].store(0, Ordering::SeqCst);
+        slots[BLOCK_LEN + 5].store(0, Ordering::SeqCst);
+
+        assert!(std::ptr::eq(registry.insert(1000), slots[3]));
+        assert!(std::ptr::eq(registry.insert(1001), slots[BLOCK_LEN + 5]));
+        assert_eq!(registry.blocks().count(), 2);
+    }
+
+    #[test]
+    fn concurrent_dialogs_get_slots_of_their_own() {
+        let registry = Registry::new();
+        thread::scope(|scope| {
+            for thread in 0..8 {
+                let registry = &registry;
+                scope.spawn(move || {
+                    for pid in 1..=BLOCK_LEN as i32 {
+                        registry.insert(thread * 1000 + pid);
+                    }
+                });
+            }
+        });
+
+        let pids: BTreeSet<i32> = registry.pids().collect();
+        assert_eq!(pids.len(), 8 * BLOCK_LEN);
+        assert_eq!(registry.blocks().count(), 8);
+    }
+
+    #[test]
+    fn tracking_stops_when_dropped() {
+        // Large enough not to be a real process id.
+        let pid = i32::MAX - 7;
+        let tracked = super::Tracked::new(pid as u32);
+        assert!(super::DIALOGS.pids().any(|tracked| tracked == pid));
+
+        drop(tracked);
+        assert!(!super::DIALOGS.pids().any(|tracked| tracked == pid));
+    }
+
+    #[test]
+    fn process_ids_that_dont_fit_arent_tracked() {
+        assert!(super::Tracked::new(0).slot.is_none());
+        assert!(super::Tracked::new(u32::MAX).slot.is_none());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn failing_to_install_restores_the_signals_already_handled() {
+        use std::{mem, ptr};
+
+        let disposition = || unsafe {
+            let mut current: libc::sigaction = mem::zeroed();
+            libc::sigaction(libc::SIGUSR2, ptr::null(), &mut current);
+            current.sa_sigaction
+        };
+        let before = disposition();
+
+        // SIGKILL can't be handled.
+        let err = super::handler::replace_handlers(&[libc::SIGUSR2, libc::SIGKILL]).unwrap_err();
+        assert_eq!(err.raw_os_error(), Some(libc::EINVAL));
+        assert_eq!(disposition(), before);
+    }
+
+    /// Set in the helper process of `terminating_the_process_kills_tracked_dialogs`, to the
+    /// file it writes the id of its dialog to.
+    #[cfg(target_os = "linux")]
+    const HELPER: &str = "ZENITY_DIALOG_SIGNAL_HELPER";
+
+    /// Runs in a helper process: tracks a long-running stand-in for a dialog, then terminates
+    /// itself. Does nothing in the regular test run.
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn signal_forwarding_helper() {
+        let Some(pid_file) = std::env::var_os(HELPER) else {
+            return;
+        };
+
+        crate::ZenitySettings::install_signal_forwarding().unwrap();
+        let mut dialog = std::process::Command::new("sleep")
+            .arg("30")
+            .spawn()
+            .unwrap();
+        let _tracked = super::Tracked::new(dialog.id());
+        std::fs::write(pid_file, dialog.id().to_string()).unwrap();
+
+        unsafe { libc::raise(libc::SIGTERM) };
+        let _ = dialog.kill();
+        let _ = dialog.wait();
+        panic!("SIGTERM didn't terminate the process");
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn terminating_the_process_kills_tracked_dialogs() {
+        use std::{
+            os::unix::process::ExitStatusExt,
+            process::{Command, Stdio},
+            time::Duration,
+        };
+
+        let scripts = crate::harness::TestScripts::new().unwrap();
+        let pid_file = scripts.dir().join("dialog.pid");
+        let status = Command::new(std::env::current_exe().unwrap())
+            .args([
+                "--exact",
+                "signals::tests::signal_forwarding_helper",
+                "--test-threads=1",
+            ])
+            .env(HELPER, &pid_file)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .unwrap();
+        assert_eq!(status.signal(), Some(libc::SIGTERM), "{:?}", status);
+
+        // The orphaned dialog is reaped by init, so a zombie counts as killed.
+        let pid = std::fs::read_to_string(&pid_file).unwrap();
+        let alive = || {
+            std::fs::read_to_string(format!("/proc/{pid}/stat"))
+                .map(|stat| !stat.rsplit(')').next().unwrap_or("").starts_with(" Z"))
+                .unwrap_or(false)
+        };
+        for _ in 0..100 {
+            if !alive() {
+                return;
+            }
+            thread::sleep(Duration::from_millis(20));
+        }
+        panic!("the dialog {} is still running", pid);
+    }
+}
@@ -0,0 +1,186 @@
+use crate::backend::find_program;
+use std::{
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    thread,
+};
+
+/// A sound played alongside a dialog. See [crate::ZenityDialog::with_sound].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SoundCue {
+    /// The sound theme's error event
+    Error,
+    /// The sound theme's warning event
+    Warning,
+    /// The sound theme's information event
+    Info,
+    /// A path to a custom sound file
+    Custom(PathBuf),
+}
+
+impl SoundCue {
+    const THEME_DIR: &'static str = "/usr/share/sounds/freedesktop/stereo";
+    const THEME_EXTENSIONS: [&'static str; 3] = ["oga", "ogg", "wav"];
+
+    /// The freedesktop sound-theme event id for the cue.
+    fn event_id(&self) -> Option<&'static str> {
+        match self {
+            SoundCue::Error => Some("dialog-error"),
+            SoundCue::Warning => Some("dialog-warning"),
+            SoundCue::Info => Some("dialog-information"),
+            SoundCue::Custom(_) => None,
+        }
+    }
+
+    /// Start playing the sound without waiting for it to finish. This is best-effort: if no
+    /// player is available or the player can't be started, a warning is logged and the sound is
+    /// skipped.
+    pub(crate) fn play(&self) {
+        let Some(mut command) = self.player_command(find_program) else {
+            log::warn!(
+                "no sound player found for {self:?}: install canberra-gtk-play, paplay or aplay"
+            );
+            return;
+        };
+
+        let child = command
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn();
+
+        match child {
+            // Reap the player in the background so it doesn't linger as a zombie.
+            Ok(mut child) => {
+                thread::spawn(move || child.wait());
+            }
+            Err(err) => log::warn!("failed to start {:?}: {err}", command.get_program()),
+        }
+    }
+
+    /// Build the command for the first player that `find` locates.
+    fn player_command(&self, find: impl Fn(&str) -> Option<PathBuf>) -> Option<Command> {
+        if let Some(canberra) = find("canberra-gtk-play") {
+            let mut command = Command::new(canberra);
+            match self {
+                SoundCue::Custom(path) => command.arg("-f").arg(path),
+                other => command.arg("-i").arg(other.event_id()?),
+            };
+            return Some(command);
+        }
+
+        let file = self.sound_file()?;
+        let is_wav = file.extension().is_some_and(|ext| ext == "wav");
+        let player = find("paplay").or_else(|| match is_wav {
+            true => find("aplay"),
+            false => None,
+        })?;
+
+        let mut command = Command::new(player);
+        command.arg(file);
+        Some(command)
+    }
+
+    /// Resolve the sound file for the cue, looking up theme events in the freedesktop theme.
+    fn sound_file(&self) -> Option<PathBuf> {
+        match self {
+            SoundCue::Custom(path) => Some(path.clone()),
+            other => {
+                let event_id = other.event_id()?;
+                Self::THEME_EXTENSIONS
+                    .iter()
+                    .map(|ext| Path::new(Self::THEME_DIR).join(format!("{event_id}.{ext}")))
+                    .find(|path| path.is_file())
+            }
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::SoundCue;
+    use crate::{backend::find_program_in, harness::TestScripts};
+    use std::{ffi::OsStr, path::PathBuf};
+
+    /// A script that prints its arguments, one per line.
+    const PLAYER: &str = "#!/bin/sh\nprintf '%s\\n' \"$@\"\n";
+
+    /// The program and arguments of the player command, with only the scripts on `PATH`.
+    fn player(scripts: &TestScripts, cue: &SoundCue) -> Option<(PathBuf, Vec<String>)> {
+        let command =
+            cue.player_command(|name| find_program_in(name, scripts.dir().as_os_str()))?;
+        let args = command
+            .get_args()
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect();
+        Some((PathBuf::from(command.get_program()), args))
+    }
+
+    #[test]
+    fn prefers_canberra_with_the_theme_event() {
+        let scripts = TestScripts::new().unwrap();
+        let canberra = scripts.add("canberra-gtk-play", PLAYER).unwrap();
+        scripts.add("paplay", PLAYER).unwrap();
+
+        assert_eq!(
+            player(&scripts, &SoundCue::Error),
+            Some((
+                canberra.clone(),
+                vec!["-i".to_owned(), "dialog-error".to_owned()]
+            ))
+        );
+        assert_eq!(
+            player(&scripts, &SoundCue::Custom("/tmp/ding.ogg".into())),
+            Some((canberra, vec!["-f".to_owned(), "/tmp/ding.ogg".to_owned()]))
+        );
+    }
+
+    #[test]
+    fn falls_back_to_paplay_for_any_file() {
+        let scripts = TestScripts::new().unwrap();
+        let paplay = scripts.add("paplay", PLAYER).unwrap();
+        scripts.add("aplay", PLAYER).unwrap();
+
+        assert_eq!(
+            player(&scripts, &SoundCue::Custom("/tmp/ding.ogg".into())),
+            Some((paplay, vec!["/tmp/ding.ogg".to_owned()]))
+        );
+    }
+
+    #[test]
+    fn falls_back_to_aplay_only_for_wav_files() {
+        let scripts = TestScripts::new().unwrap();
+        let aplay = scripts.add("aplay", PLAYER).unwrap();
+
+        assert_eq!(
+            player(&scripts, &SoundCue::Custom("/tmp/ding.wav".into())),
+            Some((aplay, vec!["/tmp/ding.wav".to_owned()]))
+        );
+        assert_eq!(
+            player(&scripts, &SoundCue::Custom("/tmp/ding.ogg".into())),
+            None
+        );
+    }
+
+    #[test]
+    fn finds_no_player_on_an_empty_path() {
+        let scripts = TestScripts::new().unwrap();
+        assert_eq!(player(&scripts, &SoundCue::Warning), None);
+        assert!(SoundCue::Warning
+            .player_command(|name| find_program_in(name, OsStr::new("")))
+            .is_none());
+    }
+
+    #[test]
+    fn runs_the_player_with_the_cue() {
+        let scripts = TestScripts::new().unwrap();
+        scripts.add("canberra-gtk-play", PLAYER).unwrap();
+
+        let output = SoundCue::Info
+            .player_command(|name| find_program_in(name, scripts.dir().as_os_str()))
+            .unwrap()
+            .output()
+            .unwrap();
+        assert_eq!(output.stdout, b"-i\ndialog-information\n");
+    }
+}
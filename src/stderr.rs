@@ -0,0 +1,214 @@
+/// Removes harmless noise from what Zenity prints to stderr, such as GTK warnings about missing
+/// modules or themes, before it's used to classify the dialog or reported in errors. A line is
+/// removed when it contains any of the patterns. The unfiltered text stays available in
+/// [crate::RawOutput::stderr]. Configured crate-wide with
+/// [crate::ZenitySettings::with_stderr_filter].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct StderrFilter {
+    patterns: Vec<String>,
+}
+
+impl StderrFilter {
+    /// Patterns of warnings GTK, GLib, and the libraries below them commonly print without
+    /// affecting the dialog.
+    pub const BENIGN_PATTERNS: [&'static str; 10] = [
+        "Gtk-Message",
+        "Failed to load module",
+        "GtkDialog mapped without a transient parent",
+        "Theme parsing error",
+        "Unable to locate theme engine",
+        "Locale not supported by C library",
+        "Couldn't connect to accessibility bus",
+        "dbind-WARNING",
+        "Fontconfig warning",
+        "libEGL warning",
+    ];
+
+    /// A filter that keeps every line.
+    pub fn none() -> Self {
+        Self {
+            patterns: Vec::new(),
+        }
+    }
+
+    /// Also remove lines containing the pattern.
+    pub fn with_pattern(mut self, pattern: impl Into<String>) -> Self {
+        self.patterns.push(pattern.into());
+        self
+    }
+
+    /// The patterns whose lines are removed.
+    pub fn patterns(&self) -> impl Iterator<Item = &str> {
+        self.patterns.iter().map(String::as_str)
+    }
+
+    /// Remove the matching lines from the text, along with the blank lines GLib prints before
+    /// its warnings. Text left with only whitespace is emptied.
+    ///
+    /// ```
+    /// # use zenity_dialog::StderrFilter;
+    /// let stderr = "Gtk-Message: 10:42:01.337: Failed to load module \"canberra-gtk-module\"\n\
+    ///               This option is not available.\n";
+    /// assert_eq!(StderrFilter::default().apply(stderr), "This option is not available.\n");
+    /// ```
+    pub fn apply(&self, stderr: &str) -> String {
+        let mut filtered = String::new();
+        let mut blank = String::new();
+        for line in stderr.split_inclusive('\n') {
+            if line.trim().is_empty() {
+                blank.push_str(line);
+            } else if self.patterns.iter().any(|pattern| line.contains(pattern)) {
+                blank.clear();
+            } else {
+                filtered.push_str(&blank);
+                filtered.push_str(line);
+                blank.clear();
+            }
+        }
+        filtered.push_str(&blank);
+
+        if filtered.trim().is_empty() {
+            filtered.clear();
+        }
+
+        filtered
+    }
+}
+
+/// Removes the [StderrFilter::BENIGN_PATTERNS].
+impl Default for StderrFilter {
+    fn default() -> Self {
+        Self {
+            patterns: Self::BENIGN_PATTERNS.map(String::from).to_vec(),
+        }
+    }
+}
+
+/// Decode stderr lossily and remove the noise matched by the installed filter.
+pub(crate) fn filtered(stderr: &[u8]) -> String {
+    crate::ZenitySettings::current()
+        .stderr_filter
+        .apply(&String::from_utf8_lossy(stderr))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StderrFilter;
+
+    /// Noise captured from Zenity 3 and 4 on desktops missing optional modules and themes,
+    /// with the blank lines GLib prints before its warnings.
+    const NOISE: &str = "Gtk-Message: 10:42:01.337: Failed to load module \"canberra-gtk-module\"\n\
+        Gtk-Message: 10:42:01.338: Failed to load module \"atk-bridge\"\n\
+        \n\
+        (zenity:48213): Gtk-WARNING **: 10:42:01.412: Theme parsing error: gtk.css:68:35: The style property GtkButton:child-displacement-x is deprecated and shouldn't be used anymore. It will be removed in a future version\n\
+        Gtk-Message: 10:42:01.501: GtkDialog mapped without a transient parent. This is discouraged.\n\
+        \n\
+        (zenity:48213): dbind-WARNING **: 10:42:01.522: Couldn't connect to accessibility bus: Failed to connect to socket /run/user/1000/at-spi/bus_0: No such file or directory\n\
+        Fontconfig warning: \"/etc/fonts/fonts.conf\", line 5: unknown element \"description\"\n\
+        libEGL warning: DRI3: Screen seems not DRI3 capable\n\
+        (zenity:48213): Gtk-WARNING **: 10:42:01.530: Locale not supported by C library.\n\
+        \tUsing the fallback 'C' locale.\n";
+
+    #[test]
+    fn removes_real_world_gtk_noise() {
+        assert_eq!(
+            StderrFilter::default().apply(NOISE),
+            // The continuation of a warning doesn't repeat its pattern.
+            "\tUsing the fallback 'C' locale.\n"
+        );
+    }
+
+    #[test]
+    fn keeps_errors_between_the_noise() {
+        let stderr = format!(
+            "{NOISE}\n(zenity:48213): Gtk-WARNING **: 10:42:02.001: cannot open display: :99\n"
+        );
+        assert_eq!(
+            StderrFilter::default().apply(&stderr),
+            "\tUsing the fallback 'C' locale.\n\n\
+             (zenity:48213): Gtk-WARNING **: 10:42:02.001: cannot open display: :99\n"
+        );
+    }
+
+    #[test]
+    fn drops_blank_lines_before_removed_warnings_only() {
+        let filter = StderrFilter::none().with_pattern("dbind-WARNING");
+        assert_eq!(
+            filter.apply("first\n\n(zenity:1): dbind-WARNING **: no bus\n\nsecond\n"),
+            "first\n\nsecond\n"
+        );
+        assert_eq!(
+            filter.apply("\n\n(zenity:1): dbind-WARNING **: no bus\n"),
+            ""
+        );
+        assert_eq!(filter.apply("  \n\t\n"), "");
+        assert_eq!(filter.apply("no trailing newline"), "no trailing newline");
+    }
+
+    #[test]
+    fn a_filter_without_patterns_keeps_everything_but_whitespace() {
+        assert_eq!(StderrFilter::none().apply(NOISE), NOISE);
+        assert_eq!(StderrFilter::none().apply("\n\n"), "");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn raw_output_keeps_the_unfiltered_text() {
+        use crate::RawOutput;
+        use std::{
+            os::unix::process::ExitStatusExt,
+            process::{ExitStatus, Output},
+        };
+
+        let _lock = crate::settings::lock_for_test();
+        let stderr = format!("{NOISE}This option is not available.\n");
+        let raw = RawOutput::from(&Output {
+            status: ExitStatus::from_raw(255 << 8),
+            stdout: Vec::new(),
+            stderr: stderr.clone().into_bytes(),
+        });
+
+        assert_eq!(raw.stderr, stderr);
+        assert_eq!(
+            raw.filtered_stderr,
+            "\tUsing the fallback 'C' locale.\nThis option is not available.\n"
+        );
+    }
+
+    #[cfg(all(unix, feature = "entry"))]
+    #[test]
+    fn dialogs_are_classified_with_the_filtered_text() {
+        use crate::{
+            dialog::Entry,
+            runner::{MockResponse, MockRunner},
+            ZenityDialog, ZenityOutput,
+        };
+
+        let _lock = crate::settings::lock_for_test();
+        let noise = NOISE.replace("\tUsing the fallback 'C' locale.\n", "");
+        let dialog = ZenityDialog::new(Entry::new()).allow_headless();
+        let show = |response: MockResponse| {
+            dialog.show_with_runner(&MockRunner::new().respond_otherwise(response))
+        };
+
+        match show(MockResponse::new(255).with_stderr(format!("{noise}Invalid option\n"))) {
+            Err(crate::Error::ZenityFailed { exit_code, stderr }) => {
+                assert_eq!((exit_code, stderr.as_str()), (255, "Invalid option\n"))
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        assert_eq!(
+            show(MockResponse::new(42).with_stderr(noise.clone())).unwrap(),
+            ZenityOutput::Unknown {
+                exit_code: 42,
+                stdout: String::new(),
+                stderr: String::new(),
+            }
+        );
+        assert!(matches!(
+            show(MockResponse::affirmed("Ada").with_stderr(noise)).unwrap(),
+            ZenityOutput::Affirmed { content: Some(_) }
+        ));
+    }
+}
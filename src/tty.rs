@@ -0,0 +1,176 @@
+use crate::{markup_to_plain, PlainMarkupStyle};
+use std::io::{self, BufRead, IsTerminal, Write};
+
+/// How an application is rendered in a terminal when no display is available. See
+/// [crate::dialog::ZenityApplication::tty_prompt].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum TtyPrompt {
+    /// Print a message. The dialog is affirmed without content.
+    Message {
+        /// The message to print
+        text: Option<String>,
+    },
+    /// Read a line of input. End of input rejects the dialog.
+    Line {
+        /// The prompt printed before reading
+        text: Option<String>,
+        /// The answer used when the user submits an empty line
+        default: Option<String>,
+        /// Whether to disable terminal echo while reading
+        secret: bool,
+    },
+}
+
+impl TtyPrompt {
+    /// Strip Pango markup from the text, for applications that render it as markup.
+    pub(crate) fn plain(text: &str, markup: bool) -> String {
+        match markup {
+            true => markup_to_plain(text, PlainMarkupStyle::Strip),
+            false => text.to_owned(),
+        }
+    }
+}
+
+/// Whether the user can be prompted in the terminal: stdin and stderr are both terminals.
+pub(crate) fn is_interactive() -> bool {
+    io::stdin().is_terminal() && io::stderr().is_terminal()
+}
+
+/// Render the prompt on the process's stdin and stderr, returning the exit code and stdout
+/// Zenity would have produced.
+pub(crate) fn render_terminal(
+    prompt: &TtyPrompt,
+    title: Option<&str>,
+) -> io::Result<(i32, String)> {
+    let secret = matches!(prompt, TtyPrompt::Line { secret: true, .. });
+    let _echo = match secret {
+        true => EchoGuard::disable(),
+        false => None,
+    };
+
+    let result = render(prompt, title, &mut io::stdin().lock(), &mut io::stderr());
+    if secret {
+        // The user's newline wasn't echoed.
+        eprintln!();
+    }
+    result
+}
+
+/// Render the prompt on the provided streams, returning the exit code and stdout Zenity would
+/// have produced.
+pub(crate) fn render(
+    prompt: &TtyPrompt,
+    title: Option<&str>,
+    input: &mut impl BufRead,
+    output: &mut impl Write,
+) -> io::Result<(i32, String)> {
+    let title = title.filter(|title| !title.is_empty());
+
+    match prompt {
+        TtyPrompt::Message { text } => {
+            match (title, text.as_deref()) {
+                (Some(title), Some(text)) => writeln!(output, "{title}: {text}")?,
+                (Some(line), None) | (None, Some(line)) => writeln!(output, "{line}")?,
+                (None, None) => {}
+            }
+            output.flush()?;
+            Ok((0, String::new()))
+        }
+        TtyPrompt::Line { text, default, .. } => {
+            if let Some(title) = title {
+                writeln!(output, "{title}")?;
+            }
+            write!(output, "{}", text.as_deref().unwrap_or(">"))?;
+            if let Some(default) = default {
+                write!(output, " [{default}]")?;
+            }
+            write!(output, " ")?;
+            output.flush()?;
+
+            let mut line = String::new();
+            if input.read_line(&mut line)? == 0 {
+                return Ok((1, String::new()));
+            }
+
+            let line = line.trim_end_matches(['\r', '\n']);
+            let answer = match (line.is_empty(), default) {
+                (true, Some(default)) => default.clone(),
+                _ => line.to_owned(),
+            };
+            Ok((0, answer))
+        }
+    }
+}
+
+/// Disables echo on stdin for as long as it's alive.
+struct EchoGuard {
+    original: libc::termios,
+}
+
+impl EchoGuard {
+    fn disable() -> Option<Self> {
+        // SAFETY: termios is plain data, and tcgetattr fully initializes it on success.
+        let mut original: libc::termios = unsafe { std::mem::zeroed() };
+        if unsafe { libc::tcgetattr(libc::STDIN_FILENO, &mut original) } != 0 {
+            return None;
+        }
+
+        let mut silent = original;
+        silent.c_lflag &= !libc::ECHO;
+        if unsafe { libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &silent) } != 0 {
+            return None;
+        }
+
+        Some(Self { original })
+    }
+}
+
+impl Drop for EchoGuard {
+    fn drop(&mut self) {
+        unsafe { libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &self.original) };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{render, TtyPrompt};
+    #[cfg(feature = "entry")]
+    use crate::dialog::Entry;
+    use crate::dialog::{Info, ZenityApplication};
+
+    fn printed(prompt: &TtyPrompt, input: &str) -> String {
+        let mut output = Vec::new();
+        render(prompt, None, &mut input.as_bytes(), &mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn markup_is_printed_as_plain_text() {
+        let info = Info::new().with_text("<b>Disk</b> full &amp; <i>read-only</i>");
+        assert_eq!(
+            printed(&info.tty_prompt().unwrap(), ""),
+            "Disk full & read-only\n"
+        );
+    }
+
+    #[cfg(feature = "entry")]
+    #[test]
+    fn prompts_print_markup_as_plain_text() {
+        let entry = Entry::new().with_text("Your <b>name</b>?");
+        assert_eq!(
+            printed(&entry.tty_prompt().unwrap(), "Ada\n"),
+            "Your name? "
+        );
+    }
+
+    #[test]
+    fn text_that_isnt_markup_is_printed_as_typed() {
+        let text = "a <b> & c";
+        for info in [
+            Info::new().with_text(text).with_no_markup(),
+            Info::new().with_text(text).with_escape_text(),
+        ] {
+            assert_eq!(printed(&info.tty_prompt().unwrap(), ""), "a <b> & c\n");
+        }
+    }
+}
@@ -0,0 +1,273 @@
+use std::fmt::Display;
+
+/// A problem with a dialog's configuration that Zenity would silently ignore or misinterpret.
+/// See [crate::ZenityDialog::validate].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ValidationError {
+    /// The name of the offending field, as in `width` or `day`.
+    pub field: &'static str,
+    /// Why the value is invalid.
+    pub reason: String,
+}
+
+impl ValidationError {
+    /// A problem with the field.
+    pub fn new(field: &'static str, reason: impl Into<String>) -> Self {
+        Self {
+            field,
+            reason: reason.into(),
+        }
+    }
+}
+
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.field, self.reason)
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+#[cfg(test)]
+mod tests {
+    use super::ValidationError;
+    #[cfg(feature = "list")]
+    use crate::dialog::List;
+    #[cfg(feature = "question")]
+    use crate::dialog::Question;
+    #[cfg(feature = "calendar")]
+    use crate::dialog::{Calendar, Month};
+    #[cfg(feature = "list")]
+    use crate::runner::{MockResponse, MockRunner};
+    use crate::{
+        dialog::{Info, ZenityApplication},
+        Arg, ZenityDialog,
+    };
+    use std::time::Duration;
+
+    /// The fields of the problems found with the dialog, in the order they're reported.
+    fn invalid_fields<T: ZenityApplication>(dialog: ZenityDialog<T>) -> Vec<&'static str> {
+        match dialog.validate() {
+            Ok(()) => Vec::new(),
+            Err(errors) => errors.iter().map(|error| error.field).collect(),
+        }
+    }
+
+    /// The reason of the only problem found with the dialog.
+    fn reason<T: ZenityApplication>(dialog: ZenityDialog<T>) -> String {
+        let mut errors = dialog.validate().unwrap_err();
+        assert_eq!(errors.len(), 1, "{:?}", errors);
+        errors.remove(0).reason
+    }
+
+    #[test]
+    fn a_default_dialog_is_valid() {
+        assert_eq!(ZenityDialog::new(Info::new()).validate(), Ok(()));
+    }
+
+    #[test]
+    fn sizes_must_be_greater_than_zero() {
+        assert_eq!(
+            invalid_fields(ZenityDialog::new(Info::new()).with_width(0)),
+            ["width"]
+        );
+        assert_eq!(
+            invalid_fields(ZenityDialog::new(Info::new()).with_height(0)),
+            ["height"]
+        );
+        assert!(
+            invalid_fields(ZenityDialog::new(Info::new()).with_width(1).with_height(1)).is_empty()
+        );
+    }
+
+    #[test]
+    fn a_timeout_must_be_at_least_a_second() {
+        let dialog = ZenityDialog::new(Info::new());
+        assert_eq!(
+            invalid_fields(dialog.clone().with_timeout(Duration::from_millis(999))),
+            ["timeout"]
+        );
+        assert_eq!(
+            invalid_fields(dialog.clone().with_timeout(Duration::ZERO)),
+            ["timeout"]
+        );
+        assert!(invalid_fields(dialog.with_timeout(Duration::from_secs(1))).is_empty());
+    }
+
+    #[test]
+    fn a_hard_timeout_must_be_greater_than_zero() {
+        let dialog = ZenityDialog::new(Info::new());
+        assert_eq!(
+            invalid_fields(dialog.clone().with_hard_timeout(Duration::ZERO)),
+            ["hard_timeout"]
+        );
+        assert!(invalid_fields(dialog.with_hard_timeout(Duration::from_millis(1))).is_empty());
+    }
+
+    #[test]
+    fn additional_arguments_must_have_a_plain_name() {
+        for (name, problem) in [
+            ("", "the name is empty"),
+            ("--", "the name is empty"),
+            ("ok label", "the name contains whitespace"),
+            ("ok-label=Go", "the name contains `=`"),
+        ] {
+            let dialog = ZenityDialog::new(Info::new()).with_additional_arg(Arg::flag(name));
+            assert!(reason(dialog).starts_with(problem), "{:?}", name);
+        }
+
+        let dialog = ZenityDialog::new(Info::new()).with_additional_arg(Arg::flag("--no-wrap"));
+        assert!(invalid_fields(dialog).is_empty());
+    }
+
+    #[cfg(feature = "question")]
+    #[test]
+    fn a_switch_needs_a_button_and_no_labels() {
+        let dialog = ZenityDialog::new(Question::new().with_switch(Vec::<String>::new()));
+        assert_eq!(invalid_fields(dialog), ["switch"]);
+
+        let dialog = ZenityDialog::new(Question::new().with_switch(["Save"]).with_ok_label("Go"));
+        assert!(reason(dialog).contains("labels are ignored"));
+
+        let dialog = ZenityDialog::new(Question::new().with_switch(["Save"]));
+        assert!(invalid_fields(dialog).is_empty());
+    }
+
+    #[cfg(feature = "calendar")]
+    #[test]
+    fn a_day_must_exist_in_the_month() {
+        let calendar = |day: usize, month: Month| {
+            ZenityDialog::new(Calendar::new().with_day(day).with_month(month))
+        };
+
+        assert_eq!(invalid_fields(calendar(0, Month::May)), ["day"]);
+        assert_eq!(invalid_fields(calendar(32, Month::May)), ["day"]);
+        assert_eq!(reason(calendar(31, Month::April)), "April has no day 31");
+        assert_eq!(
+            reason(calendar(30, Month::February)),
+            "February has no day 30"
+        );
+        assert!(invalid_fields(calendar(31, Month::May)).is_empty());
+        // Without a year, February 29th may exist.
+        assert!(invalid_fields(calendar(29, Month::February)).is_empty());
+    }
+
+    #[cfg(feature = "calendar")]
+    #[test]
+    fn a_year_must_be_one_zenity_shows() {
+        assert_eq!(
+            invalid_fields(ZenityDialog::new(Calendar::new().with_year(0isize))),
+            ["year"]
+        );
+        assert_eq!(
+            invalid_fields(ZenityDialog::new(Calendar::new().with_year(10_000isize))),
+            ["year"]
+        );
+        assert!(invalid_fields(ZenityDialog::new(Calendar::new().with_year(9999isize))).is_empty());
+
+        // An invalid year is reported once, not also as a missing February 29th.
+        let dialog = ZenityDialog::new(
+            Calendar::new()
+                .with_day(29usize)
+                .with_month(Month::February)
+                .with_year(0isize),
+        );
+        assert_eq!(invalid_fields(dialog), ["year"]);
+    }
+
+    #[cfg(feature = "calendar")]
+    #[test]
+    fn a_date_format_must_be_parsed_back() {
+        let dialog = ZenityDialog::new(Calendar::new().with_format("%B %d"));
+        assert_eq!(invalid_fields(dialog), ["format"]);
+
+        let dialog = ZenityDialog::new(Calendar::new().with_format(Calendar::DEFAULT_DATE_FORMAT));
+        assert!(invalid_fields(dialog).is_empty());
+    }
+
+    #[cfg(feature = "list")]
+    #[test]
+    fn a_list_needs_a_column() {
+        assert_eq!(invalid_fields(ZenityDialog::new(List::new())), ["columns"]);
+    }
+
+    #[cfg(feature = "list")]
+    #[test]
+    fn every_row_must_fill_the_columns() {
+        let list = List::new()
+            .with_column("Name")
+            .with_column("Size")
+            .with_row(["a", "1"])
+            .with_row(["b"]);
+        assert_eq!(
+            reason(ZenityDialog::new(list)),
+            "row 1 has 1 cells, but the list has 2 columns"
+        );
+    }
+
+    #[cfg(feature = "list")]
+    #[test]
+    fn printed_and_hidden_columns_must_exist() {
+        let list = List::new().with_column("Name").with_column("Size");
+        assert_eq!(
+            invalid_fields(ZenityDialog::new(list.clone().with_print_column(0))),
+            ["print_column"]
+        );
+        assert_eq!(
+            invalid_fields(ZenityDialog::new(list.clone().with_hide_column(3))),
+            ["hide_column"]
+        );
+        assert!(invalid_fields(ZenityDialog::new(
+            list.with_print_column(2).with_hide_column(1)
+        ))
+        .is_empty());
+    }
+
+    #[cfg(feature = "list")]
+    #[test]
+    fn a_list_cant_be_both_a_checklist_and_a_radiolist() {
+        let list = List::new()
+            .with_column("")
+            .with_column("Name")
+            .with_checklist()
+            .with_radiolist();
+        assert_eq!(invalid_fields(ZenityDialog::new(list)), ["radiolist"]);
+    }
+
+    #[cfg(feature = "list")]
+    #[test]
+    fn every_problem_is_reported_at_once() {
+        let list = List::new().with_checklist().with_radiolist();
+        let dialog = ZenityDialog::new(list)
+            .with_width(0)
+            .with_timeout(Duration::ZERO)
+            .with_additional_arg(Arg::flag(""));
+
+        assert_eq!(
+            invalid_fields(dialog.clone()),
+            [
+                "width",
+                "timeout",
+                "additional_args",
+                "columns",
+                "radiolist"
+            ]
+        );
+
+        // Showing the dialog fails with all of them, without running Zenity.
+        let runner = MockRunner::new().respond_otherwise(MockResponse::affirmed(""));
+        match dialog.show_with_runner(&runner) {
+            Err(crate::Error::InvalidDialog(errors)) => assert_eq!(errors.len(), 5),
+            result => panic!("{:?}", result),
+        }
+        assert!(runner.invocations().is_empty());
+    }
+
+    #[test]
+    fn an_error_names_the_field_and_the_reason() {
+        assert_eq!(
+            ValidationError::new("width", "must be greater than zero").to_string(),
+            "width: must be greater than zero"
+        );
+    }
+}
@@ -0,0 +1,317 @@
+use crate::{probe, ZenitySettings};
+use std::{
+    ffi::{OsStr, OsString},
+    fmt::Display,
+    path::Path,
+};
+
+static VERSIONS: probe::ProbeCache<Version> = probe::ProbeCache::new();
+
+/// A Zenity version, as reported by `zenity --version`. Versions compare by major, minor, and
+/// patch number, in that order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Version {
+    /// The major version. Zenity 4 is the GTK 4 and libadwaita rewrite.
+    pub major: u32,
+    /// The minor version
+    pub minor: u32,
+    /// The patch version. Zero if the version doesn't have one.
+    pub patch: u32,
+}
+
+impl Version {
+    /// Create a version from its parts.
+    pub const fn new(major: u32, minor: u32, patch: u32) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+        }
+    }
+
+    /// Parse the output of `zenity --version`, as in `3.44.0`. Surrounding whitespace and
+    /// distribution suffixes, as in `3.44.0-1`, are ignored. Returns [None] if the output
+    /// doesn't start with a version number.
+    pub fn parse(output: &str) -> Option<Self> {
+        let output = output.trim();
+        let end = output
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .unwrap_or(output.len());
+
+        let mut parts = output[..end]
+            .split('.')
+            .map(|part| part.parse::<u32>().ok());
+        let major = parts.next()??;
+        let minor = parts.next().unwrap_or(Some(0))?;
+        let patch = parts.next().unwrap_or(Some(0))?;
+
+        Some(Self::new(major, minor, patch))
+    }
+}
+
+impl Display for Version {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// The version of the Zenity executable configured in the installed [ZenitySettings]. The
+/// result is cached per program path.
+pub fn zenity_version() -> crate::Result<Version> {
+    program_version(ZenitySettings::current().resolved_program())
+}
+
+/// The version of the provided Zenity executable, as reported by `--version`. The result is
+/// cached per program path, including failures.
+pub fn program_version(program: impl AsRef<Path>) -> crate::Result<Version> {
+    VERSIONS.get_or_probe(program.as_ref(), |program| {
+        let stdout = probe::run(program, "--version")?;
+        Version::parse(&stdout).ok_or_else(|| {
+            crate::Error::ParseResultFailure(crate::ParseError::new(
+                "version",
+                stdout.clone(),
+                format!("{} printed an unrecognized version", program.display()),
+            ))
+        })
+    })
+}
+
+/// Drop the cached version of the program, so that it's probed again on next use.
+pub(crate) fn forget_version(program: &Path) {
+    VERSIONS.forget(program);
+}
+
+/// Which generation of Zenity's option names to emit.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub(crate) enum FlagStyle {
+    /// Detect the style from the installed version, falling back to [FlagStyle::Legacy].
+    #[default]
+    Auto,
+    /// The option names of Zenity 3 and earlier.
+    Legacy,
+    /// The option names of Zenity 4, the libadwaita rewrite.
+    Modern,
+}
+
+impl FlagStyle {
+    /// Options renamed in Zenity 4, as pairs of the legacy and the modern name.
+    const RENAMED: [(&'static str, &'static str); 1] = [("--icon-name", "--icon")];
+
+    /// Whether two option names, given without the leading dashes, name the same option in
+    /// some Zenity version, as `icon` and `icon-name` do.
+    pub(crate) fn same_option(a: &str, b: &str) -> bool {
+        let canonical = |name: &str| {
+            Self::RENAMED
+                .iter()
+                .find(|&&(legacy, modern)| name == &legacy[2..] || name == &modern[2..])
+                .map_or(name.to_owned(), |&(legacy, _)| legacy[2..].to_owned())
+        };
+        canonical(a) == canonical(b)
+    }
+
+    /// Resolve [FlagStyle::Auto] by probing the version of the program. If the version can't be
+    /// determined, the legacy names are used, as they're still accepted by Zenity 4.
+    pub(crate) fn resolve(self, program: &Path) -> Self {
+        match self {
+            FlagStyle::Auto => Self::for_version(program_version(program)),
+            style => style,
+        }
+    }
+
+    /// Like [FlagStyle::resolve], but without running the program: [FlagStyle::Auto] only
+    /// follows a version that was already probed, and uses the legacy names otherwise.
+    pub(crate) fn resolve_cached(self, program: &Path) -> Self {
+        match self {
+            FlagStyle::Auto => match VERSIONS.cached(program) {
+                Some(version) => Self::for_version(version),
+                None => FlagStyle::Legacy,
+            },
+            style => style,
+        }
+    }
+
+    fn for_version(version: crate::Result<Version>) -> Self {
+        match version {
+            Ok(version) if version.major >= 4 => FlagStyle::Modern,
+            _ => FlagStyle::Legacy,
+        }
+    }
+
+    /// Rename the options in `args` to this style. Values are preserved, and arguments that
+    /// aren't renamed pass through unchanged, as do all arguments for an unresolved
+    /// [FlagStyle::Auto].
+    pub(crate) fn translate(self, args: Vec<OsString>) -> Vec<OsString> {
+        args.into_iter()
+            .map(|arg| self.translate_arg(arg))
+            .collect()
+    }
+
+    fn translate_arg(self, arg: OsString) -> OsString {
+        let Some((name, value)) = split_option(&arg) else {
+            return arg;
+        };
+
+        let renamed = Self::RENAMED
+            .iter()
+            .find_map(|&(legacy, modern)| match self {
+                FlagStyle::Modern if name == legacy => Some(modern),
+                FlagStyle::Legacy if name == modern => Some(legacy),
+                _ => None,
+            });
+
+        match (renamed, value) {
+            (Some(name), Some(value)) => {
+                let mut renamed = OsString::from(format!("{name}="));
+                renamed.push(value);
+                renamed
+            }
+            (Some(name), None) => name.into(),
+            (None, _) => arg,
+        }
+    }
+}
+
+/// Split an argument into its name and value, as in `--icon` and `x.png` for `--icon=x.png`.
+/// Only the name must be valid UTF-8, so that paths that aren't are still translated. On other
+/// platforms than Unix, arguments that aren't valid UTF-8 aren't split.
+fn split_option(arg: &OsStr) -> Option<(&str, Option<&OsStr>)> {
+    if let Some(text) = arg.to_str() {
+        return Some(match text.split_once('=') {
+            Some((name, value)) => (name, Some(OsStr::new(value))),
+            None => (text, None),
+        });
+    }
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStrExt;
+
+        let bytes = arg.as_bytes();
+        let split = bytes.iter().position(|&byte| byte == b'=')?;
+        let name = std::str::from_utf8(&bytes[..split]).ok()?;
+        Some((name, Some(OsStr::from_bytes(&bytes[split + 1..]))))
+    }
+
+    #[cfg(not(unix))]
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{program_version, FlagStyle, Version};
+    use std::ffi::OsString;
+
+    fn args(args: &[&str]) -> Vec<OsString> {
+        args.iter().map(OsString::from).collect()
+    }
+
+    #[test]
+    fn parses_versions_with_suffixes_and_missing_parts() {
+        assert_eq!(Version::parse("3.44.0\n"), Some(Version::new(3, 44, 0)));
+        assert_eq!(
+            Version::parse("4.0.1-1ubuntu1"),
+            Some(Version::new(4, 0, 1))
+        );
+        assert_eq!(Version::parse("4.1"), Some(Version::new(4, 1, 0)));
+        assert_eq!(Version::parse("zenity 4"), None);
+        assert!(Version::new(3, 44, 0) < Version::new(4, 0, 0));
+    }
+
+    #[test]
+    fn the_modern_style_renames_legacy_options() {
+        assert_eq!(
+            FlagStyle::Modern.translate(args(&[
+                "--info",
+                "--icon-name=dialog-warning",
+                "--icon-name",
+                "--icon=kept",
+                "--text=--icon-name=x",
+            ])),
+            args(&[
+                "--info",
+                "--icon=dialog-warning",
+                "--icon",
+                "--icon=kept",
+                "--text=--icon-name=x",
+            ])
+        );
+    }
+
+    #[test]
+    fn the_legacy_style_renames_modern_options() {
+        assert_eq!(
+            FlagStyle::Legacy.translate(args(&[
+                "--info",
+                "--icon=dialog-warning",
+                "--icon",
+                "--icon-name=kept",
+                "--window-icon=x.png",
+            ])),
+            args(&[
+                "--info",
+                "--icon-name=dialog-warning",
+                "--icon-name",
+                "--icon-name=kept",
+                "--window-icon=x.png",
+            ])
+        );
+    }
+
+    #[test]
+    fn an_unresolved_style_renames_nothing() {
+        let unchanged = args(&["--icon=a", "--icon-name=b"]);
+        assert_eq!(FlagStyle::Auto.translate(unchanged.clone()), unchanged);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn translates_values_that_arent_utf8() {
+        use std::os::unix::ffi::{OsStrExt, OsStringExt};
+
+        let arg = OsString::from_vec(b"--icon-name=/tmp/caf\xe9.png".to_vec());
+        let translated = FlagStyle::Modern.translate(vec![arg]);
+        assert_eq!(translated[0].as_bytes(), b"--icon=/tmp/caf\xe9.png");
+    }
+
+    #[test]
+    fn renamed_options_are_the_same_option() {
+        assert!(FlagStyle::same_option("icon", "icon-name"));
+        assert!(FlagStyle::same_option("icon-name", "icon"));
+        assert!(FlagStyle::same_option("icon", "icon"));
+        assert!(FlagStyle::same_option("width", "width"));
+        assert!(!FlagStyle::same_option("icon", "window-icon"));
+        assert!(!FlagStyle::same_option("width", "height"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn the_style_follows_the_version_unless_forced() {
+        let scripts = crate::harness::TestScripts::new().unwrap();
+        let zenity_3 = scripts.add("zenity-3", "#!/bin/sh\necho 3.44.0\n").unwrap();
+        let zenity_4 = scripts.add("zenity-4", "#!/bin/sh\necho 4.0.1\n").unwrap();
+        let missing = scripts.dir().join("missing");
+
+        // Without a probed version, nothing is run and the legacy names are used.
+        for program in [&zenity_3, &zenity_4, &missing] {
+            assert_eq!(FlagStyle::Auto.resolve_cached(program), FlagStyle::Legacy);
+        }
+
+        assert_eq!(FlagStyle::Auto.resolve(&zenity_3), FlagStyle::Legacy);
+        assert_eq!(FlagStyle::Auto.resolve(&zenity_4), FlagStyle::Modern);
+        assert_eq!(FlagStyle::Auto.resolve(&missing), FlagStyle::Legacy);
+
+        // Once probed, the cached version is followed.
+        assert_eq!(program_version(&zenity_4).unwrap(), Version::new(4, 0, 1));
+        assert_eq!(FlagStyle::Auto.resolve_cached(&zenity_3), FlagStyle::Legacy);
+        assert_eq!(FlagStyle::Auto.resolve_cached(&zenity_4), FlagStyle::Modern);
+        assert_eq!(FlagStyle::Auto.resolve_cached(&missing), FlagStyle::Legacy);
+
+        // A forced style ignores the version.
+        for program in [&zenity_3, &zenity_4, &missing] {
+            for style in [FlagStyle::Legacy, FlagStyle::Modern] {
+                assert_eq!(style.resolve(program), style);
+                assert_eq!(style.resolve_cached(program), style);
+            }
+        }
+    }
+}